@@ -0,0 +1,124 @@
+// Formatting a RoughTime with write_english(), which allocates nothing,
+// against to_string(), which allocates a String for every call. A counting
+// allocator checks the allocations before the timings are taken:
+//
+//     cargo bench --bench formatting
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Write};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use enums_patterns::rough_time::{RoughTime, TimeUnit};
+
+// The system allocator, counting every allocation made through it.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// A fixed buffer on the stack, as an embedded program might write to.
+struct Buffer {
+    bytes: [u8; 64],
+    len: usize
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn times() -> Vec<RoughTime> {
+    let mut times = vec![RoughTime::JustNow];
+    for &units in &TimeUnit::ALL {
+        for &count in &[1, 2, 45, 1_000_000] {
+            times.push(RoughTime::InThePast(units, count));
+            times.push(RoughTime::InTheFuture(units, count));
+        }
+    }
+    times
+}
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn formatting(c: &mut Criterion) {
+    let times = times();
+    let mut reused = String::with_capacity(64);
+
+    let to_string = allocations(|| {
+        for rt in &times {
+            black_box(rt.to_string());
+        }
+    });
+    let into_buffer = allocations(|| {
+        for rt in &times {
+            let mut buffer = Buffer { bytes: [0; 64], len: 0 };
+            rt.write_english(&mut buffer).unwrap();
+            black_box(&buffer.bytes[..buffer.len]);
+        }
+    });
+    let into_string = allocations(|| {
+        for rt in &times {
+            reused.clear();
+            rt.write_english(&mut reused).unwrap();
+            black_box(&reused);
+        }
+    });
+    println!("allocations formatting {} times:", times.len());
+    println!("    to_string()                         {}", to_string);
+    println!("    write_english() to a buffer         {}", into_buffer);
+    println!("    write_english() to a reused String  {}", into_string);
+    assert_eq!(into_buffer, 0);
+    assert_eq!(into_string, 0);
+
+    c.bench_function("to_string", |b| {
+        b.iter(|| {
+            for rt in &times {
+                black_box(black_box(rt).to_string());
+            }
+        })
+    });
+    c.bench_function("write_english to a buffer", |b| {
+        b.iter(|| {
+            for rt in &times {
+                let mut buffer = Buffer { bytes: [0; 64], len: 0 };
+                black_box(rt).write_english(&mut buffer).unwrap();
+                black_box(&buffer.bytes[..buffer.len]);
+            }
+        })
+    });
+    c.bench_function("write_english to a reused String", |b| {
+        b.iter(|| {
+            for rt in &times {
+                reused.clear();
+                black_box(rt).write_english(&mut reused).unwrap();
+                black_box(&reused);
+            }
+        })
+    });
+}
+
+criterion_group!(benches, formatting);
+criterion_main!(benches);
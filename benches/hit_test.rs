@@ -0,0 +1,79 @@
+// Hit testing a document of 100,000 shapes, through the quadtree that
+// Document::shape_at() uses and, for comparison, by checking every shape
+// from the top down as it did before the index.
+//
+//     cargo bench --bench hit_test
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use enums_patterns::geometry::{Document, Point, Rect, Shape};
+
+const SHAPES: usize = 100_000;
+const CANVAS: f64 = 10_000.0;
+
+// A small linear congruential generator, so every run places the same
+// shapes without pulling in a crate for it.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self.0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Rectangles and ellipses up to 50 units across, scattered over the canvas.
+fn document() -> Document {
+    let mut random = Lcg(1);
+    let mut document = Document::new();
+    for i in 0..SHAPES {
+        let center = Point::new(random.next() * CANVAS, random.next() * CANVAS);
+        let (w, h) = (1.0 + random.next() * 49.0, 1.0 + random.next() * 49.0);
+        let shape = if i % 2 == 0 {
+            Shape::Rect(Rect::new(center - Point::new(w / 2.0, h / 2.0),
+                                  center + Point::new(w / 2.0, h / 2.0)))
+        } else {
+            let rotation = random.next();
+            Shape::Ellipse { center, radius_x: w / 2.0, radius_y: h / 2.0, rotation }
+        };
+        document.add(shape);
+    }
+    document
+}
+
+fn points() -> Vec<Point> {
+    let mut random = Lcg(2);
+    (0..1_000).map(|_| Point::new(random.next() * CANVAS, random.next() * CANVAS)).collect()
+}
+
+fn hit_test(c: &mut Criterion) {
+    let document = document();
+    let points = points();
+
+    // Each scan takes a couple of seconds, so fewer samples than the
+    // default 100 will do.
+    let mut group = c.benchmark_group("100k shapes, 1000 points");
+    group.sample_size(10);
+    group.bench_function("shape_at", |b| {
+        b.iter(|| {
+            for &p in &points {
+                black_box(document.shape_at(black_box(p)));
+            }
+        })
+    });
+    group.bench_function("linear scan", |b| {
+        b.iter(|| {
+            for &p in &points {
+                let p = black_box(p);
+                black_box(document.shapes().rev().find(|(_, shape)| shape.contains_point(p)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hit_test);
+criterion_main!(benches);
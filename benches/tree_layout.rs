@@ -0,0 +1,101 @@
+// BinaryTree's links against the Option<Box<TreeNode>> representation it
+// was proposed to switch to: their sizes, and how fast each takes inserts.
+// The Option version here is a copy of add() with only the representation
+// changed, so any difference comes from the layout. ArenaTree, which keeps
+// every node in one Vec, is measured alongside, on lookups as well.
+//
+//     cargo bench --bench tree_layout
+
+use std::hint::black_box;
+use std::mem;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use enums_patterns::binary_tree::{ArenaTree, BinaryTree, TreeNode};
+
+struct Node<T> {
+    element: T,
+    left: Link<T>,
+    right: Link<T>,
+    size: usize
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+fn add<T: Ord>(root: &mut Link<T>, value: T) {
+    let mut link = root;
+    while let Some(ref mut node) = *link {
+        node.size += 1;
+        link = if value <= node.element { &mut node.left } else { &mut node.right };
+    }
+    *link = Some(Box::new(Node { element: value, left: None, right: None, size: 1 }));
+}
+
+// Random-looking values, the same on every run, so the trees stay bushy.
+fn values(len: usize) -> Vec<u64> {
+    let mut state = 1u64;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            state >> 16
+        })
+        .collect()
+}
+
+fn tree_layout(c: &mut Criterion) {
+    println!("size of a link:");
+    println!("    BinaryTree<u64>               {} bytes", mem::size_of::<BinaryTree<u64>>());
+    println!("    Option<Box<Node<u64>>>        {} bytes", mem::size_of::<Link<u64>>());
+    println!("size of a node holding a u64:");
+    println!("    TreeNode<u64>                 {} bytes", mem::size_of::<TreeNode<u64>>());
+    println!("    Node<u64>                     {} bytes", mem::size_of::<Node<u64>>());
+
+    let values = values(100_000);
+    let mut group = c.benchmark_group("insert 100k u64s");
+    group.sample_size(20);
+    group.bench_function("BinaryTree", |b| {
+        b.iter_batched(BinaryTree::new,
+                       |mut tree| {
+                           for &value in &values {
+                               tree.add(black_box(value));
+                           }
+                           tree
+                       },
+                       BatchSize::LargeInput)
+    });
+    group.bench_function("Option<Box<Node>>", |b| {
+        b.iter_batched(|| None,
+                       |mut tree| {
+                           for &value in &values {
+                               add(&mut tree, black_box(value));
+                           }
+                           tree
+                       },
+                       BatchSize::LargeInput)
+    });
+    group.bench_function("ArenaTree", |b| {
+        b.iter_batched(ArenaTree::new,
+                       |mut tree| {
+                           for &value in &values {
+                               tree.add(black_box(value));
+                           }
+                           tree
+                       },
+                       BatchSize::LargeInput)
+    });
+    group.finish();
+
+    let binary: BinaryTree<u64> = values.iter().cloned().collect();
+    let arena: ArenaTree<u64> = values.iter().cloned().collect();
+    let mut group = c.benchmark_group("look up 100k u64s");
+    group.sample_size(20);
+    group.bench_function("BinaryTree", |b| {
+        b.iter(|| values.iter().filter(|value| binary.contains(black_box(value))).count())
+    });
+    group.bench_function("ArenaTree", |b| {
+        b.iter(|| values.iter().filter(|value| arena.contains(black_box(value))).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tree_layout);
+criterion_main!(benches);
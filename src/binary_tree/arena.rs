@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+use core::mem;
+
+// The same kind of tree as BinaryTree, but instead of each node being its own
+// heap allocation, all the nodes live side by side in one Vec and refer to
+// their children by index. Walking the tree then touches memory that is far
+// more likely to already be in cache, and adding an element usually doesn't
+// allocate at all.
+//
+// Removing an element leaves a hole in the Vec. Holes are chained together
+// into a free list and reused by later adds, so a tree that shrinks and grows
+// again doesn't keep growing its storage.
+pub struct ArenaTree<T> {
+    slots: Vec<Slot<T>>,
+    root: Link,
+    free: Link,
+    len: usize
+}
+
+// The index of a node in `slots`, or None for an empty subtree.
+type Link = Option<usize>;
+
+enum Slot<T> {
+    Occupied(ArenaNode<T>),
+    // A hole, linking to the next hole in the free list.
+    Vacant(Link)
+}
+
+struct ArenaNode<T> {
+    element: T,
+    left: Link,
+    right: Link
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right
+}
+
+impl<T> ArenaTree<T> {
+    pub fn new() -> ArenaTree<T> {
+        ArenaTree::with_capacity(0)
+    }
+
+    // Reserves room for `capacity` elements up front, for bulk loading.
+    pub fn with_capacity(capacity: usize) -> ArenaTree<T> {
+        ArenaTree { slots: Vec::with_capacity(capacity), root: None, free: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.root = None;
+        self.free = None;
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { tree: self, stack: Vec::new() };
+        iter.push_left_edge(self.root);
+        iter
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut index = self.root?;
+        while let Some(right) = self.node(index).right {
+            index = right;
+        }
+        Some(&self.node(index).element)
+    }
+
+    fn node(&self, index: usize) -> &ArenaNode<T> {
+        match self.slots[index] {
+            Slot::Occupied(ref node) => node,
+            Slot::Vacant(_) => panic!("tree link points at a free slot")
+        }
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut ArenaNode<T> {
+        match self.slots[index] {
+            Slot::Occupied(ref mut node) => node,
+            Slot::Vacant(_) => panic!("tree link points at a free slot")
+        }
+    }
+
+    // Stores a new node, in a hole from the free list if there is one.
+    fn allocate(&mut self, element: T) -> usize {
+        let node = Slot::Occupied(ArenaNode { element, left: None, right: None });
+        match self.free {
+            Some(index) => {
+                self.free = match self.slots[index] {
+                    Slot::Vacant(next) => next,
+                    Slot::Occupied(_) => panic!("free list points at a live node")
+                };
+                self.slots[index] = node;
+                index
+            }
+            None => {
+                self.slots.push(node);
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    // Turns a node's slot into a hole, returning its element.
+    fn release(&mut self, index: usize) -> T {
+        match mem::replace(&mut self.slots[index], Slot::Vacant(self.free)) {
+            Slot::Occupied(node) => {
+                self.free = Some(index);
+                node.element
+            }
+            Slot::Vacant(_) => panic!("released a free slot")
+        }
+    }
+
+    // Points the link from `parent` on the given side, or the root link if
+    // there is no parent, at `child`.
+    fn set_link(&mut self, parent: Option<(usize, Side)>, child: Link) {
+        match parent {
+            None => self.root = child,
+            Some((index, Side::Left)) => self.node_mut(index).left = child,
+            Some((index, Side::Right)) => self.node_mut(index).right = child
+        }
+    }
+}
+
+impl<T: Ord> ArenaTree<T> {
+    pub fn add(&mut self, value: T) {
+        let mut parent = None;
+        let mut link = self.root;
+        while let Some(index) = link {
+            let node = self.node(index);
+            if value <= node.element {
+                parent = Some((index, Side::Left));
+                link = node.left;
+            } else {
+                parent = Some((index, Side::Right));
+                link = node.right;
+            }
+        }
+
+        let index = self.allocate(value);
+        self.set_link(parent, Some(index));
+        self.len += 1;
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    // Finds the node holding `value`, along with the link that points to it.
+    fn find(&self, value: &T) -> Option<(usize, Option<(usize, Side)>)> {
+        let mut parent = None;
+        let mut link = self.root;
+        while let Some(index) = link {
+            let node = self.node(index);
+            match value.cmp(&node.element) {
+                Ordering::Less => {
+                    parent = Some((index, Side::Left));
+                    link = node.left;
+                }
+                Ordering::Equal =>
+                    return Some((index, parent)),
+                Ordering::Greater => {
+                    parent = Some((index, Side::Right));
+                    link = node.right;
+                }
+            }
+        }
+        None
+    }
+
+    // Removes one occurrence of `value`, returning whether there was one.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (index, parent) = match self.find(value) {
+            Some(found) => found,
+            None => return false
+        };
+
+        let (left, right) = {
+            let node = self.node(index);
+            (node.left, node.right)
+        };
+        match (left, right) {
+            (None, child) | (child, None) => {
+                self.set_link(parent, child);
+                self.release(index);
+            }
+            (Some(_), Some(right)) => {
+                // Two children: unlink the smallest node of the right subtree
+                // and move its element up into this node, whose links all stay
+                // as they are.
+                let mut successor_parent = (index, Side::Right);
+                let mut successor = right;
+                while let Some(left) = self.node(successor).left {
+                    successor_parent = (successor, Side::Left);
+                    successor = left;
+                }
+                let successor_right = self.node(successor).right;
+                self.set_link(Some(successor_parent), successor_right);
+                let element = self.release(successor);
+                self.node_mut(index).element = element;
+            }
+        }
+
+        self.len -= 1;
+        true
+    }
+}
+
+impl<T> Default for ArenaTree<T> {
+    fn default() -> ArenaTree<T> {
+        ArenaTree::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for ArenaTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> ArenaTree<T> {
+        let iter = iter.into_iter();
+        let mut tree = ArenaTree::with_capacity(iter.size_hint().0);
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for ArenaTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+// In-order iterator. The stack holds indices rather than references, but
+// otherwise this is BinaryTree's Iter.
+pub struct Iter<'a, T> {
+    tree: &'a ArenaTree<T>,
+    stack: Vec<usize>
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_edge(&mut self, mut link: Link) {
+        while let Some(index) = link {
+            self.stack.push(index);
+            link = self.tree.node(index).left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let index = self.stack.pop()?;
+        let node = self.tree.node(index);
+        self.push_left_edge(node.right);
+        Some(&node.element)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks the free list and the tree between them account for every slot
+    // exactly once, and that the tree is in order.
+    fn check(tree: &ArenaTree<u32>) {
+        let mut holes = 0;
+        let mut link = tree.free;
+        while let Some(index) = link {
+            holes += 1;
+            link = match tree.slots[index] {
+                Slot::Vacant(next) => next,
+                Slot::Occupied(_) => panic!("free list reaches a live node")
+            };
+        }
+        assert_eq!(tree.len() + holes, tree.slots.len());
+        let elements: Vec<_> = tree.iter().collect();
+        assert_eq!(elements.len(), tree.len());
+        assert!(elements.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn add_contains_and_remove() {
+        let mut tree: ArenaTree<u32> = [5, 2, 8, 1, 3, 7, 9, 5].iter().cloned().collect();
+        check(&tree);
+        assert_eq!(tree.len(), 8);
+        assert!(tree.contains(&3) && tree.contains(&9));
+        assert!(!tree.contains(&4));
+        assert_eq!((tree.min(), tree.max()), (Some(&1), Some(&9)));
+
+        // A leaf, a node with one child, the root with two, and a duplicate.
+        for value in [1, 2, 5, 5] {
+            assert!(tree.remove(&value), "{}", value);
+            check(&tree);
+        }
+        assert!(!tree.remove(&5));
+        assert!(!tree.remove(&4));
+        assert!(tree.iter().cloned().eq([3, 7, 8, 9]));
+    }
+
+    #[test]
+    fn iterates_in_order() {
+        let values = [50, 20, 80, 10, 30, 70, 90, 25, 35, 20, 85];
+        let tree: ArenaTree<u32> = values.iter().cloned().collect();
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        assert!(tree.iter().cloned().eq(sorted));
+        assert!((&tree).into_iter().eq(tree.iter()));
+        assert_eq!(ArenaTree::<u32>::new().iter().next(), None);
+    }
+
+    // Holes left by removals are filled before the Vec grows again.
+    #[test]
+    fn removed_slots_are_reused() {
+        let mut tree: ArenaTree<u32> = (0..100).collect();
+        let capacity = tree.slots.len();
+        for value in (0..100).filter(|value| value % 3 == 0) {
+            assert!(tree.remove(&value));
+        }
+        check(&tree);
+        assert_eq!(tree.slots.len(), capacity);
+
+        for value in 100..134 {
+            tree.add(value);
+        }
+        check(&tree);
+        assert_eq!(tree.len(), 100);
+        assert_eq!(tree.slots.len(), capacity);
+        assert!(tree.free.is_none());
+
+        tree.add(134);
+        assert_eq!(tree.slots.len(), capacity + 1);
+    }
+
+    #[test]
+    fn clear_empties_the_storage() {
+        let mut tree: ArenaTree<u32> = (0..10).collect();
+        tree.remove(&3);
+        tree.clear();
+        assert!(tree.is_empty());
+        assert!(tree.slots.is_empty() && tree.free.is_none());
+        tree.add(1);
+        check(&tree);
+    }
+}
@@ -0,0 +1,192 @@
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::ops::RangeBounds;
+
+use super::iter::{below_start, within_end};
+use super::{BinaryTree, IntoIter, TreeNode};
+
+// Operations that move whole subtrees at once instead of adding or removing
+// elements one at a time.
+
+impl<T: Ord> BinaryTree<T> {
+    // Splits the tree in two: elements less than or equal to `key` stay in
+    // this tree, and everything greater is moved into the returned tree.
+    // Only the nodes on the search path for `key` are touched, so this takes
+    // time proportional to the height of the tree, not its size.
+    pub fn split(&mut self, key: &T) -> BinaryTree<T> {
+        self.split_where(|element| element <= key)
+    }
+
+    // Removes every element within `range` and returns them as a tree of
+    // their own. We split off everything from the start of the range onwards,
+    // split that again at the end of the range, and join what lies below the
+    // range back onto what lies above it. Each step only touches one path
+    // through the tree, so apart from the work of joining, which is bounded
+    // by the height too, the elements in the range are never visited.
+    pub fn extract_range<R: RangeBounds<T>>(&mut self, range: R) -> BinaryTree<T> {
+        let mut extracted = self.split_where(|element| below_start(&range, element));
+        let above = extracted.split_where(|element| within_end(&range, element));
+        let below = core::mem::take(self);
+        *self = below.join(above);
+        extracted
+    }
+
+    // Splits the tree at the point where `low` stops holding: elements for
+    // which it returns true stay, and the rest move into the returned tree.
+    // `low` must hold for some prefix of the elements in sorted order and
+    // for none after it.
+    fn split_where<F>(&mut self, low: F) -> BinaryTree<T>
+        where F: Fn(&T) -> bool
+    {
+        // Walk down the search path, detaching each node from the subtree we
+        // continue into. A node that belongs on the low side keeps its left
+        // subtree, which is entirely low too; a node on the high side keeps
+        // its right subtree.
+        let mut path = Vec::new();
+        let mut rest = self.take_node();
+        while let Some(mut node) = rest {
+            let low = low(&node.element);
+            rest = if low {
+                node.right.take_node()
+            } else {
+                node.left.take_node()
+            };
+            path.push((node, low));
+        }
+
+        // Reassemble bottom-up. Each low node takes the low tree built so far
+        // as its new right subtree, since everything deeper on the path is
+        // greater than it; high nodes likewise take the high tree on their
+        // left.
+        let mut low_tree = BinaryTree::Empty;
+        let mut high_tree = BinaryTree::Empty;
+        while let Some((mut node, low)) = path.pop() {
+            if low {
+                node.right = low_tree;
+                node.update_size();
+                low_tree = BinaryTree::NonEmpty(node);
+            } else {
+                node.left = high_tree;
+                node.update_size();
+                high_tree = BinaryTree::NonEmpty(node);
+            }
+        }
+
+        *self = low_tree;
+        high_tree
+    }
+
+    // Merges two trees into one containing every element of both. Like add(),
+    // this keeps duplicates: an element present in both trees appears twice.
+    //
+    // Since both trees can already be read out in sorted order, we merge the
+    // two sorted sequences and build a perfectly balanced tree from the result
+    // as it streams past. That is O(n + m), against O(m log(n + m)) or worse
+    // for adding the elements of one tree to the other, and the result is
+    // balanced no matter what shape the inputs were.
+    pub fn union(self, other: BinaryTree<T>) -> BinaryTree<T> {
+        let len = self.len() + other.len();
+        let mut merged = Merge {
+            a: self.into_iter().peekable(),
+            b: other.into_iter().peekable()
+        };
+        BinaryTree::build_balanced(len, &mut merged)
+    }
+}
+
+impl<T> BinaryTree<T> {
+    // Removes every element for which `keep` returns false. The survivors are
+    // already in sorted order as they come out of the tree, so rather than
+    // removing the failures one by one, at O(height) each, we rebuild from
+    // the survivors. That always takes O(n) time and a Vec of the survivors,
+    // even when nothing is removed, and always leaves the tree balanced: its
+    // old shape is not kept, even if `keep` holds for every element.
+    pub fn retain<F>(&mut self, mut keep: F)
+        where F: FnMut(&T) -> bool
+    {
+        let kept: Vec<T> = self.drain().filter(|element| keep(element)).collect();
+        let len = kept.len();
+        *self = BinaryTree::build_balanced(len, &mut kept.into_iter());
+    }
+
+    // Applies `f` to every element, producing a new tree. The mapped values
+    // come out in the order of the original elements; if `f` is monotonic,
+    // that is already sorted, and the standard library's sort recognizes a
+    // sorted run in a single O(n) pass. Otherwise it costs one O(n log n)
+    // sort, which still beats adding the values one at a time into what may
+    // be an unbalanced tree. Either way the result is balanced.
+    pub fn map<U, F>(self, f: F) -> BinaryTree<U>
+        where U: Ord, F: FnMut(T) -> U
+    {
+        let mut mapped: Vec<U> = self.into_iter().map(f).collect();
+        mapped.sort();
+        let len = mapped.len();
+        BinaryTree::build_balanced(len, &mut mapped.into_iter())
+    }
+
+    // Joins two trees where every element of `self` is less than or equal to
+    // every element of `high`. The smallest element of `high` becomes the new
+    // root, with the two trees as its subtrees. The result is no better
+    // balanced than its inputs, but the join costs only the height of `high`.
+    fn join(self, mut high: BinaryTree<T>) -> BinaryTree<T> {
+        if self.is_empty() {
+            return high;
+        }
+        match high.pop_min() {
+            Some(root) => BinaryTree::node(root, self, high),
+            None => self
+        }
+    }
+}
+
+// Merges two sorted sequences into one. On ties, elements from `a` come first.
+struct Merge<T: Ord> {
+    a: Peekable<IntoIter<T>>,
+    b: Peekable<IntoIter<T>>
+}
+
+impl<T: Ord> Iterator for Merge<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let take_a = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => a <= b,
+            (Some(_), None) => true,
+            (None, _) => false
+        };
+        if take_a {
+            self.a.next()
+        } else {
+            self.b.next()
+        }
+    }
+}
+
+impl<T> TreeNode<T> {
+    fn update_size(&mut self) {
+        self.size = 1 + self.left.len() + self.right.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retain_always_rebalances() {
+        let mut tree: BinaryTree<u32> = (0..15).collect();
+        assert_eq!(tree.height(), 15);
+        tree.retain(|_| true);
+        assert!(tree.iter().cloned().eq(0..15));
+        assert_eq!(tree.height(), 4);
+        assert!(tree.is_balanced());
+
+        tree.retain(|&element| element % 3 != 0);
+        assert!(tree.iter().cloned().eq((0..15).filter(|element| element % 3 != 0)));
+        assert_eq!(tree.len(), 10);
+        assert!(tree.is_balanced());
+
+        tree.retain(|_| false);
+        assert!(tree.is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+use std::io::{self, Read, Write};
+
+use super::preorder::{OutOfOrder, PreorderBuilder};
+use super::BinaryTree;
+
+// A compact binary format for trees, for when serde and a text format are too
+// bulky. The layout is:
+//
+//     b"BTRE"              magic number
+//     version: u8          currently 1
+//     len: u64             number of nodes, little-endian
+//     len node records, in pre-order
+//
+// and each node record is one flags byte, with bit 0 set if the node has a
+// left child and bit 1 set if it has a right child, followed by the element
+// as encoded by its BinaryCodec impl. Compared with the serde encoding, which
+// spends a token on every empty subtree, this costs one byte per node on top
+// of the elements.
+//
+// Both directions use an explicit stack, so trees of any depth can be
+// written and read back. The reader and writer are used as given, one small
+// read or write at a time, so wrap files and sockets in a BufReader or
+// BufWriter.
+
+const MAGIC: &[u8; 4] = b"BTRE";
+const VERSION: u8 = 1;
+
+const HAS_LEFT: u8 = 0b01;
+const HAS_RIGHT: u8 = 0b10;
+
+// How to write a single element and read it back. Implemented for the
+// integer types, bool, char and String; implement it for your own element
+// types to store trees of them.
+pub trait BinaryCodec: Sized {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+// Integers are written little-endian at their full width. usize and isize
+// are always written as 64 bits, so a tree written on one platform can be
+// read on another.
+macro_rules! impl_binary_codec_for_int {
+    ($($t:ty => $wire:ty),*) => {
+        $(
+            impl BinaryCodec for $t {
+                fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&(*self as $wire).to_le_bytes())
+                }
+
+                fn decode<R: Read>(reader: &mut R) -> io::Result<$t> {
+                    let mut bytes = [0; std::mem::size_of::<$wire>()];
+                    reader.read_exact(&mut bytes)?;
+                    let value = <$wire>::from_le_bytes(bytes);
+                    <$t as std::convert::TryFrom<$wire>>::try_from(value)
+                        .map_err(|_| invalid_data("integer out of range for this platform"))
+                }
+            }
+        )*
+    }
+}
+
+impl_binary_codec_for_int! {
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => u64,
+    i8 => i8, i16 => i16, i32 => i32, i64 => i64, i128 => i128, isize => i64
+}
+
+impl BinaryCodec for bool {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u8).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<bool> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid bool"))
+        }
+    }
+}
+
+impl BinaryCodec for char {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u32).encode(writer)
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<char> {
+        std::char::from_u32(u32::decode(reader)?)
+            .ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+// A string is its length in bytes, as a u64, followed by its UTF-8.
+impl BinaryCodec for String {
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().encode(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<String> {
+        let len = usize::decode(reader)?;
+        // Read through take() rather than allocating `len` bytes up front, so
+        // a corrupt length can't make us allocate an enormous buffer.
+        let mut bytes = Vec::new();
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "string cut short"));
+        }
+        String::from_utf8(bytes).map_err(|_| invalid_data("string is not valid UTF-8"))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn out_of_order(_: OutOfOrder) -> io::Error {
+    invalid_data("tree elements are out of order")
+}
+
+impl<T: BinaryCodec> BinaryTree<T> {
+    // Writes the tree to `writer` in the format described above.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        VERSION.encode(&mut writer)?;
+        self.len().encode(&mut writer)?;
+
+        let mut stack = Vec::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            stack.push(node);
+        }
+        while let Some(node) = stack.pop() {
+            let mut flags = 0;
+            if let BinaryTree::NonEmpty(ref right) = node.right {
+                flags |= HAS_RIGHT;
+                stack.push(right);
+            }
+            if let BinaryTree::NonEmpty(ref left) = node.left {
+                flags |= HAS_LEFT;
+                stack.push(left);
+            }
+            flags.encode(&mut writer)?;
+            node.element.encode(&mut writer)?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl<T: BinaryCodec + Ord> BinaryTree<T> {
+    // Reads back a tree written by write_to(), with exactly the shape it had.
+    // Fails with ErrorKind::InvalidData if the input isn't a tree in this
+    // format or its elements aren't in order, and ErrorKind::UnexpectedEof if
+    // it ends too soon.
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<BinaryTree<T>> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != *MAGIC {
+            return Err(invalid_data("not a binary tree file"));
+        }
+        let version = u8::decode(&mut reader)?;
+        if version != VERSION {
+            return Err(invalid_data("unsupported binary tree format version"));
+        }
+        let len = u64::decode(&mut reader)?;
+
+        // The node records say only which children exist, but the builder
+        // wants the pre-order walk spelled out, with a token for every empty
+        // subtree. So we keep a stack of the subtrees still to come, each
+        // marked with whether it has a node: a missing child turns into a
+        // None token at the point in the walk where it would have appeared.
+        let mut builder = PreorderBuilder::new();
+        let mut pending = vec![len > 0];
+        let mut read = 0;
+        while let Some(has_node) = pending.pop() {
+            if !has_node {
+                if let Some(tree) = builder.push_in_order(None).map_err(out_of_order)? {
+                    if read != len {
+                        return Err(invalid_data("node count doesn't match header"));
+                    }
+                    return Ok(tree);
+                }
+                continue;
+            }
+
+            let flags = u8::decode(&mut reader)?;
+            if flags & !(HAS_LEFT | HAS_RIGHT) != 0 {
+                return Err(invalid_data("invalid node flags"));
+            }
+            if read == len {
+                return Err(invalid_data("node count doesn't match header"));
+            }
+            read += 1;
+            builder.push_in_order(Some(T::decode(&mut reader)?)).map_err(out_of_order)?;
+            pending.push(flags & HAS_RIGHT != 0);
+            pending.push(flags & HAS_LEFT != 0);
+        }
+
+        unreachable!("the builder finishes the tree when the last subtree closes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(tree: &BinaryTree<u32>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        tree.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    // The header for a tree of `len` nodes, followed by a node record for
+    // each of `nodes`.
+    fn records(len: u64, nodes: &[(u8, u32)]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&len.to_le_bytes());
+        for &(flags, element) in nodes {
+            bytes.push(flags);
+            bytes.extend_from_slice(&element.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn round_trip_keeps_the_shape() {
+        let tree: BinaryTree<u32> = [2, 1, 3, 3, 0].iter().cloned().collect();
+        let bytes = encode(&tree);
+        let back = BinaryTree::<u32>::read_from(&bytes[..]).unwrap();
+        assert_eq!(encode(&back), bytes);
+        assert!(back.iter().cloned().eq([0, 1, 2, 3, 3]));
+        assert_eq!(BinaryTree::<u32>::read_from(&encode(&BinaryTree::new())[..]).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn out_of_order_elements_are_rejected() {
+        let both = HAS_LEFT | HAS_RIGHT;
+        for bytes in [records(3, &[(both, 2), (0, 3), (0, 1)]),
+                      records(2, &[(HAS_RIGHT, 2), (0, 1)]),
+                      records(3, &[(HAS_LEFT, 5), (HAS_RIGHT, 1), (0, 6)])] {
+            let error = BinaryTree::<u32>::read_from(&bytes[..]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(error.to_string(), "tree elements are out of order");
+        }
+        let equal = records(3, &[(HAS_LEFT | HAS_RIGHT, 2), (0, 2), (0, 2)]);
+        assert_eq!(BinaryTree::<u32>::read_from(&equal[..]).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn a_chain_deeper_than_the_stack() {
+        let len = 100_000;
+        let nodes: Vec<_> = (0..len).map(|element| (HAS_RIGHT, element)).collect();
+        let mut bytes = records(u64::from(len), &nodes);
+        let last = bytes.len() - 5;
+        bytes[last] = 0;
+        let tree = BinaryTree::<u32>::read_from(&bytes[..]).unwrap();
+        assert_eq!(tree.len(), len as usize);
+        assert_eq!(tree.max(), Some(&(len - 1)));
+    }
+}
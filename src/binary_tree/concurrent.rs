@@ -0,0 +1,110 @@
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::BinaryTree;
+
+// A BinaryTree that can be shared between threads, say in an Arc, and used
+// through a shared reference. It guards the whole tree with a single RwLock:
+// any number of threads can search at once, while adding or removing takes
+// the tree to itself. That's coarse, but lookups no longer queue up behind
+// each other the way they do with the whole tree in a Mutex.
+//
+// Nothing can borrow from inside the tree once the lock is released, so
+// queries return owned values, and with_tree() runs a closure under the read
+// lock for anything else.
+pub struct ConcurrentBinaryTree<T> {
+    tree: RwLock<BinaryTree<T>>
+}
+
+impl<T> ConcurrentBinaryTree<T> {
+    pub fn new() -> ConcurrentBinaryTree<T> {
+        ConcurrentBinaryTree::from_tree(BinaryTree::Empty)
+    }
+
+    pub fn from_tree(tree: BinaryTree<T>) -> ConcurrentBinaryTree<T> {
+        ConcurrentBinaryTree { tree: RwLock::new(tree) }
+    }
+
+    pub fn into_inner(self) -> BinaryTree<T> {
+        self.tree.into_inner().expect("tree lock poisoned")
+    }
+
+    // If a thread panicked while holding the write lock, it may have left the
+    // tree half-updated, with cached sizes that no longer add up. Carrying on
+    // with it would give wrong answers, so we propagate the panic instead.
+    fn read(&self) -> RwLockReadGuard<'_, BinaryTree<T>> {
+        self.tree.read().expect("tree lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BinaryTree<T>> {
+        self.tree.write().expect("tree lock poisoned")
+    }
+
+    // Runs `f` with shared access to the tree, holding the read lock for as
+    // long as it takes, so it should be quick.
+    pub fn with_tree<R, F>(&self, f: F) -> R
+        where F: FnOnce(&BinaryTree<T>) -> R
+    {
+        f(&self.read())
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    // A copy of the tree as it stands right now.
+    pub fn snapshot(&self) -> BinaryTree<T>
+        where T: Clone
+    {
+        self.read().clone()
+    }
+}
+
+impl<T: Ord> ConcurrentBinaryTree<T> {
+    pub fn add(&self, value: T) {
+        self.write().add(value);
+    }
+
+    pub fn remove(&self, value: &T) -> bool {
+        self.write().remove(value)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.read().contains(value)
+    }
+
+    pub fn min(&self) -> Option<T>
+        where T: Clone
+    {
+        self.read().min().cloned()
+    }
+
+    pub fn max(&self) -> Option<T>
+        where T: Clone
+    {
+        self.read().max().cloned()
+    }
+
+    pub fn pop_min(&self) -> Option<T> {
+        self.write().pop_min()
+    }
+
+    pub fn pop_max(&self) -> Option<T> {
+        self.write().pop_max()
+    }
+}
+
+impl<T> Default for ConcurrentBinaryTree<T> {
+    fn default() -> ConcurrentBinaryTree<T> {
+        ConcurrentBinaryTree::new()
+    }
+}
+
+impl<T> From<BinaryTree<T>> for ConcurrentBinaryTree<T> {
+    fn from(tree: BinaryTree<T>) -> ConcurrentBinaryTree<T> {
+        ConcurrentBinaryTree::from_tree(tree)
+    }
+}
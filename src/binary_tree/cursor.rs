@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use super::{BinaryTree, TreeNode};
+
+// Cursors point at a single node of a tree and move around it structurally:
+// down to the left or right child, or back up to the parent. Iterators only
+// ever go forward in sorted order; a cursor can go anywhere.
+
+// A read-only cursor. It keeps the path from the root down to the current
+// node as a stack of references, so moving up is just a pop.
+pub struct Cursor<'a, T> {
+    path: Vec<&'a TreeNode<T>>
+}
+
+impl<'a, T> Cursor<'a, T> {
+    // The element at the cursor, or None if the tree is empty.
+    pub fn current(&self) -> Option<&'a T> {
+        self.path.last().map(|node| &node.element)
+    }
+
+    // How far the cursor is below the root, which is at depth 0.
+    pub fn depth(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+
+    // Each move returns whether it happened. A cursor that can't move, because
+    // there is no child on that side or it is already at the root, stays put.
+
+    pub fn move_left(&mut self) -> bool {
+        let node = match self.path.last() {
+            Some(node) => *node,
+            None => return false
+        };
+        match node.left {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref left) => {
+                self.path.push(left);
+                true
+            }
+        }
+    }
+
+    pub fn move_right(&mut self) -> bool {
+        let node = match self.path.last() {
+            Some(node) => *node,
+            None => return false
+        };
+        match node.right {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref right) => {
+                self.path.push(right);
+                true
+            }
+        }
+    }
+
+    pub fn move_up(&mut self) -> bool {
+        if self.path.len() <= 1 {
+            return false;
+        }
+        self.path.pop();
+        true
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right
+}
+
+// A cursor that can also change the tree. Holding mutable references to a
+// node and all of its ancestors at once isn't possible in safe Rust, so
+// instead CursorMut remembers the way down from the root as a list of left
+// and right turns, and follows it again whenever it needs the current node.
+// That makes each operation O(depth) rather than O(1), which is the price of
+// not needing any unsafe code.
+pub struct CursorMut<'a, T> {
+    tree: &'a mut BinaryTree<T>,
+    path: Vec<Side>
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    // The subtree whose root is the current node.
+    fn subtree(&self) -> &BinaryTree<T> {
+        let mut tree = &*self.tree;
+        for &side in &self.path {
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref node) =>
+                    if side == Side::Left { &node.left } else { &node.right },
+                BinaryTree::Empty =>
+                    unreachable!("cursor path leads into an empty subtree")
+            };
+        }
+        tree
+    }
+
+    fn subtree_mut(&mut self) -> &mut BinaryTree<T> {
+        let mut tree = &mut *self.tree;
+        for &side in &self.path {
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref mut node) =>
+                    if side == Side::Left { &mut node.left } else { &mut node.right },
+                BinaryTree::Empty =>
+                    unreachable!("cursor path leads into an empty subtree")
+            };
+        }
+        tree
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        match *self.subtree() {
+            BinaryTree::Empty => None,
+            BinaryTree::NonEmpty(ref node) => Some(&node.element)
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    fn move_down(&mut self, side: Side) -> bool {
+        let has_child = match *self.subtree() {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref node) =>
+                if side == Side::Left { !node.left.is_empty() } else { !node.right.is_empty() }
+        };
+        if has_child {
+            self.path.push(side);
+        }
+        has_child
+    }
+
+    pub fn move_left(&mut self) -> bool {
+        self.move_down(Side::Left)
+    }
+
+    pub fn move_right(&mut self) -> bool {
+        self.move_down(Side::Right)
+    }
+
+    pub fn move_up(&mut self) -> bool {
+        self.path.pop().is_some()
+    }
+
+    // Removes the element at the cursor and returns it. The cursor stays at
+    // the same position in the tree, now occupied by whichever element took
+    // the removed one's place, or moves up to the parent if nothing did.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.subtree().is_empty() {
+            return None;
+        }
+
+        // Every ancestor is about to lose one element from its subtree.
+        let mut tree = &mut *self.tree;
+        for &side in &self.path {
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref mut node) => {
+                    node.size -= 1;
+                    if side == Side::Left { &mut node.left } else { &mut node.right }
+                }
+                BinaryTree::Empty =>
+                    unreachable!("cursor path leads into an empty subtree")
+            };
+        }
+
+        let removed = tree.remove_root();
+        if tree.is_empty() {
+            self.path.pop();
+        }
+        removed
+    }
+}
+
+impl<'a, T: Ord> CursorMut<'a, T> {
+    // Replaces the element at the cursor with `value`, returning the old
+    // element, but only if `value` belongs in the same spot: at least as big
+    // as everything before it in sorted order, and smaller than everything
+    // after. Otherwise the tree is left alone and `value` handed back as the
+    // error, since changing it would break the ordering the tree relies on.
+    pub fn replace_current(&mut self, value: T) -> Result<T, T> {
+        if !self.fits_here(&value) {
+            return Err(value);
+        }
+        match *self.subtree_mut() {
+            BinaryTree::NonEmpty(ref mut node) => Ok(mem::replace(&mut node.element, value)),
+            BinaryTree::Empty => Err(value)
+        }
+    }
+
+    fn fits_here(&self, value: &T) -> bool {
+        // Each turn on the way down bounds the current element. Everything to
+        // the left of an ancestor is <= it, everything to its right is > it.
+        let mut tree = &*self.tree;
+        for &side in &self.path {
+            let node = match *tree {
+                BinaryTree::NonEmpty(ref node) => node,
+                BinaryTree::Empty => return false
+            };
+            let within = match side {
+                Side::Left => *value <= node.element,
+                Side::Right => *value > node.element
+            };
+            if !within {
+                return false;
+            }
+            tree = if side == Side::Left { &node.left } else { &node.right };
+        }
+
+        // The current node's own subtrees bound it too.
+        match *tree {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref node) =>
+                node.left.max().is_none_or(|max| max <= value)
+                    && node.right.min().is_none_or(|min| value < min)
+        }
+    }
+
+    // Adds `value` to the tree wherever it belongs. A new element always goes
+    // in as a leaf, so no existing node moves and the cursor stays where it is.
+    pub fn insert(&mut self, value: T) {
+        self.tree.add(value);
+    }
+}
+
+impl<T> BinaryTree<T> {
+    // A cursor starting at the root.
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        let mut path = Vec::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            path.push(&**node);
+        }
+        Cursor { path }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { tree: self, path: Vec::new() }
+    }
+}
@@ -0,0 +1,337 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+
+use super::{BinaryTree, TreeNode};
+
+// Borrowing in-order iterator, yielding the elements in sorted order. It can
+// be consumed from both ends: `front` holds the nodes still to be visited on
+// the way up from the smallest element, smallest on top, and `back` does the
+// same for the largest. The two stacks can hold the same nodes, so we track
+// how many elements are left to stop the ends from running past each other.
+pub struct Iter<'a, T> {
+    front: Vec<&'a TreeNode<T>>,
+    back: Vec<&'a TreeNode<T>>,
+    remaining: usize
+}
+
+fn push_left_edge<'a, T>(stack: &mut Vec<&'a TreeNode<T>>, mut tree: &'a BinaryTree<T>) {
+    while let BinaryTree::NonEmpty(ref node) = *tree {
+        stack.push(node);
+        tree = &node.left;
+    }
+}
+
+fn push_right_edge<'a, T>(stack: &mut Vec<&'a TreeNode<T>>, mut tree: &'a BinaryTree<T>) {
+    while let BinaryTree::NonEmpty(ref node) = *tree {
+        stack.push(node);
+        tree = &node.right;
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.pop()?;
+        self.remaining -= 1;
+        push_left_edge(&mut self.front, &node.right);
+        Some(&node.element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.pop()?;
+        self.remaining -= 1;
+        push_right_edge(&mut self.back, &node.left);
+        Some(&node.element)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+// Pre-order: each element comes before everything in its subtrees, so the
+// root is first. Feeding this sequence back to add() rebuilds the same shape.
+pub struct PreorderIter<'a, T> {
+    stack: Vec<&'a TreeNode<T>>
+}
+
+impl<'a, T> Iterator for PreorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        if let BinaryTree::NonEmpty(ref right) = node.right {
+            self.stack.push(right);
+        }
+        if let BinaryTree::NonEmpty(ref left) = node.left {
+            self.stack.push(left);
+        }
+        Some(&node.element)
+    }
+}
+
+// Post-order: each element comes after everything in its subtrees, so the
+// root is last. A node goes on the stack twice: once to schedule its
+// subtrees, and once more, flagged, to be yielded after them.
+pub struct PostorderIter<'a, T> {
+    stack: Vec<(&'a TreeNode<T>, bool)>
+}
+
+impl<'a, T> Iterator for PostorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let (node, subtrees_done) = self.stack.pop()?;
+            if subtrees_done {
+                return Some(&node.element);
+            }
+            self.stack.push((node, true));
+            if let BinaryTree::NonEmpty(ref right) = node.right {
+                self.stack.push((right, false));
+            }
+            if let BinaryTree::NonEmpty(ref left) = node.left {
+                self.stack.push((left, false));
+            }
+        }
+    }
+}
+
+// Level order, breadth first: the root, then its children left to right, then
+// their children, and so on. Each element comes paired with its depth, the
+// root being at depth 0.
+pub struct LevelIter<'a, T> {
+    queue: VecDeque<(usize, &'a TreeNode<T>)>
+}
+
+impl<'a, T> Iterator for LevelIter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        let (depth, node) = self.queue.pop_front()?;
+        if let BinaryTree::NonEmpty(ref left) = node.left {
+            self.queue.push_back((depth + 1, left));
+        }
+        if let BinaryTree::NonEmpty(ref right) = node.right {
+            self.queue.push_back((depth + 1, right));
+        }
+        Some((depth, &node.element))
+    }
+}
+
+impl<T> BinaryTree<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        push_left_edge(&mut front, self);
+        push_right_edge(&mut back, self);
+        Iter { front, back, remaining: self.len() }
+    }
+
+    pub fn iter_preorder(&self) -> PreorderIter<'_, T> {
+        let mut stack = Vec::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            stack.push(&**node);
+        }
+        PreorderIter { stack }
+    }
+
+    pub fn iter_postorder(&self) -> PostorderIter<'_, T> {
+        let mut stack = Vec::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            stack.push((&**node, false));
+        }
+        PostorderIter { stack }
+    }
+
+    pub fn iter_levels(&self) -> LevelIter<'_, T> {
+        let mut queue = VecDeque::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            queue.push_back((0, &**node));
+        }
+        LevelIter { queue }
+    }
+
+    // Both of these know exactly how many elements they'll collect, thanks to
+    // the cached size, so the vector is allocated once at the right size.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut elements = Vec::with_capacity(self.len());
+        elements.extend(self);
+        elements
+    }
+
+    pub fn to_vec(&self) -> Vec<&T> {
+        let mut elements = Vec::with_capacity(self.len());
+        elements.extend(self.iter());
+        elements
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BinaryTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Consuming in-order iterator. The stack holds the nodes whose element hasn't
+// been yielded yet; the top of the stack is always the smallest of them. Each
+// node's left subtree has already been detached and pushed by the time the
+// node itself is popped.
+pub struct IntoIter<T> {
+    stack: Vec<Box<TreeNode<T>>>
+}
+
+impl<T> IntoIter<T> {
+    pub(super) fn new(tree: BinaryTree<T>) -> IntoIter<T> {
+        let mut iter = IntoIter { stack: Vec::new() };
+        iter.push_left_edge(tree);
+        iter
+    }
+
+    fn push_left_edge(&mut self, mut tree: BinaryTree<T>) {
+        while let Some(mut node) = tree.take_node() {
+            tree = mem::take(&mut node.left);
+            self.stack.push(node);
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut node = self.stack.pop()?;
+        let right = mem::take(&mut node.right);
+        self.push_left_edge(right);
+        Some(node.element)
+    }
+}
+
+impl<T> IntoIterator for BinaryTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self)
+    }
+}
+
+// Yields the elements of a tree by value, in sorted order. The tree is left
+// empty as soon as the Drain is created; whatever isn't consumed is dropped
+// along with the Drain. Like IntoIter, the only extra memory it needs is a
+// stack as deep as the tree is tall.
+pub struct Drain<'a, T> {
+    inner: IntoIter<T>,
+    marker: PhantomData<&'a mut BinaryTree<T>>
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+}
+
+impl<T> BinaryTree<T> {
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            inner: IntoIter::new(mem::take(self)),
+            marker: PhantomData
+        }
+    }
+}
+
+// In-order iterator over the elements that fall within a range. Rather than
+// checking the upper bound as it goes, the iterator counts up front how many
+// elements it will yield, using the cached subtree sizes, and stops once it
+// has yielded them. That means it never needs to hold on to the bounds.
+pub struct Range<'a, T> {
+    stack: Vec<&'a TreeNode<T>>,
+    remaining: usize
+}
+
+pub(super) fn below_start<T: Ord, R: RangeBounds<T>>(range: &R, element: &T) -> bool {
+    match range.start_bound() {
+        Bound::Included(start) => element < start,
+        Bound::Excluded(start) => element <= start,
+        Bound::Unbounded => false
+    }
+}
+
+pub(super) fn within_end<T: Ord, R: RangeBounds<T>>(range: &R, element: &T) -> bool {
+    match range.end_bound() {
+        Bound::Included(end) => element <= end,
+        Bound::Excluded(end) => element < end,
+        Bound::Unbounded => true
+    }
+}
+
+impl<'a, T: Ord> Range<'a, T> {
+    fn new<R: RangeBounds<T>>(tree: &'a BinaryTree<T>, range: R) -> Range<'a, T> {
+        let before = tree.count_prefix(|element| below_start(&range, element));
+        let through_end = tree.count_prefix(|element| within_end(&range, element));
+
+        // Push the path down to the first element in range, skipping every
+        // subtree that lies entirely below the start bound.
+        let mut stack = Vec::new();
+        let mut tree = tree;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            if below_start(&range, &node.element) {
+                tree = &node.right;
+            } else {
+                stack.push(&**node);
+                tree = &node.left;
+            }
+        }
+
+        Range { stack, remaining: through_end.saturating_sub(before) }
+    }
+}
+
+impl<'a, T> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.stack.pop()?;
+        self.remaining -= 1;
+        push_left_edge(&mut self.stack, &node.right);
+        Some(&node.element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Range<'a, T> {}
+
+impl<T: Ord> BinaryTree<T> {
+    // Iterates in sorted order over just the elements within `range`, as in
+    // tree.range(10..20) or tree.range(..=limit). Subtrees that lie entirely
+    // outside the range are never visited.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Range<'_, T> {
+        Range::new(self, range)
+    }
+}
@@ -0,0 +1,405 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+use core::mem;
+
+use super::{self as tree, BinaryTree, TreeNode};
+
+// A map from keys to values, built on the same BinaryTree. Each element of
+// the underlying tree is a key paired with its value, ordered by key alone, so
+// all of BinaryTree's machinery (cached sizes, iterative add and drop, and so
+// on) carries over. Unlike BinaryTree, a map holds each key at most once.
+pub struct BinaryTreeMap<K, V> {
+    tree: BinaryTree<MapEntry<K, V>>
+}
+
+struct MapEntry<K, V> {
+    key: K,
+    value: V
+}
+
+impl<K: Ord, V> PartialEq for MapEntry<K, V> {
+    fn eq(&self, other: &MapEntry<K, V>) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, V> Eq for MapEntry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for MapEntry<K, V> {
+    fn partial_cmp(&self, other: &MapEntry<K, V>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for MapEntry<K, V> {
+    fn cmp(&self, other: &MapEntry<K, V>) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<K, V> BinaryTreeMap<K, V> {
+    pub fn new() -> BinaryTreeMap<K, V> {
+        BinaryTreeMap { tree: BinaryTree::Empty }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    // Entries, keys, and values all come out sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { inner: self.tree.iter() }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.tree.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.tree.iter() }
+    }
+}
+
+impl<K: Ord, V> BinaryTreeMap<K, V> {
+    // Adds a key-value pair to the map. If the key was already present, its
+    // value is replaced and the old value returned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(entry) = self.tree.find_by_mut(|entry| key.cmp(&entry.key)) {
+            return Some(mem::replace(&mut entry.value, value));
+        }
+        self.tree.add(MapEntry { key, value });
+        None
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.tree.find_by(|entry| key.cmp(&entry.key))
+            .map(|entry| &entry.value)
+    }
+
+    // Handing out `&mut V` is safe because entries are ordered by key alone:
+    // no change to the value can put the entry in the wrong place.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.tree.find_by_mut(|entry| key.cmp(&entry.key))
+            .map(|entry| &mut entry.value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.tree.remove_by(|entry| key.cmp(&entry.key))
+            .map(|entry| entry.value)
+    }
+
+    // Returns an Entry for `key` that can read, update, or fill in the value
+    // without the caller looking the key up twice:
+    //
+    //     *counts.entry(word).or_insert(0) += 1;
+    //
+    // This walks down the tree once. Each step reads the node to decide where
+    // to go, then reborrows it mutably to go there, which is what lets the
+    // borrow checker see that the reference we end up with is the only one
+    // into the tree. On the way down we keep a reference to each node's cached
+    // size, split off from the borrow of its children, so that filling in a
+    // vacant entry can bump them without walking down again.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let mut sizes = Vec::new();
+        let mut tree = &mut self.tree;
+        loop {
+            let ordering = match *tree {
+                BinaryTree::NonEmpty(ref node) => key.cmp(&node.element.key),
+                BinaryTree::Empty => break
+            };
+            if ordering == Ordering::Equal {
+                break;
+            }
+            let node = match *tree {
+                BinaryTree::NonEmpty(ref mut node) => node,
+                BinaryTree::Empty => unreachable!()
+            };
+            let TreeNode { ref mut size, ref mut left, ref mut right, .. } = **node;
+            sizes.push(size);
+            tree = if ordering == Ordering::Less { left } else { right };
+        }
+
+        if tree.is_empty() {
+            return Entry::Vacant(VacantEntry { slot: tree, sizes, key });
+        }
+        match *tree {
+            BinaryTree::NonEmpty(ref mut node) =>
+                Entry::Occupied(OccupiedEntry { entry: &mut node.element }),
+            BinaryTree::Empty =>
+                unreachable!()
+        }
+    }
+}
+
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>)
+}
+
+pub struct OccupiedEntry<'a, K, V> {
+    entry: &'a mut MapEntry<K, V>
+}
+
+// `slot` is the empty subtree where the key belongs, and `sizes` the cached
+// sizes of the nodes above it.
+pub struct VacantEntry<'a, K, V> {
+    slot: &'a mut BinaryTree<MapEntry<K, V>>,
+    sizes: Vec<&'a mut usize>,
+    key: K
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref occupied) => occupied.key(),
+            Entry::Vacant(ref vacant) => vacant.key()
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(occupied) => occupied.into_mut(),
+            Entry::Vacant(vacant) => vacant.insert(default)
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(occupied) => occupied.into_mut(),
+            Entry::Vacant(vacant) => vacant.insert(default())
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+        where V: Default
+    {
+        self.or_insert_with(V::default)
+    }
+
+    // Applies `f` to the value if there is one, leaving a vacant entry alone,
+    // so it can be chained with or_insert():
+    //
+    //     map.entry(key).and_modify(|v| *v += 1).or_insert(1);
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V> {
+        match self {
+            Entry::Occupied(mut occupied) => {
+                f(occupied.get_mut());
+                Entry::Occupied(occupied)
+            }
+            Entry::Vacant(vacant) =>
+                Entry::Vacant(vacant)
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.entry.key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.entry.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.entry.value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.entry.value
+    }
+
+    // Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(&mut self.entry.value, value)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        for size in self.sizes {
+            *size += 1;
+        }
+        *self.slot = BinaryTree::node(MapEntry { key: self.key, value },
+                                      BinaryTree::Empty, BinaryTree::Empty);
+        match *self.slot {
+            BinaryTree::NonEmpty(ref mut node) => &mut node.element.value,
+            BinaryTree::Empty => unreachable!()
+        }
+    }
+}
+
+impl<K, V> Default for BinaryTreeMap<K, V> {
+    fn default() -> BinaryTreeMap<K, V> {
+        BinaryTreeMap::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BinaryTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> BinaryTreeMap<K, V> {
+        let mut map = BinaryTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for BinaryTreeMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+// The map's iterators are thin wrappers around the tree's in-order iterators
+// that split each entry into its parts.
+
+pub struct Iter<'a, K, V> {
+    inner: tree::Iter<'a, MapEntry<K, V>>
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.inner.next_back().map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct Keys<'a, K, V> {
+    inner: tree::Iter<'a, MapEntry<K, V>>
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.inner.next_back().map(|entry| &entry.key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+pub struct Values<'a, K, V> {
+    inner: tree::Iter<'a, MapEntry<K, V>>
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.inner.next_back().map(|entry| &entry.value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+pub struct IntoIter<K, V> {
+    inner: tree::IntoIter<MapEntry<K, V>>
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.inner.next().map(|entry| (entry.key, entry.value))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BinaryTreeMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V> IntoIterator for BinaryTreeMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { inner: self.tree.into_iter() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_fills_in_or_finds_each_key() {
+        let mut map = BinaryTreeMap::new();
+        for word in ["b", "d", "a", "c", "b", "e", "a", "b"] {
+            *map.entry(word).or_insert(0) += 1;
+        }
+        let counts: Vec<_> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(counts, [("a", 2), ("b", 3), ("c", 1), ("d", 1), ("e", 1)]);
+        assert_eq!(map.len(), 5);
+    }
+
+    // Filling in a vacant entry bumps the cached sizes it passed on the way
+    // down, which select() relies on.
+    #[test]
+    fn entry_keeps_cached_sizes_up_to_date() {
+        let mut map = BinaryTreeMap::new();
+        for key in [50, 20, 80, 10, 30, 70, 90, 25, 35, 30, 20] {
+            map.entry(key).or_insert(());
+        }
+        let keys: Vec<_> = map.keys().cloned().collect();
+        assert_eq!(keys, [10, 20, 25, 30, 35, 50, 70, 80, 90]);
+        for (i, &key) in keys.iter().enumerate() {
+            assert_eq!(map.tree.select(i).map(|entry| entry.key), Some(key));
+        }
+    }
+}
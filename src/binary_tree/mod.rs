@@ -0,0 +1,602 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem::{self, ManuallyDrop};
+use core::ptr;
+
+pub mod arena;
+mod bulk;
+#[cfg(feature = "std")]
+mod codec;
+#[cfg(feature = "std")]
+mod concurrent;
+mod cursor;
+mod iter;
+pub mod map;
+pub mod multiset;
+#[cfg(feature = "rayon")]
+mod par;
+pub mod persistent;
+mod preorder;
+#[cfg(feature = "proptest")]
+mod proptest_impls;
+mod query;
+mod render;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod set_ops;
+mod shape;
+mod sorted_by;
+mod visit;
+
+pub use self::arena::ArenaTree;
+#[cfg(feature = "std")]
+pub use self::codec::BinaryCodec;
+#[cfg(feature = "std")]
+pub use self::concurrent::ConcurrentBinaryTree;
+pub use self::cursor::{Cursor, CursorMut};
+pub use self::iter::{Drain, IntoIter, Iter, LevelIter, PostorderIter, PreorderIter, Range};
+pub use self::map::BinaryTreeMap;
+pub use self::multiset::BinaryMultiSet;
+#[cfg(feature = "rayon")]
+pub use self::par::ParIter;
+pub use self::persistent::PersistentTree;
+pub use self::set_ops::{Difference, Intersection, TreeDiff};
+pub use self::sorted_by::BinaryTreeBy;
+pub use self::visit::{TreeVisitor, Visit};
+
+use self::preorder::PreorderBuilder;
+
+// The BinaryTree from the end of the chapter. A tree is either empty, or a
+// boxed node holding one element and two subtrees. Everything smaller than or
+// equal to a node's element lives in its left subtree, everything greater in
+// its right subtree.
+#[derive(Default)]
+pub enum BinaryTree<T> {
+    #[default]
+    Empty,
+    NonEmpty(Box<TreeNode<T>>)
+}
+
+// A Box is never null, so the compiler uses the null pointer to stand for
+// Empty and a BinaryTree is exactly one pointer wide, with no tag and no
+// padding: the same layout Option<Box<TreeNode<T>>> gets. There's nothing to
+// gain from switching the representation to Option, and doing so would take
+// away the ability to match on BinaryTree::Empty and BinaryTree::NonEmpty.
+// benches/tree_layout.rs measures the two against each other. This check
+// keeps it that way.
+const _: () = assert!(mem::size_of::<BinaryTree<u8>>() == mem::size_of::<Box<TreeNode<u8>>>());
+
+// The fields are private so callers can't reach in and break the ordering,
+// but they can still match on BinaryTree::NonEmpty(node) and walk the tree
+// through the accessors below.
+//
+// Each node also caches the number of elements in the subtree rooted at it,
+// itself included, so len() doesn't have to walk the tree. Everything that
+// changes the shape of the tree must keep `size` up to date.
+pub struct TreeNode<T> {
+    element: T,
+    left: BinaryTree<T>,
+    right: BinaryTree<T>,
+    size: usize
+}
+
+impl<T> TreeNode<T> {
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+
+    pub fn left(&self) -> &BinaryTree<T> {
+        &self.left
+    }
+
+    pub fn right(&self) -> &BinaryTree<T> {
+        &self.right
+    }
+}
+
+impl<T> BinaryTree<T> {
+    pub fn new() -> BinaryTree<T> {
+        BinaryTree::Empty
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            BinaryTree::Empty => true,
+            BinaryTree::NonEmpty(_) => false
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match *self {
+            BinaryTree::Empty => 0,
+            BinaryTree::NonEmpty(ref node) => node.size
+        }
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = match *self {
+            BinaryTree::Empty => return None,
+            BinaryTree::NonEmpty(ref node) => node
+        };
+        while let BinaryTree::NonEmpty(ref left) = node.left {
+            node = left;
+        }
+        Some(&node.element)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = match *self {
+            BinaryTree::Empty => return None,
+            BinaryTree::NonEmpty(ref node) => node
+        };
+        while let BinaryTree::NonEmpty(ref right) = node.right {
+            node = right;
+        }
+        Some(&node.element)
+    }
+
+    // Removes and returns the smallest element of the tree.
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // Walk down the left edge. Each node we pass is about to lose an
+        // element from its left subtree.
+        let mut tree = self;
+        while tree.has_left_child() {
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref mut node) => {
+                    node.size -= 1;
+                    &mut node.left
+                }
+                BinaryTree::Empty => unreachable!()
+            };
+        }
+
+        // The smallest node has no left child, so its right subtree simply
+        // moves up into its place.
+        let node = tree.take_node()?;
+        let TreeNode { element, right, .. } = *node;
+        *tree = right;
+        Some(element)
+    }
+
+    // Removes and returns the largest element of the tree. This mirrors
+    // pop_min(), walking down the right edge instead.
+    pub fn pop_max(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut tree = self;
+        while tree.has_right_child() {
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref mut node) => {
+                    node.size -= 1;
+                    &mut node.right
+                }
+                BinaryTree::Empty => unreachable!()
+            };
+        }
+
+        let node = tree.take_node()?;
+        let TreeNode { element, left, .. } = *node;
+        *tree = left;
+        Some(element)
+    }
+
+    fn has_left_child(&self) -> bool {
+        match *self {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref node) => !node.left.is_empty()
+        }
+    }
+
+    fn has_right_child(&self) -> bool {
+        match *self {
+            BinaryTree::Empty => false,
+            BinaryTree::NonEmpty(ref node) => !node.right.is_empty()
+        }
+    }
+
+    // Makes a non-empty tree out of an element and two subtrees, working out
+    // the cached size from the subtrees.
+    fn node(element: T, left: BinaryTree<T>, right: BinaryTree<T>) -> BinaryTree<T> {
+        let size = 1 + left.len() + right.len();
+        BinaryTree::NonEmpty(Box::new(TreeNode { element, left, right, size }))
+    }
+
+    // Detaches the root node, leaving this tree empty.
+    //
+    // Because BinaryTree implements Drop, Rust won't let us move the box out
+    // of a NonEmpty tree with an ordinary pattern. Instead we wrap the tree in
+    // ManuallyDrop, so its destructor never runs, and read the box out of it.
+    //
+    // Links held as Option<Box<TreeNode<T>>> would let Option::take() do
+    // this safely, but only by making the enum callers match on a wrapper
+    // around a private Option, and the Drop impl, which keeps dropping a
+    // deep tree from overflowing the stack, would still be needed on the
+    // wrapper. So this one small unsafe block stays, and everything else
+    // that moves nodes goes through it.
+    fn take_node(&mut self) -> Option<Box<TreeNode<T>>> {
+        let tree = ManuallyDrop::new(mem::take(self));
+        match *tree {
+            BinaryTree::Empty => None,
+            // Safety: `tree` is never used again and its destructor never
+            // runs, so reading the box out moves ownership of the node to the
+            // caller rather than duplicating it.
+            BinaryTree::NonEmpty(ref node) => Some(unsafe { ptr::read(node) })
+        }
+    }
+
+    // Removes the element at the root of this tree, splicing its subtrees
+    // back together. When both subtrees are non-empty, the smallest element
+    // of the right subtree takes the root's place.
+    fn remove_root(&mut self) -> Option<T> {
+        let node = self.take_node()?;
+        let TreeNode { element, left, mut right, .. } = *node;
+
+        *self = if left.is_empty() {
+            right
+        } else if right.is_empty() {
+            left
+        } else {
+            let successor = right.pop_min()
+                .expect("non-empty subtree has a minimum");
+            BinaryTree::node(successor, left, right)
+        };
+
+        Some(element)
+    }
+
+    // The searches below are written in terms of a function that compares
+    // the element being looked for against each element on the way down,
+    // rather than requiring T: Ord. That lets BinaryTreeMap look entries up
+    // by key alone, and BinaryTreeBy use a comparator of its own.
+    fn find_by<F>(&self, target: F) -> Option<&T>
+        where F: Fn(&T) -> Ordering
+    {
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            tree = match target(&node.element) {
+                Ordering::Less => &node.left,
+                Ordering::Equal => return Some(&node.element),
+                Ordering::Greater => &node.right
+            };
+        }
+        None
+    }
+
+    // The caller must not change the element in a way that affects where it
+    // belongs in the tree.
+    fn find_by_mut<F>(&mut self, target: F) -> Option<&mut T>
+        where F: Fn(&T) -> Ordering
+    {
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref mut node) = *tree {
+            tree = match target(&node.element) {
+                Ordering::Less => &mut node.left,
+                Ordering::Equal => return Some(&mut node.element),
+                Ordering::Greater => &mut node.right
+            };
+        }
+        None
+    }
+
+    // add(), ordering elements with `compare` instead of Ord, and handing back
+    // a reference to the newly added element.
+    fn add_by<F>(&mut self, value: T, compare: F) -> &mut T
+        where F: Fn(&T, &T) -> Ordering
+    {
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref mut node) = *tree {
+            node.size += 1;
+            tree = if compare(&value, &node.element) != Ordering::Greater {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        *tree = BinaryTree::node(value, BinaryTree::Empty, BinaryTree::Empty);
+        match *tree {
+            BinaryTree::NonEmpty(ref mut node) => &mut node.element,
+            BinaryTree::Empty => unreachable!()
+        }
+    }
+
+    // We look for the element first so that the walk down can decrement the
+    // cached sizes knowing the removal will happen.
+    fn remove_by<F>(&mut self, target: F) -> Option<T>
+        where F: Fn(&T) -> Ordering
+    {
+        self.find_by(&target)?;
+
+        let mut tree = self;
+        loop {
+            let ordering = match *tree {
+                BinaryTree::NonEmpty(ref node) => target(&node.element),
+                BinaryTree::Empty => unreachable!("find_by() found the element on this path")
+            };
+            if ordering == Ordering::Equal {
+                break;
+            }
+
+            tree = match *tree {
+                BinaryTree::NonEmpty(ref mut node) => {
+                    node.size -= 1;
+                    if ordering == Ordering::Less {
+                        &mut node.left
+                    } else {
+                        &mut node.right
+                    }
+                }
+                BinaryTree::Empty => unreachable!()
+            };
+        }
+
+        tree.remove_root()
+    }
+
+    // Builds a perfectly balanced tree from exactly `len` elements, taken from
+    // `iter` in order. The left half is built first so that the middle element
+    // comes out of the iterator just in time to become the root.
+    fn build_balanced<I>(len: usize, iter: &mut I) -> BinaryTree<T>
+        where I: Iterator<Item = T>
+    {
+        if len == 0 {
+            return BinaryTree::Empty;
+        }
+
+        let left_len = len / 2;
+        let left = BinaryTree::build_balanced(left_len, iter);
+        let element = iter.next()
+            .expect("iterator yielded fewer elements than promised");
+        let right = BinaryTree::build_balanced(len - left_len - 1, iter);
+
+        BinaryTree::node(element, left, right)
+    }
+}
+
+impl<T: Ord> BinaryTree<T> {
+    // The book's version of add() calls itself once per level, which blows the
+    // stack on a tree that has degenerated into a long chain. This does the
+    // same thing with a loop: `tree` is a mutable reference that steps down
+    // into the left or right subtree until it lands on the Empty spot where
+    // the new node belongs.
+    pub fn add(&mut self, value: T) {
+        self.add_by(value, T::cmp);
+    }
+
+    // add() puts a value into the tree even if an equal value is already
+    // there. This adds `value` only if it isn't, returning whether it did.
+    // For counting duplicates rather than storing them, see BinaryMultiSet.
+    pub fn add_unique(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            return false;
+        }
+        self.add(value);
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.find_by(|element| value.cmp(element)).is_some()
+    }
+
+    // Removes one occurrence of `value` from the tree, returning whether
+    // there was one to remove.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.remove_by(|element| value.cmp(element)).is_some()
+    }
+
+    // Inserting already-sorted data one element at a time with add() produces
+    // a tree that is really a linked list. When the caller knows the input is
+    // sorted, we can instead build a perfectly balanced tree directly in O(n),
+    // making the middle element the root and building each half the same way.
+    pub fn from_sorted_iter<I>(iter: I) -> BinaryTree<T>
+        where I: IntoIterator<Item = T>
+    {
+        BinaryTree::from_sorted_vec(iter.into_iter().collect())
+    }
+
+    pub fn from_sorted_vec(elements: Vec<T>) -> BinaryTree<T> {
+        debug_assert!(elements.windows(2).all(|pair| pair[0] <= pair[1]),
+                      "tree built from unsorted input");
+
+        let len = elements.len();
+        BinaryTree::build_balanced(len, &mut elements.into_iter())
+    }
+
+    pub fn from_sorted_slice(elements: &[T]) -> BinaryTree<T>
+        where T: Clone
+    {
+        debug_assert!(elements.windows(2).all(|pair| pair[0] <= pair[1]),
+                      "tree built from unsorted input");
+
+        BinaryTree::build_balanced(elements.len(), &mut elements.iter().cloned())
+    }
+
+    // Moves every element of `other` into this tree, leaving nothing behind.
+    pub fn append(&mut self, other: BinaryTree<T>) {
+        self.extend(other);
+    }
+}
+
+// Left to itself, dropping a tree drops the root's box, which drops both
+// subtrees, which drop their boxes, and so on: one level of recursion per
+// level of the tree, enough to overflow the stack on a long chain. Instead we
+// detach nodes onto a worklist, as std's LinkedList does, so each node is
+// freed only once its subtrees have been taken off it and it has nothing left
+// to recurse into.
+impl<T> Drop for BinaryTree<T> {
+    fn drop(&mut self) {
+        let mut worklist = Vec::new();
+        worklist.extend(self.take_node());
+        while let Some(mut node) = worklist.pop() {
+            worklist.extend(node.left.take_node());
+            worklist.extend(node.right.take_node());
+        }
+    }
+}
+
+// A clone has the same shape as the original, not just the same elements. We
+// stream the original's pre-order walk into a PreorderBuilder so that, like
+// Drop, cloning never recurses.
+impl<T: Clone> Clone for BinaryTree<T> {
+    fn clone(&self) -> BinaryTree<T> {
+        let mut builder = PreorderBuilder::new();
+        let mut stack = vec![self];
+        while let Some(tree) = stack.pop() {
+            let token = match *tree {
+                BinaryTree::Empty => None,
+                BinaryTree::NonEmpty(ref node) => {
+                    stack.push(&node.right);
+                    stack.push(&node.left);
+                    Some(node.element.clone())
+                }
+            };
+            if let Some(clone) = builder.push(token) {
+                return clone;
+            }
+        }
+        unreachable!("a pre-order walk always completes the tree")
+    }
+}
+
+// Equality, on the other hand, is about the elements and not the shape: two
+// trees are equal if they hold the same elements, so a balanced tree and a
+// degenerate chain built from the same values compare equal. Hash follows
+// suit, hashing the sorted sequence of elements.
+impl<T: PartialEq> PartialEq for BinaryTree<T> {
+    fn eq(&self, other: &BinaryTree<T>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for BinaryTree<T> {}
+
+impl<T: Hash> Hash for BinaryTree<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for element in self {
+            element.hash(state);
+        }
+    }
+}
+
+// Like the other comparisons, Debug shows the elements in sorted order, as a
+// set, rather than the shape of the tree. A derived Debug would recurse once
+// per level, and we want to be able to print any tree we can build.
+// render_ascii() is there for when the shape matters.
+impl<T: fmt::Debug> fmt::Debug for BinaryTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinaryTree<T> {
+        let mut tree = BinaryTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T: Ord> Extend<T> for BinaryTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::thread;
+
+    use super::{BinaryTree, PreorderBuilder};
+
+    // A right-leaning chain holding 0..len, built straight from its pre-order
+    // walk so that making a deep tree doesn't take quadratic time.
+    fn chain(len: u32) -> BinaryTree<u32> {
+        let mut builder = PreorderBuilder::new();
+        for element in 0..len {
+            assert!(builder.push(Some(element)).is_none());
+            assert!(builder.push(None).is_none());
+        }
+        builder.push(None).expect("the last empty subtree completes the chain")
+    }
+
+    // Runs `f` on a thread with a stack far too small for anything that
+    // recurses once per level of a deep tree.
+    fn with_small_stack<F, R>(f: F) -> R
+        where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+    {
+        thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(f)
+            .expect("couldn't spawn a thread")
+            .join()
+            .expect("the thread panicked")
+    }
+
+    #[test]
+    fn add_sorted_elements_in_a_small_stack() {
+        let len = if cfg!(miri) { 200 } else { 20_000 };
+        let tree = with_small_stack(move || {
+            let mut tree = BinaryTree::new();
+            for element in 0..len {
+                tree.add(element);
+            }
+            tree
+        });
+        assert_eq!(tree.len(), len);
+        assert!(tree.iter().cloned().eq(0..len));
+    }
+
+    #[test]
+    fn add_sorted_elements_to_a_chain_millions_deep() {
+        let len = if cfg!(miri) { 1_000 } else { 3_000_000 };
+        let mut tree = chain(len);
+        for element in len..len + 10 {
+            tree.add(element);
+        }
+        assert_eq!(tree.len(), len as usize + 10);
+        assert_eq!(tree.min(), Some(&0));
+        assert_eq!(tree.max(), Some(&(len + 9)));
+        assert!(tree.contains(&(len + 5)));
+    }
+
+    #[test]
+    fn drop_a_million_node_chain_in_a_small_stack() {
+        let len = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let tree = chain(len);
+        assert_eq!(tree.len(), len as usize);
+        with_small_stack(move || drop(tree));
+    }
+
+    // Drop takes nodes apart with ptr::read, so check that every element is
+    // dropped exactly once, from a bushy tree as well as a chain.
+    #[test]
+    fn drop_drops_every_element_once() {
+        extern crate alloc;
+
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut tree = BinaryTree::new();
+        for i in [5, 2, 8, 1, 3, 7, 9, 4, 6, 0] {
+            tree.add((i, Rc::clone(&counter)));
+        }
+        assert_eq!(Rc::strong_count(&counter), 11);
+        drop(tree);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
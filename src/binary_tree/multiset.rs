@@ -0,0 +1,125 @@
+use core::iter::FromIterator;
+
+use super::map::{self, BinaryTreeMap};
+
+// BinaryTree::add() keeps every duplicate as a node of its own. For data with
+// lots of repetition, it's much cheaper to keep one node per distinct value,
+// with a count of how many times it has been added. That's just a map from
+// values to counts, so that's what this is built on.
+pub struct BinaryMultiSet<T> {
+    counts: BinaryTreeMap<T, usize>,
+    len: usize
+}
+
+impl<T> BinaryMultiSet<T> {
+    pub fn new() -> BinaryMultiSet<T> {
+        BinaryMultiSet { counts: BinaryTreeMap::new(), len: 0 }
+    }
+
+    // The total number of elements, counting duplicates.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The number of distinct elements.
+    pub fn distinct_len(&self) -> usize {
+        self.counts.len()
+    }
+
+    // Iterates over the distinct elements in sorted order, each paired with
+    // the number of times it occurs.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.counts.iter() }
+    }
+}
+
+impl<T: Ord> BinaryMultiSet<T> {
+    // Adds one occurrence of `value`, returning how many there are now.
+    pub fn insert(&mut self, value: T) -> usize {
+        self.len += 1;
+        let count = self.counts.entry(value).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).cloned().unwrap_or(0)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.counts.contains_key(value)
+    }
+
+    // Removes one occurrence of `value`, returning whether there was one.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let remaining = match self.counts.get_mut(value) {
+            None => return false,
+            Some(count) => {
+                *count -= 1;
+                *count
+            }
+        };
+        if remaining == 0 {
+            self.counts.remove(value);
+        }
+        self.len -= 1;
+        true
+    }
+
+    // Removes every occurrence of `value`, returning how many there were.
+    pub fn remove_all(&mut self, value: &T) -> usize {
+        let count = self.counts.remove(value).unwrap_or(0);
+        self.len -= count;
+        count
+    }
+}
+
+impl<T> Default for BinaryMultiSet<T> {
+    fn default() -> BinaryMultiSet<T> {
+        BinaryMultiSet::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryMultiSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinaryMultiSet<T> {
+        let mut set = BinaryMultiSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord> Extend<T> for BinaryMultiSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: map::Iter<'a, T, usize>
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<(&'a T, usize)> {
+        self.inner.next().map(|(value, &count)| (value, count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<(&'a T, usize)> {
+        self.inner.next_back().map(|(value, &count)| (value, count))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
@@ -0,0 +1,59 @@
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use super::BinaryTree;
+
+// Parallel iteration over a tree's elements with rayon, as in
+// tree.par_iter().filter(..).count(). Work is divided along the tree's own
+// structure: a subtree splits into its left subtree on one side, and its root
+// element plus right subtree on the other, so each half stays in sorted order
+// and no element is copied or collected up front.
+pub struct ParIter<'a, T> {
+    tree: &'a BinaryTree<T>
+}
+
+impl<'a, T: Sync> ParallelIterator for ParIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<&'a T>
+    {
+        bridge_unindexed(TreeProducer { first: None, tree: self.tree }, consumer)
+    }
+}
+
+// One piece of the work: the element `first`, if there is one, followed in
+// order by every element of `tree`.
+struct TreeProducer<'a, T> {
+    first: Option<&'a T>,
+    tree: &'a BinaryTree<T>
+}
+
+impl<'a, T: Sync> UnindexedProducer for TreeProducer<'a, T> {
+    type Item = &'a T;
+
+    fn split(self) -> (TreeProducer<'a, T>, Option<TreeProducer<'a, T>>) {
+        match *self.tree {
+            BinaryTree::Empty =>
+                (self, None),
+            BinaryTree::NonEmpty(ref node) =>
+                (TreeProducer { first: self.first, tree: &node.left },
+                 Some(TreeProducer { first: Some(&node.element), tree: &node.right }))
+        }
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+        where F: Folder<&'a T>
+    {
+        folder.consume_iter(self.first.into_iter().chain(self.tree.iter()))
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for &'a BinaryTree<T> {
+    type Item = &'a T;
+    type Iter = ParIter<'a, T>;
+
+    fn into_par_iter(self) -> ParIter<'a, T> {
+        ParIter { tree: self }
+    }
+}
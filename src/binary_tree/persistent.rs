@@ -0,0 +1,248 @@
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+use core::mem::{self, ManuallyDrop};
+use core::ptr;
+
+// A persistent tree never changes once built. Adding or removing an element
+// returns a new tree and leaves the old one intact, so keeping an old version
+// around as a snapshot, or to undo back to, costs nothing extra.
+//
+// That's cheap because the two versions share structure. The nodes are
+// reference counted instead of boxed, and the new tree only copies the nodes
+// on the path from the root down to the change, O(log n) of them in a
+// balanced tree; every subtree hanging off that path is shared with the old
+// tree. It's the same shape as BinaryTree, with Rc in place of Box.
+#[derive(Default)]
+pub enum PersistentTree<T> {
+    #[default]
+    Empty,
+    NonEmpty(Rc<PersistentNode<T>>)
+}
+
+pub struct PersistentNode<T> {
+    element: T,
+    left: PersistentTree<T>,
+    right: PersistentTree<T>,
+    size: usize
+}
+
+impl<T> PersistentNode<T> {
+    pub fn element(&self) -> &T {
+        &self.element
+    }
+
+    pub fn left(&self) -> &PersistentTree<T> {
+        &self.left
+    }
+
+    pub fn right(&self) -> &PersistentTree<T> {
+        &self.right
+    }
+}
+
+// Cloning a persistent tree is O(1): the clone shares every node.
+impl<T> Clone for PersistentTree<T> {
+    fn clone(&self) -> PersistentTree<T> {
+        match *self {
+            PersistentTree::Empty => PersistentTree::Empty,
+            PersistentTree::NonEmpty(ref node) => PersistentTree::NonEmpty(Rc::clone(node))
+        }
+    }
+}
+
+impl<T> PersistentTree<T> {
+    pub fn new() -> PersistentTree<T> {
+        PersistentTree::Empty
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            PersistentTree::Empty => true,
+            PersistentTree::NonEmpty(_) => false
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match *self {
+            PersistentTree::Empty => 0,
+            PersistentTree::NonEmpty(ref node) => node.size
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_edge(self);
+        iter
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut max = None;
+        let mut tree = self;
+        while let PersistentTree::NonEmpty(ref node) = *tree {
+            max = Some(&node.element);
+            tree = &node.right;
+        }
+        max
+    }
+
+    fn node(element: T, left: PersistentTree<T>, right: PersistentTree<T>) -> PersistentTree<T> {
+        let size = 1 + left.len() + right.len();
+        PersistentTree::NonEmpty(Rc::new(PersistentNode { element, left, right, size }))
+    }
+
+    // Detaches the root, leaving this tree empty. This uses the same trick as
+    // BinaryTree::take_node(), for the same reason: PersistentTree implements
+    // Drop, so the Rc can't be moved out with an ordinary pattern.
+    fn take_root(&mut self) -> Option<Rc<PersistentNode<T>>> {
+        let tree = ManuallyDrop::new(mem::take(self));
+        match *tree {
+            PersistentTree::Empty => None,
+            // Safety: `tree` is never used again and its destructor never
+            // runs, so this moves the Rc out rather than duplicating it.
+            PersistentTree::NonEmpty(ref node) => Some(unsafe { ptr::read(node) })
+        }
+    }
+}
+
+impl<T: Ord + Clone> PersistentTree<T> {
+    // Returns a new tree with `value` added. The nodes on the path down to the
+    // new leaf are copied, which is why elements need to be Clone.
+    pub fn add(&self, value: T) -> PersistentTree<T> {
+        let mut path = Vec::new();
+        let mut tree = self;
+        while let PersistentTree::NonEmpty(ref node) = *tree {
+            let went_left = value <= node.element;
+            path.push((&**node, went_left));
+            tree = if went_left { &node.left } else { &node.right };
+        }
+
+        let leaf = PersistentTree::node(value, PersistentTree::Empty, PersistentTree::Empty);
+        PersistentTree::rebuild_path(path, leaf)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut tree = self;
+        while let PersistentTree::NonEmpty(ref node) = *tree {
+            tree = match value.cmp(&node.element) {
+                Ordering::Less => &node.left,
+                Ordering::Equal => return true,
+                Ordering::Greater => &node.right
+            };
+        }
+        false
+    }
+
+    // Returns a new tree with one occurrence of `value` removed, or a cheap
+    // clone of this one if `value` isn't present.
+    pub fn remove(&self, value: &T) -> PersistentTree<T> {
+        let mut path = Vec::new();
+        let mut tree = self;
+        let found = loop {
+            let node = match *tree {
+                PersistentTree::Empty => return self.clone(),
+                PersistentTree::NonEmpty(ref node) => node
+            };
+            match value.cmp(&node.element) {
+                Ordering::Equal => break node,
+                Ordering::Less => {
+                    path.push((&**node, true));
+                    tree = &node.left;
+                }
+                Ordering::Greater => {
+                    path.push((&**node, false));
+                    tree = &node.right;
+                }
+            }
+        };
+
+        // As in BinaryTree::remove_root(), a node with two children is
+        // replaced by the smallest element of its right subtree.
+        let replacement = match (&found.left, &found.right) {
+            (&PersistentTree::Empty, right) => right.clone(),
+            (left, &PersistentTree::Empty) => left.clone(),
+            (left, right) => {
+                let successor = right.min().expect("non-empty subtree has a minimum").clone();
+                let right = right.remove(&successor);
+                PersistentTree::node(successor, left.clone(), right)
+            }
+        };
+        PersistentTree::rebuild_path(path, replacement)
+    }
+
+    // Copies the nodes on `path`, bottom-up, hanging `subtree` where the path
+    // ended. Each entry records a node and whether the path went left from it.
+    fn rebuild_path(path: Vec<(&PersistentNode<T>, bool)>, subtree: PersistentTree<T>)
+        -> PersistentTree<T>
+    {
+        let mut tree = subtree;
+        for (node, went_left) in path.into_iter().rev() {
+            tree = if went_left {
+                PersistentTree::node(node.element.clone(), tree, node.right.clone())
+            } else {
+                PersistentTree::node(node.element.clone(), node.left.clone(), tree)
+            };
+        }
+        tree
+    }
+}
+
+// Nodes may be shared with other versions of the tree, so dropping a tree only
+// frees the nodes no other version still refers to. As with BinaryTree, we do
+// that with a worklist rather than recursion.
+impl<T> Drop for PersistentTree<T> {
+    fn drop(&mut self) {
+        let mut worklist = Vec::new();
+        worklist.extend(self.take_root());
+        while let Some(node) = worklist.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                worklist.extend(node.left.take_root());
+                worklist.extend(node.right.take_root());
+            }
+        }
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for PersistentTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> PersistentTree<T> {
+        iter.into_iter().fold(PersistentTree::new(), |tree, value| tree.add(value))
+    }
+}
+
+// In-order iterator, the same as BinaryTree's.
+pub struct Iter<'a, T> {
+    stack: Vec<&'a PersistentNode<T>>
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_edge(&mut self, mut tree: &'a PersistentTree<T>) {
+        while let PersistentTree::NonEmpty(ref node) = *tree {
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left_edge(&node.right);
+        Some(&node.element)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+
+use super::BinaryTree;
+
+// Rebuilds a tree from its pre-order walk, given as a stream of tokens:
+// Some(element) for a node, None for an empty subtree. So the tree with root
+// 2 and children 1 and 3 is fed in as
+//
+//     Some(2), Some(1), None, None, Some(3), None, None
+//
+// The builder keeps its own stack of unfinished nodes instead of recursing,
+// so it can rebuild a tree of any depth. Serialization and clone() both use it.
+pub(super) struct PreorderBuilder<T> {
+    stack: Vec<PartialNode<T>>
+}
+
+// A node whose element has been read but whose subtrees aren't finished yet.
+// `left` is filled in once the whole left subtree has been read.
+struct PartialNode<T> {
+    element: T,
+    left: Option<BinaryTree<T>>
+}
+
+impl<T> PreorderBuilder<T> {
+    pub(super) fn new() -> PreorderBuilder<T> {
+        PreorderBuilder { stack: Vec::new() }
+    }
+
+    // Feeds in the next token, returning the finished tree once the token
+    // that completes it has been fed in.
+    pub(super) fn push(&mut self, token: Option<T>) -> Option<BinaryTree<T>> {
+        match self.push_checked(token, |_, _| true) {
+            Ok(tree) => tree,
+            Err(OutOfOrder) => unreachable!("nothing is out of order when nothing is compared")
+        }
+    }
+
+    // push(), checking each element against its neighbours in order as the
+    // tree comes together: an element against the largest in its left
+    // subtree once that's finished, and against the smallest in its right
+    // subtree once that is. Every pair of neighbours is one or the other, and
+    // each finished subtree's edge is walked down only once, so checking
+    // costs O(n) over the whole tree.
+    fn push_checked<F>(&mut self, token: Option<T>, in_order: F)
+                       -> Result<Option<BinaryTree<T>>, OutOfOrder>
+        where F: Fn(&T, &T) -> bool
+    {
+        if let Some(element) = token {
+            self.stack.push(PartialNode { element, left: None });
+            return Ok(None);
+        }
+
+        // An empty subtree just finished. Hand it to the nearest node that is
+        // still waiting for its left subtree, closing off on the way every
+        // node whose right subtree this completes.
+        let mut finished = BinaryTree::Empty;
+        loop {
+            match self.stack.pop() {
+                None =>
+                    return Ok(Some(finished)),
+                Some(PartialNode { element, left: None }) => {
+                    if finished.max().is_some_and(|before| !in_order(before, &element)) {
+                        return Err(OutOfOrder);
+                    }
+                    self.stack.push(PartialNode { element, left: Some(finished) });
+                    return Ok(None);
+                }
+                Some(PartialNode { element, left: Some(left) }) => {
+                    if finished.min().is_some_and(|after| !in_order(&element, after)) {
+                        return Err(OutOfOrder);
+                    }
+                    finished = BinaryTree::node(element, left, finished);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "serde", feature = "std"))]
+impl<T: Ord> PreorderBuilder<T> {
+    // push() for rebuilding a tree from outside, which fails if the elements
+    // aren't in order, as they wouldn't be in a tree add() had built, so that
+    // searching it would go wrong.
+    pub(super) fn push_in_order(&mut self, token: Option<T>)
+                                -> Result<Option<BinaryTree<T>>, OutOfOrder> {
+        self.push_checked(token, |before, after| before <= after)
+    }
+}
+
+// The error from push_in_order() when an element comes after a greater one.
+pub(super) struct OutOfOrder;
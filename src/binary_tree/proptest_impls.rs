@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+use proptest::arbitrary::{any_with, Arbitrary};
+use proptest::collection::{self, SizeRange, VecStrategy};
+use proptest::strategy::{Map, Strategy};
+
+use super::BinaryTree;
+
+// Lets proptest generate trees, so that properties like "iteration is sorted"
+// or "remove undoes add" can be checked against thousands of random inputs:
+//
+//     proptest! {
+//         #[test]
+//         fn iteration_is_sorted(tree in any::<BinaryTree<i32>>()) {
+//             let elements: Vec<_> = tree.iter().collect();
+//             prop_assert!(elements.windows(2).all(|pair| pair[0] <= pair[1]));
+//         }
+//     }
+//
+// A tree is generated by adding a random sequence of elements one at a time,
+// in the order they were generated, so the trees come in all the shapes that
+// real insertion orders produce, lopsided ones included. Shrinking works on
+// that sequence: proptest tries dropping elements and shrinking the ones that
+// remain, and rebuilds the tree from what's left, so a failing case boils
+// down to a short list of small values.
+//
+// The parameters are the range of sequence lengths, which defaults to the
+// same 0..100 that proptest uses for a Vec, and the parameters for generating
+// each element.
+impl<T> Arbitrary for BinaryTree<T>
+    where T: Arbitrary + Ord
+{
+    type Parameters = (SizeRange, T::Parameters);
+    type Strategy = Map<VecStrategy<T::Strategy>, fn(Vec<T>) -> BinaryTree<T>>;
+
+    fn arbitrary_with((size, element): Self::Parameters) -> Self::Strategy {
+        collection::vec(any_with::<T>(element), size)
+            .prop_map(from_insertions as fn(Vec<T>) -> BinaryTree<T>)
+    }
+}
+
+fn from_insertions<T: Ord>(elements: Vec<T>) -> BinaryTree<T> {
+    elements.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use proptest::prelude::*;
+
+    use super::super::BinaryTree;
+
+    // Every subtree's cached size, checked against a count of its elements.
+    fn sizes_are_right<T>(tree: &BinaryTree<T>) -> bool {
+        match *tree {
+            BinaryTree::Empty => true,
+            BinaryTree::NonEmpty(ref node) =>
+                node.size == 1 + node.left.len() + node.right.len()
+                    && sizes_are_right(&node.left)
+                    && sizes_are_right(&node.right)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn iteration_is_sorted(tree in any::<BinaryTree<i32>>()) {
+            let elements: Vec<_> = tree.iter().collect();
+            prop_assert!(elements.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+
+        #[test]
+        fn len_counts_every_element(elements in any::<Vec<i16>>()) {
+            let tree: BinaryTree<i16> = elements.iter().cloned().collect();
+            prop_assert_eq!(tree.len(), elements.len());
+            prop_assert_eq!(tree.iter().count(), elements.len());
+            prop_assert!(sizes_are_right(&tree));
+        }
+
+        #[test]
+        fn iteration_gives_the_elements_sorted(elements in any::<Vec<i16>>()) {
+            let tree: BinaryTree<i16> = elements.iter().cloned().collect();
+            let mut sorted = elements;
+            sorted.sort();
+            prop_assert_eq!(tree.iter().cloned().collect::<Vec<_>>(), sorted);
+        }
+
+        #[test]
+        fn remove_undoes_add(mut tree in any::<BinaryTree<i8>>(), value in any::<i8>()) {
+            let before: Vec<_> = tree.iter().cloned().collect();
+            tree.add(value);
+            prop_assert!(tree.contains(&value));
+            prop_assert!(tree.remove(&value));
+            prop_assert_eq!(tree.iter().cloned().collect::<Vec<_>>(), before);
+            prop_assert!(sizes_are_right(&tree));
+        }
+
+        #[test]
+        fn removing_every_element_empties_the_tree(elements in any::<Vec<i8>>()) {
+            let mut tree: BinaryTree<i8> = elements.iter().cloned().collect();
+            for (i, value) in elements.iter().enumerate() {
+                prop_assert!(tree.remove(value));
+                prop_assert_eq!(tree.len(), elements.len() - i - 1);
+                prop_assert!(sizes_are_right(&tree));
+            }
+            prop_assert!(tree.is_empty());
+            prop_assert!(!tree.remove(&0));
+        }
+    }
+}
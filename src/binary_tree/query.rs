@@ -0,0 +1,83 @@
+use super::BinaryTree;
+
+// Lookups relative to a key or a position. None of these need the key to be
+// present in the tree: each one walks a single root-to-leaf path, using the
+// cached subtree sizes or remembering the best candidate seen so far.
+
+impl<T> BinaryTree<T> {
+    // The element at index `k` in sorted order, counting from zero, so
+    // select(0) is the minimum and select(len() / 2) is a median.
+    pub fn select(&self, k: usize) -> Option<&T> {
+        let mut k = k;
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            let left_len = node.left.len();
+            if k < left_len {
+                tree = &node.left;
+            } else if k == left_len {
+                return Some(&node.element);
+            } else {
+                k -= left_len + 1;
+                tree = &node.right;
+            }
+        }
+        None
+    }
+
+    // Counts the elements for which `in_prefix` holds, given that it holds for
+    // some run of the smallest elements and for none of the rest: x < key,
+    // say. Whole left subtrees are counted from their cached sizes.
+    pub(super) fn count_prefix<F>(&self, in_prefix: F) -> usize
+        where F: Fn(&T) -> bool
+    {
+        let mut count = 0;
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            if in_prefix(&node.element) {
+                count += node.left.len() + 1;
+                tree = &node.right;
+            } else {
+                tree = &node.left;
+            }
+        }
+        count
+    }
+}
+
+impl<T: Ord> BinaryTree<T> {
+    // The number of elements strictly less than `value`. When `value` is in
+    // the tree, this is the index select() would find it at.
+    pub fn rank(&self, value: &T) -> usize {
+        self.count_prefix(|element| element < value)
+    }
+
+    // The smallest element strictly greater than `value`.
+    pub fn successor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            if node.element > *value {
+                best = Some(&node.element);
+                tree = &node.left;
+            } else {
+                tree = &node.right;
+            }
+        }
+        best
+    }
+
+    // The largest element strictly less than `value`.
+    pub fn predecessor(&self, value: &T) -> Option<&T> {
+        let mut best = None;
+        let mut tree = self;
+        while let BinaryTree::NonEmpty(ref node) = *tree {
+            if node.element < *value {
+                best = Some(&node.element);
+                tree = &node.right;
+            } else {
+                tree = &node.left;
+            }
+        }
+        best
+    }
+}
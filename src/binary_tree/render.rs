@@ -0,0 +1,93 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt;
+
+use super::BinaryTree;
+
+impl<T: fmt::Display> BinaryTree<T> {
+    // Draws the tree sideways as indented ASCII art, one element per line,
+    // with the left subtree listed before the right:
+    //
+    //     Mercury
+    //     ├── Earth
+    //     │   ├── ∅
+    //     │   └── Jupiter
+    //     └── Venus
+    //
+    // A missing child is drawn as ∅ only when its sibling exists, so leaves
+    // stay on a single line. The walk uses an explicit stack rather than
+    // recursion so a badly unbalanced tree can still be printed.
+    pub fn render_ascii(&self) -> String {
+        let mut out = String::new();
+        let mut stack = vec![(self, String::new(), String::new())];
+
+        while let Some((tree, line_prefix, child_prefix)) = stack.pop() {
+            out.push_str(&line_prefix);
+            match *tree {
+                BinaryTree::Empty =>
+                    out.push('∅'),
+                BinaryTree::NonEmpty(ref node) => {
+                    out.push_str(&node.element.to_string());
+                    if !node.left.is_empty() || !node.right.is_empty() {
+                        stack.push((&node.right,
+                                    format!("{}└── ", child_prefix),
+                                    format!("{}    ", child_prefix)));
+                        stack.push((&node.left,
+                                    format!("{}├── ", child_prefix),
+                                    format!("{}│   ", child_prefix)));
+                    }
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    // Emits the tree as a Graphviz digraph. Nodes are numbered in pre-order
+    // and labelled with their elements; edges are labelled L and R. As with
+    // render_ascii(), a missing child gets a small point node when its sibling
+    // exists, so Graphviz still draws the other child on the correct side.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph BinaryTree {\n");
+        let mut next_id = 0;
+        let mut stack = vec![(self, None)];
+
+        while let Some((tree, parent)) = stack.pop() {
+            let id = next_id;
+            next_id += 1;
+
+            match *tree {
+                BinaryTree::Empty =>
+                    out.push_str(&format!("    n{} [shape=point];\n", id)),
+                BinaryTree::NonEmpty(ref node) => {
+                    let label = escape_dot(&node.element.to_string());
+                    out.push_str(&format!("    n{} [label=\"{}\"];\n", id, label));
+                    if !node.left.is_empty() || !node.right.is_empty() {
+                        stack.push((&node.right, Some((id, "R"))));
+                        stack.push((&node.left, Some((id, "L"))));
+                    }
+                }
+            }
+
+            if let Some((parent_id, side)) = parent {
+                out.push_str(&format!("    n{} -> n{} [label=\"{}\"];\n",
+                                      parent_id, id, side));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<T: fmt::Display> fmt::Display for BinaryTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render_ascii())
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use super::preorder::{OutOfOrder, PreorderBuilder};
+use super::{BinaryTree, TreeNode};
+
+// A tree is serialized as its pre-order walk, written as a flat sequence of
+// optional elements: Some(element) for a node, None for an empty subtree. So
+// the tree with root 2 and children 1 and 3 becomes, in JSON,
+//
+//     [2, 1, null, null, 3, null, null]
+//
+// Keeping the encoding flat means neither direction needs to recurse, so a
+// huge or badly unbalanced tree can't overflow the stack on the way in or out.
+// Reading a tree back keeps its shape, so it fails if the elements aren't in
+// order, rather than returning a tree that can't be searched.
+
+fn write_preorder<T, S>(tree: &BinaryTree<T>, seq: &mut S) -> Result<(), S::Error>
+    where T: Serialize, S: SerializeSeq
+{
+    let mut stack = vec![tree];
+
+    while let Some(tree) = stack.pop() {
+        match *tree {
+            BinaryTree::Empty =>
+                seq.serialize_element(&None::<&T>)?,
+            BinaryTree::NonEmpty(ref node) => {
+                seq.serialize_element(&Some(&node.element))?;
+                stack.push(&node.right);
+                stack.push(&node.left);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl<T: Serialize> Serialize for BinaryTree<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        write_preorder(self, &mut seq)?;
+        seq.end()
+    }
+}
+
+// A node is serialized exactly like the non-empty tree rooted at it.
+impl<T: Serialize> Serialize for TreeNode<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&Some(&self.element))?;
+        write_preorder(&self.left, &mut seq)?;
+        write_preorder(&self.right, &mut seq)?;
+        seq.end()
+    }
+}
+
+struct PreorderVisitor<T> {
+    marker: PhantomData<T>
+}
+
+impl<'de, T: Deserialize<'de> + Ord> Visitor<'de> for PreorderVisitor<T> {
+    type Value = BinaryTree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a pre-order sequence of optional tree elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BinaryTree<T>, A::Error> {
+        let mut builder = PreorderBuilder::new();
+        let mut read = 0;
+
+        loop {
+            let token: Option<T> = match seq.next_element()? {
+                Some(token) => token,
+                None => return Err(de::Error::invalid_length(read, &self))
+            };
+            read += 1;
+
+            let finished = builder.push_in_order(token)
+                .map_err(|OutOfOrder| de::Error::custom("tree elements are out of order"))?;
+            if let Some(tree) = finished {
+                if seq.next_element::<IgnoredAny>()?.is_some() {
+                    return Err(de::Error::custom("trailing elements after a complete tree"));
+                }
+                return Ok(tree);
+            }
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for BinaryTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BinaryTree<T>, D::Error> {
+        deserializer.deserialize_seq(PreorderVisitor { marker: PhantomData })
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for TreeNode<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TreeNode<T>, D::Error> {
+        match BinaryTree::deserialize(deserializer)?.take_node() {
+            None =>
+                Err(de::Error::custom("expected a tree node, found an empty tree")),
+            Some(node) =>
+                Ok(*node)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::vec::Vec;
+    use std::string::ToString;
+
+    use super::*;
+
+    fn from_json(json: &str) -> Result<BinaryTree<u32>, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn round_trip_keeps_the_shape() {
+        let tree: BinaryTree<u32> = [2, 1, 3, 3, 0].iter().cloned().collect();
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(json, "[2,1,0,null,null,null,3,3,null,null,null]");
+        let back = from_json(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert!(back.iter().cloned().eq([0, 1, 2, 3, 3]));
+    }
+
+    #[test]
+    fn out_of_order_elements_are_rejected() {
+        for json in ["[2,3,null,null,1,null,null]",
+                     "[2,null,1,null,null]",
+                     "[5,1,null,6,null,null,null]",
+                     "[1,null,5,3,null,2,null,null,null]"] {
+            let error = from_json(json).unwrap_err().to_string();
+            assert!(error.starts_with("tree elements are out of order"), "{}: {}", json, error);
+        }
+        assert_eq!(from_json("[2,2,null,null,2,null,null]").unwrap().len(), 3);
+        assert_eq!(from_json("[null]").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn a_chain_deeper_than_the_stack() {
+        let len = 100_000;
+        let mut json = Vec::new();
+        for element in 0..len {
+            json.push(element.to_string());
+            json.push("null".to_string());
+        }
+        json.push("null".to_string());
+        let tree = from_json(&std::format!("[{}]", json.join(","))).unwrap();
+        assert_eq!(tree.len(), len as usize);
+        assert_eq!(tree.max(), Some(&(len - 1)));
+    }
+}
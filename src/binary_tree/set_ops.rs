@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::Peekable;
+
+use super::{BinaryTree, Range};
+
+// Set operations between two trees, in the style of BTreeSet's. Each one walks
+// both trees in order side by side, the way you'd merge two sorted lists, so
+// comparing trees of sizes n and m takes O(n + m) with no lookups.
+//
+// Duplicates are matched up one for one: if `a` holds three 5s and `b` holds
+// one, the intersection has one 5 and the difference a - b has two.
+
+pub struct Intersection<'a, T> {
+    a: Range<'a, T>,
+    b: Peekable<Range<'a, T>>
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let mut a = self.a.next()?;
+        loop {
+            match a.cmp(self.b.peek()?) {
+                Ordering::Less =>
+                    a = self.a.next()?,
+                Ordering::Equal => {
+                    self.b.next();
+                    return Some(a);
+                }
+                Ordering::Greater => {
+                    self.b.next();
+                }
+            }
+        }
+    }
+}
+
+pub struct Difference<'a, T> {
+    a: Range<'a, T>,
+    b: Peekable<Range<'a, T>>
+}
+
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let mut a = self.a.next()?;
+        loop {
+            let b = match self.b.peek() {
+                Some(b) => b,
+                None => return Some(a)
+            };
+            match a.cmp(b) {
+                Ordering::Less =>
+                    return Some(a),
+                Ordering::Equal => {
+                    self.b.next();
+                    a = self.a.next()?;
+                }
+                Ordering::Greater => {
+                    self.b.next();
+                }
+            }
+        }
+    }
+}
+
+// The result of comparing two trees: every element of either tree ends up in
+// exactly one of the three lists, each in sorted order.
+pub struct TreeDiff<'a, T> {
+    pub only_in_left: Vec<&'a T>,
+    pub only_in_right: Vec<&'a T>,
+    pub common: Vec<&'a T>
+}
+
+impl<'a, T> TreeDiff<'a, T> {
+    // True if the two trees held the same elements.
+    pub fn is_unchanged(&self) -> bool {
+        self.only_in_left.is_empty() && self.only_in_right.is_empty()
+    }
+}
+
+impl<T: Ord> BinaryTree<T> {
+    // Compares this tree (the left side) with `other` (the right side) in a
+    // single merged pass over both.
+    pub fn diff<'a>(&'a self, other: &'a BinaryTree<T>) -> TreeDiff<'a, T> {
+        let mut diff = TreeDiff {
+            only_in_left: Vec::new(),
+            only_in_right: Vec::new(),
+            common: Vec::new()
+        };
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+
+        loop {
+            let ordering = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => return diff
+            };
+            match ordering {
+                Ordering::Less =>
+                    diff.only_in_left.extend(left.next()),
+                Ordering::Equal => {
+                    diff.common.extend(left.next());
+                    right.next();
+                }
+                Ordering::Greater =>
+                    diff.only_in_right.extend(right.next())
+            }
+        }
+    }
+
+    // The elements found in both this tree and `other`, in sorted order.
+    pub fn intersection<'a>(&'a self, other: &'a BinaryTree<T>) -> Intersection<'a, T> {
+        Intersection {
+            a: self.range(..),
+            b: other.range(..).peekable()
+        }
+    }
+
+    // The elements of this tree that are not in `other`, in sorted order.
+    pub fn difference<'a>(&'a self, other: &'a BinaryTree<T>) -> Difference<'a, T> {
+        Difference {
+            a: self.range(..),
+            b: other.range(..).peekable()
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::BinaryTree;
+
+// Methods for inspecting the shape of a tree rather than its contents. Both
+// walk the tree with an explicit work stack instead of recursing, so they are
+// safe to call on a degenerate tree that is millions of levels deep, which is
+// exactly the kind of tree you'd want to check for.
+
+impl<T> BinaryTree<T> {
+    // The number of nodes on the longest path from the root down to a leaf.
+    // An empty tree has height 0 and a single node has height 1.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut level = vec![self];
+
+        loop {
+            let mut next_level = Vec::new();
+            for tree in level {
+                if let BinaryTree::NonEmpty(ref node) = *tree {
+                    next_level.push(&node.left);
+                    next_level.push(&node.right);
+                }
+            }
+            if next_level.is_empty() {
+                return height;
+            }
+            height += 1;
+            level = next_level;
+        }
+    }
+
+    // True if, at every node, the heights of the left and right subtrees
+    // differ by at most one: the same condition an AVL tree maintains.
+    pub fn is_balanced(&self) -> bool {
+        // A post-order walk. A node is pushed once to schedule its subtrees,
+        // and again (marked `true`) to combine their heights once both have
+        // been computed and left on the `heights` stack.
+        let mut work = vec![(self, false)];
+        let mut heights: Vec<usize> = Vec::new();
+
+        while let Some((tree, subtrees_done)) = work.pop() {
+            match *tree {
+                BinaryTree::Empty =>
+                    heights.push(0),
+                BinaryTree::NonEmpty(ref node) if !subtrees_done => {
+                    work.push((tree, true));
+                    work.push((&node.right, false));
+                    work.push((&node.left, false));
+                }
+                BinaryTree::NonEmpty(_) => {
+                    let right = heights.pop().unwrap();
+                    let left = heights.pop().unwrap();
+                    if left > right + 1 || right > left + 1 {
+                        return false;
+                    }
+                    heights.push(1 + left.max(right));
+                }
+            }
+        }
+
+        true
+    }
+}
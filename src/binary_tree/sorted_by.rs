@@ -0,0 +1,130 @@
+use core::cmp::Ordering;
+
+use super::{BinaryTree, IntoIter, Iter};
+
+// A BinaryTree that orders its elements with a comparator of the caller's
+// choosing instead of Ord. That allows elements that aren't Ord at all, like
+// floats, as well as a different order for types that are, like strings
+// compared case-insensitively.
+//
+// The comparator can't live in the BinaryTree enum itself, so it sits
+// alongside the tree here. BinaryTreeBy deliberately doesn't hand out the
+// inner tree: BinaryTree's own contains(), remove() and so on would compare
+// with Ord and go looking in the wrong places. Every method here that depends
+// on the order goes through the stored comparator instead.
+pub struct BinaryTreeBy<T, C> {
+    tree: BinaryTree<T>,
+    compare: C
+}
+
+impl<T> BinaryTree<T> {
+    // An empty tree ordered by `compare`:
+    //
+    //     let mut tree = BinaryTree::new_by(|a: &f64, b: &f64| a.total_cmp(b));
+    pub fn new_by<C>(compare: C) -> BinaryTreeBy<T, C>
+        where C: Fn(&T, &T) -> Ordering
+    {
+        BinaryTreeBy { tree: BinaryTree::Empty, compare }
+    }
+
+    // An empty tree ordered by the key `key` extracts from each element:
+    //
+    //     let mut tree = BinaryTree::new_by_key(|s: &String| s.to_lowercase());
+    pub fn new_by_key<K, F>(key: F) -> BinaryTreeBy<T, impl Fn(&T, &T) -> Ordering>
+        where K: Ord, F: Fn(&T) -> K
+    {
+        BinaryTree::new_by(move |a: &T, b: &T| key(a).cmp(&key(b)))
+    }
+}
+
+impl<T, C> BinaryTreeBy<T, C>
+    where C: Fn(&T, &T) -> Ordering
+{
+    pub fn add(&mut self, value: T) {
+        self.tree.add_by(value, &self.compare);
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    // Finds the element that compares equal to `value`. With a comparator
+    // that ignores case, say, that may not be identical to `value`.
+    pub fn get(&self, value: &T) -> Option<&T> {
+        let compare = &self.compare;
+        self.tree.find_by(|element| compare(value, element))
+    }
+
+    // Removes and returns one element that compares equal to `value`.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let compare = &self.compare;
+        self.tree.remove_by(|element| compare(value, element))
+    }
+
+    // The rest don't depend on how elements are compared, only on where they
+    // sit in the tree, so they pass straight through to it.
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.tree.iter()
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.tree.min()
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.tree.max()
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.tree.pop_min()
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.tree.pop_max()
+    }
+
+    pub fn select(&self, k: usize) -> Option<&T> {
+        self.tree.select(k)
+    }
+
+    pub fn height(&self) -> usize {
+        self.tree.height()
+    }
+}
+
+impl<T, C> Extend<T> for BinaryTreeBy<T, C>
+    where C: Fn(&T, &T) -> Ordering
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a BinaryTreeBy<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.tree.iter()
+    }
+}
+
+impl<T, C> IntoIterator for BinaryTreeBy<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.tree.into_iter()
+    }
+}
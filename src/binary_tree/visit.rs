@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use super::{BinaryTree, TreeNode};
+
+// What a visitor wants to happen next after one of its callbacks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visit {
+    // Carry on with the walk as normal.
+    Continue,
+    // Don't descend into any of this node's children that haven't been
+    // visited yet. The node's own remaining callbacks still run, so a
+    // visitor that skips in pre_order() still gets in_order() and
+    // post_order() for that node, just with nothing visited in between.
+    SkipSubtree,
+    // End the walk immediately.
+    Stop
+}
+
+// A visitor gets a callback at each of the three points a depth-first walk
+// passes a node: before its left subtree, between its subtrees, and after its
+// right subtree. Each callback defaults to doing nothing, so a visitor only
+// implements the ones it cares about.
+//
+// Iterators are simpler when all you need is the elements in some order, but
+// they can't be told to skip part of the tree, and each one only sees a node
+// once. Something like "find the first element above a threshold, pruning
+// subtrees that can't contain one" needs both.
+pub trait TreeVisitor<T> {
+    fn pre_order(&mut self, _element: &T) -> Visit {
+        Visit::Continue
+    }
+
+    fn in_order(&mut self, _element: &T) -> Visit {
+        Visit::Continue
+    }
+
+    fn post_order(&mut self, _element: &T) -> Visit {
+        Visit::Continue
+    }
+}
+
+// The three points at which a walk passes each node, in the order it passes
+// them.
+enum Stage {
+    Pre,
+    In,
+    Post
+}
+
+impl<T> BinaryTree<T> {
+    // Walks the tree depth-first, calling the visitor's callbacks as it goes.
+    // Returns ControlFlow::Break if the visitor stopped the walk early, and
+    // ControlFlow::Continue if it ran to the end.
+    //
+    // Each node goes on the work stack once per stage, along with whether its
+    // unvisited children have been skipped, so the walk never recurses and
+    // handles degenerate trees of any depth.
+    pub fn walk<V: TreeVisitor<T>>(&self, visitor: &mut V) -> ControlFlow<()> {
+        let mut stack: Vec<(&TreeNode<T>, Stage, bool)> = Vec::new();
+        if let BinaryTree::NonEmpty(ref node) = *self {
+            stack.push((node, Stage::Pre, false));
+        }
+
+        while let Some((node, stage, skipped)) = stack.pop() {
+            let visit = match stage {
+                Stage::Pre => visitor.pre_order(&node.element),
+                Stage::In => visitor.in_order(&node.element),
+                Stage::Post => visitor.post_order(&node.element)
+            };
+            let skipped = match visit {
+                Visit::Continue => skipped,
+                Visit::SkipSubtree => true,
+                Visit::Stop => return ControlFlow::Break(())
+            };
+
+            // Schedule the node's next stage, then the child that comes
+            // before it, so that the child is visited first.
+            let child = match stage {
+                Stage::Pre => {
+                    stack.push((node, Stage::In, skipped));
+                    &node.left
+                }
+                Stage::In => {
+                    stack.push((node, Stage::Post, skipped));
+                    &node.right
+                }
+                Stage::Post => continue
+            };
+            if let (false, BinaryTree::NonEmpty(ref child)) = (skipped, child) {
+                stack.push((child, Stage::Pre, false));
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
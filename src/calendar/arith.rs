@@ -0,0 +1,372 @@
+// The arithmetic of each calendar, in terms of Julian Day Numbers (see
+// Calendar::epoch()). Years use astronomical numbering where a calendar is
+// counted back before its year 1, so the Gregorian year 0 is 1 BC and -1 is
+// 2 BC. Most of the formulas are from Reingold and Dershowitz, Calendrical
+// Calculations; the Gregorian and Julian ones count from 1 March of year 0
+// so that the leap day falls at the end of each year, as in Howard
+// Hinnant's date algorithms.
+//
+// The Chinese calendar depends on the positions of the sun and moon rather
+// than on arithmetic, so everything here is None for it.
+
+use super::Calendar;
+
+// The Julian Day Number of 1 March of year 0 in each calendar.
+const GREGORIAN_MARCH_0: i64 = 1_721_120;
+const JULIAN_MARCH_0: i64 = 1_721_118;
+
+// Thai Buddhist years are Gregorian ones, numbered from 543 BC.
+const BUDDHIST_OFFSET: i64 = 543;
+
+pub(super) fn is_leap_year(calendar: Calendar, year: i64) -> Option<bool> {
+    Some(match calendar {
+        Calendar::Gregorian => gregorian_leap(year),
+        Calendar::Buddhist => gregorian_leap(year - BUDDHIST_OFFSET),
+        Calendar::Julian => year.rem_euclid(4) == 0,
+        Calendar::Ethiopian => year.rem_euclid(4) == 3,
+        Calendar::Hebrew => hebrew_leap(year),
+        Calendar::Islamic => (14 + 11 * year).rem_euclid(30) < 11,
+        Calendar::Chinese => return None
+    })
+}
+
+pub(super) fn months_in_year(calendar: Calendar, year: i64) -> Option<u8> {
+    match calendar {
+        Calendar::Gregorian | Calendar::Buddhist | Calendar::Julian | Calendar::Islamic => Some(12),
+        Calendar::Ethiopian => Some(13),
+        Calendar::Hebrew => Some(if hebrew_leap(year) { 13 } else { 12 }),
+        Calendar::Chinese => None
+    }
+}
+
+// The length of `month` in `year`, or None if the year has no such month.
+pub(super) fn days_in_month(calendar: Calendar, year: i64, month: u8) -> Option<u8> {
+    if month == 0 || month > months_in_year(calendar, year)? {
+        return None;
+    }
+    let leap = is_leap_year(calendar, year)?;
+    Some(match calendar {
+        Calendar::Gregorian | Calendar::Buddhist | Calendar::Julian => match month {
+            2 => if leap { 29 } else { 28 },
+            4 | 6 | 9 | 11 => 30,
+            _ => 31
+        },
+        Calendar::Ethiopian => match month {
+            13 => if leap { 6 } else { 5 },
+            _ => 30
+        },
+        Calendar::Islamic => match month {
+            12 if leap => 30,
+            _ => if month % 2 == 1 { 30 } else { 29 }
+        },
+        Calendar::Hebrew => hebrew_days_in_month(year, month),
+        Calendar::Chinese => return None
+    })
+}
+
+// Whether `month` is a handful of days added to make up the year's length,
+// rather than a month proper. That's the Ethiopian Pagume.
+#[cfg(feature = "std")]
+pub(super) fn is_epagomenal(calendar: Calendar, month: u8) -> bool {
+    calendar == Calendar::Ethiopian && month == 13
+}
+
+// The Julian Day Number of a date, or None if the date doesn't exist.
+pub(super) fn to_julian_day(calendar: Calendar, year: i64, month: u8, day: u8) -> Option<i64> {
+    if day == 0 || day > days_in_month(calendar, year, month)? {
+        return None;
+    }
+    let (m, d) = (i64::from(month), i64::from(day));
+    Some(match calendar {
+        Calendar::Gregorian => gregorian_to_jdn(year, m, d),
+        Calendar::Buddhist => gregorian_to_jdn(year - BUDDHIST_OFFSET, m, d),
+        Calendar::Julian => julian_to_jdn(year, m, d),
+        Calendar::Ethiopian => ethiopian_to_jdn(year, m, d),
+        Calendar::Islamic => islamic_to_jdn(year, m, d),
+        Calendar::Hebrew => hebrew_new_year(year) + hebrew_days_before(year, month) + d - 1,
+        Calendar::Chinese => return None
+    })
+}
+
+// The year, month and day of a Julian Day Number.
+pub(super) fn from_julian_day(calendar: Calendar, jdn: i64) -> Option<(i64, u8, u8)> {
+    Some(match calendar {
+        Calendar::Gregorian => gregorian_from_jdn(jdn),
+        Calendar::Buddhist => {
+            let (year, month, day) = gregorian_from_jdn(jdn);
+            (year + BUDDHIST_OFFSET, month, day)
+        }
+        Calendar::Julian => julian_from_jdn(jdn),
+        Calendar::Ethiopian => ethiopian_from_jdn(jdn),
+        Calendar::Islamic => islamic_from_jdn(jdn),
+        Calendar::Hebrew => hebrew_from_jdn(jdn),
+        Calendar::Chinese => return None
+    })
+}
+
+fn gregorian_leap(year: i64) -> bool {
+    year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+}
+
+// The day of a year that starts on 1 March, counting from 0, and the year
+// that starts then.
+fn march_based(year: i64, month: i64, day: i64) -> (i64, i64) {
+    let year = if month <= 2 { year - 1 } else { year };
+    let month = (month + 9) % 12;
+    (year, (153 * month + 2) / 5 + day - 1)
+}
+
+// The inverse of march_based().
+fn from_march_based(year: i64, day_of_year: i64) -> (i64, u8, u8) {
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = if month <= 2 { year + 1 } else { year };
+    (year, month as u8, day as u8)
+}
+
+fn gregorian_to_jdn(year: i64, month: i64, day: i64) -> i64 {
+    let (year, day_of_year) = march_based(year, month, day);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    GREGORIAN_MARCH_0 + era * 146_097 + day_of_era
+}
+
+fn gregorian_from_jdn(jdn: i64) -> (i64, u8, u8) {
+    let days = jdn - GREGORIAN_MARCH_0;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    from_march_based(era * 400 + year_of_era, day_of_year)
+}
+
+fn julian_to_jdn(year: i64, month: i64, day: i64) -> i64 {
+    let (year, day_of_year) = march_based(year, month, day);
+    JULIAN_MARCH_0 + year.div_euclid(4) * 1461 + year.rem_euclid(4) * 365 + day_of_year
+}
+
+fn julian_from_jdn(jdn: i64) -> (i64, u8, u8) {
+    let days = jdn - JULIAN_MARCH_0;
+    let cycle = days.div_euclid(1461);
+    let day_of_cycle = days.rem_euclid(1461);
+    let year_of_cycle = (day_of_cycle - day_of_cycle / 1460) / 365;
+    from_march_based(cycle * 4 + year_of_cycle, day_of_cycle - 365 * year_of_cycle)
+}
+
+// Ethiopian years are twelve months of 30 days and a thirteenth of 5, or 6
+// in the year before a Julian leap year.
+fn ethiopian_to_jdn(year: i64, month: i64, day: i64) -> i64 {
+    Calendar::Ethiopian.epoch() - 1 + 365 * (year - 1) + year.div_euclid(4) + 30 * (month - 1) + day
+}
+
+fn ethiopian_from_jdn(jdn: i64) -> (i64, u8, u8) {
+    let year = (4 * (jdn - Calendar::Ethiopian.epoch()) + 1463).div_euclid(1461);
+    let day_of_year = jdn - ethiopian_to_jdn(year, 1, 1);
+    (year, (day_of_year / 30 + 1) as u8, (day_of_year % 30 + 1) as u8)
+}
+
+// The tabular Islamic calendar: months alternate between 30 and 29 days,
+// with the last getting a 30th day in 11 years of every 30.
+fn islamic_to_jdn(year: i64, month: i64, day: i64) -> i64 {
+    Calendar::Islamic.epoch() - 1
+        + (year - 1) * 354
+        + (3 + 11 * year).div_euclid(30)
+        + 29 * (month - 1)
+        + month / 2
+        + day
+}
+
+fn islamic_from_jdn(jdn: i64) -> (i64, u8, u8) {
+    let year = (30 * (jdn - Calendar::Islamic.epoch()) + 10_646).div_euclid(10_631);
+    let day_of_year = jdn - islamic_to_jdn(year, 1, 1);
+    let month = ((11 * day_of_year + 330) / 325).min(12);
+    let day = jdn - islamic_to_jdn(year, month, 1) + 1;
+    (year, month as u8, day as u8)
+}
+
+// Seven of every nineteen Hebrew years have a thirteenth month.
+fn hebrew_leap(year: i64) -> bool {
+    (7 * year + 1).rem_euclid(19) < 7
+}
+
+// Days from the epoch to the molad of Tishrei of `year`, put off by a day
+// when it falls on a day that Rosh Hashanah can't.
+fn hebrew_elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let day = 29 * months + parts.div_euclid(25_920);
+    if (3 * (day + 1)).rem_euclid(7) < 3 { day + 1 } else { day }
+}
+
+// The Julian Day Number of 1 Tishrei, the first day of `year`.
+fn hebrew_new_year(year: i64) -> i64 {
+    let this_year = hebrew_elapsed_days(year);
+    let next_year = hebrew_elapsed_days(year + 1);
+    let correction = if next_year - this_year == 356 {
+        2
+    } else if this_year - hebrew_elapsed_days(year - 1) == 382 {
+        1
+    } else {
+        0
+    };
+    Calendar::Hebrew.epoch() + this_year + correction
+}
+
+fn hebrew_year_length(year: i64) -> i64 {
+    hebrew_new_year(year + 1) - hebrew_new_year(year)
+}
+
+// Months are numbered from Tishrei, so in a leap year the sixth and seventh
+// are Adar I and Adar II, and in a common year the sixth is Adar and the
+// seventh Nisan. Cheshvan and Kislev are the months that stretch or shrink
+// to give the year its length.
+fn hebrew_days_in_month(year: i64, month: u8) -> u8 {
+    let leap = hebrew_leap(year);
+    let length = hebrew_year_length(year);
+    match month {
+        1 | 5 => 30,
+        2 => if length % 10 == 5 { 30 } else { 29 },
+        3 => if length % 10 == 3 { 29 } else { 30 },
+        4 => 29,
+        6 => if leap { 30 } else { 29 },
+        7 if leap => 29,
+        _ => {
+            let after_adar = month - if leap { 8 } else { 7 };
+            if after_adar.is_multiple_of(2) { 30 } else { 29 }
+        }
+    }
+}
+
+// The number in `to_year` of the month numbered `month` in `from_year`,
+// matched by name. Only the months after Shevat move; Adar, in a common
+// year, corresponds to Adar II in a leap one, and Adar I, in a leap year, to
+// Adar in a common one.
+#[cfg(feature = "std")]
+pub(super) fn hebrew_month_in_year(from_year: i64, month: u8, to_year: i64) -> u8 {
+    match (hebrew_leap(from_year), hebrew_leap(to_year)) {
+        (true, false) if month >= 7 => month - 1,
+        (false, true) if month >= 6 => month + 1,
+        _ => month
+    }
+}
+
+fn hebrew_days_before(year: i64, month: u8) -> i64 {
+    (1..month).map(|earlier| i64::from(hebrew_days_in_month(year, earlier))).sum()
+}
+
+fn hebrew_from_jdn(jdn: i64) -> (i64, u8, u8) {
+    // An estimate from the mean year, which is never more than a year out.
+    let mut year = (jdn - Calendar::Hebrew.epoch()) * 98_496 / 35_975_351 + 1;
+    while hebrew_new_year(year) > jdn {
+        year -= 1;
+    }
+    while hebrew_new_year(year + 1) <= jdn {
+        year += 1;
+    }
+    let mut day = jdn - hebrew_new_year(year);
+    let mut month = 1;
+    loop {
+        let length = i64::from(hebrew_days_in_month(year, month));
+        if day < length {
+            return (year, month, day as u8 + 1);
+        }
+        day -= length;
+        month += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDARS: [Calendar; 6] = [
+        Calendar::Gregorian,
+        Calendar::Julian,
+        Calendar::Buddhist,
+        Calendar::Ethiopian,
+        Calendar::Islamic,
+        Calendar::Hebrew
+    ];
+
+    // Dates whose Julian Day Numbers are well known.
+    const REFERENCE_DATES: [(Calendar, i64, u8, u8, i64); 16] = [
+        (Calendar::Gregorian, 2000, 1, 1, 2_451_545),
+        (Calendar::Gregorian, 1970, 1, 1, 2_440_588),
+        (Calendar::Gregorian, 1582, 10, 15, 2_299_161),
+        (Calendar::Gregorian, 1, 1, 1, 1_721_426),
+        (Calendar::Gregorian, -4713, 11, 24, 0),
+        (Calendar::Gregorian, 2024, 2, 29, 2_460_370),
+        (Calendar::Julian, 1582, 10, 4, 2_299_160),
+        (Calendar::Julian, 2000, 1, 1, 2_451_558),
+        (Calendar::Julian, -4712, 1, 1, 0),
+        (Calendar::Buddhist, 2543, 1, 1, 2_451_545),
+        (Calendar::Ethiopian, 1, 1, 1, 1_724_221),
+        // Enkutatash, the Ethiopian new year, on 12 September 2023.
+        (Calendar::Ethiopian, 2016, 1, 1, 2_460_200),
+        (Calendar::Islamic, 1, 1, 1, 1_948_440),
+        (Calendar::Hebrew, 1, 1, 1, 347_998),
+        // Rosh Hashanah on 16 September 2023 and 3 October 2024.
+        (Calendar::Hebrew, 5784, 1, 1, 2_460_204),
+        (Calendar::Hebrew, 5785, 1, 1, 2_460_587)
+    ];
+
+    #[test]
+    fn reference_dates() {
+        for (calendar, year, month, day, jdn) in REFERENCE_DATES {
+            assert_eq!(to_julian_day(calendar, year, month, day), Some(jdn),
+                       "{} {}-{}-{}", calendar, year, month, day);
+            assert_eq!(from_julian_day(calendar, jdn), Some((year, month, day)),
+                       "{} {}", calendar, jdn);
+        }
+    }
+
+    // Every day over a few centuries either side of year 1 and of today,
+    // taken to a date and back.
+    #[test]
+    fn every_day_round_trips() {
+        let ranges = [1_600_000..1_800_000, 2_400_000..2_500_000];
+        for calendar in CALENDARS {
+            for jdn in ranges.iter().cloned().flatten() {
+                let (year, month, day) = from_julian_day(calendar, jdn).unwrap();
+                assert_eq!(to_julian_day(calendar, year, month, day), Some(jdn),
+                           "{} {} came out as {}-{}-{}", calendar, jdn, year, month, day);
+            }
+        }
+    }
+
+    // Every date in a span of years, taken to a day number and back, with
+    // consecutive dates on consecutive days.
+    #[test]
+    fn every_date_round_trips() {
+        for calendar in CALENDARS {
+            let (start, _, _) = from_julian_day(calendar, 2_400_000).unwrap();
+            let mut previous = None;
+            for year in start..start + 200 {
+                for month in 1..=months_in_year(calendar, year).unwrap() {
+                    for day in 1..=days_in_month(calendar, year, month).unwrap() {
+                        let jdn = to_julian_day(calendar, year, month, day).unwrap();
+                        assert_eq!(from_julian_day(calendar, jdn), Some((year, month, day)));
+                        if let Some(previous) = previous {
+                            assert_eq!(jdn, previous + 1,
+                                       "{} {}-{}-{}", calendar, year, month, day);
+                        }
+                        previous = Some(jdn);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dates_that_dont_exist() {
+        assert_eq!(to_julian_day(Calendar::Gregorian, 1900, 2, 29), None);
+        assert_eq!(to_julian_day(Calendar::Julian, 1900, 2, 29), Some(2_415_092));
+        assert_eq!(to_julian_day(Calendar::Gregorian, 2023, 13, 1), None);
+        assert_eq!(to_julian_day(Calendar::Gregorian, 2023, 1, 0), None);
+        assert_eq!(to_julian_day(Calendar::Chinese, 2023, 1, 1), None);
+        assert_eq!(from_julian_day(Calendar::Chinese, 2_451_545), None);
+    }
+}
@@ -0,0 +1,164 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use super::{arith, Calendar};
+
+// A day as some calendar names it. Years use astronomical numbering where a
+// calendar is counted back before its year 1: the Gregorian year 0 is 1 BC.
+// Months count from 1 in the order the calendar's year runs, as listed by
+// Calendar::month_names().
+//
+// The fields are public so dates can be written out directly; nothing
+// checks them until the date is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CalendarDate {
+    pub calendar: Calendar,
+    pub year: i32,
+    pub month: u8,
+    pub day: u8
+}
+
+// The error returned when a date can't be worked with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateError {
+    // The calendar's dates can't be worked out by arithmetic. That's the
+    // Chinese calendar, which follows the sun and moon.
+    UnsupportedCalendar(Calendar),
+    // A month or day that the year doesn't have, like 30 February.
+    InvalidDate(CalendarDate),
+    // A date whose year, in the calendar converted to, doesn't fit in an
+    // i32.
+    OutOfRange
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DateError::UnsupportedCalendar(calendar) =>
+                write!(f, "dates in the {} calendar are not supported", calendar.name()),
+            DateError::InvalidDate(date) =>
+                write!(f, "{}-{:02}-{:02} is not a date in the {} calendar",
+                       date.year, date.month, date.day, date.calendar.name()),
+            DateError::OutOfRange =>
+                f.write_str("date is out of range")
+        }
+    }
+}
+
+impl core::error::Error for DateError {}
+
+impl CalendarDate {
+    // Builds a date, checking that the calendar has it.
+    pub fn new(calendar: Calendar, year: i32, month: u8, day: u8) -> Result<CalendarDate, DateError> {
+        let date = CalendarDate { calendar, year, month, day };
+        date.to_julian_day()?;
+        Ok(date)
+    }
+
+    // The same day in another calendar:
+    //
+    //     // Ethiopian New Year, 1 Meskerem 2017, was 11 September 2024.
+    //     let enkutatash = CalendarDate::new(Calendar::Ethiopian, 2017, 1, 1)?;
+    //     assert_eq!(enkutatash.convert_to(Calendar::Gregorian)?,
+    //                CalendarDate::new(Calendar::Gregorian, 2024, 9, 11)?);
+    pub fn convert_to(self, calendar: Calendar) -> Result<CalendarDate, DateError> {
+        CalendarDate::from_julian_day(calendar, self.to_julian_day()?)
+    }
+
+    // The Julian Day Number of this date, the day count that all the
+    // calendars are converted through.
+    pub fn to_julian_day(self) -> Result<i64, DateError> {
+        if self.calendar == Calendar::Chinese {
+            return Err(DateError::UnsupportedCalendar(self.calendar));
+        }
+        arith::to_julian_day(self.calendar, i64::from(self.year), self.month, self.day)
+            .ok_or(DateError::InvalidDate(self))
+    }
+
+    // The date in `calendar` of the day with Julian Day Number `jdn`.
+    pub fn from_julian_day(calendar: Calendar, jdn: i64) -> Result<CalendarDate, DateError> {
+        let (year, month, day) = arith::from_julian_day(calendar, jdn)
+            .ok_or(DateError::UnsupportedCalendar(calendar))?;
+        let year = i32::try_from(year).map_err(|_| DateError::OutOfRange)?;
+        Ok(CalendarDate { calendar, year, month, day })
+    }
+}
+
+// Date arithmetic for counting calendar months and years between two dates,
+// for RoughTime::relative_to_in(). Moving to a month or year that's too short
+// for the day lands on its last day instead, so a month after 31 January is
+// 28 or 29 February.
+#[cfg(feature = "std")]
+impl CalendarDate {
+    // The same day and month `years` years on. In the Hebrew calendar that
+    // means the month of the same name, which has a different number in
+    // leap and common years, with Adar becoming Adar II in a leap year.
+    pub(crate) fn add_years(self, years: i64) -> Result<CalendarDate, DateError> {
+        let year = i32::try_from(i64::from(self.year) + years).map_err(|_| DateError::OutOfRange)?;
+        let months = self.calendar.months_in_year(year)
+            .ok_or(DateError::UnsupportedCalendar(self.calendar))?;
+        let month = match self.calendar {
+            Calendar::Hebrew =>
+                arith::hebrew_month_in_year(i64::from(self.year), self.month, i64::from(year)),
+            _ => self.month.min(months)
+        };
+        CalendarDate { year, month, ..self }.clamp_day()
+    }
+
+    // The same day `months` months on. The Ethiopian Pagume, 5 or 6 days
+    // added at the end of the year to make up its length, isn't counted as
+    // a month here but as the tail of the one before it, so a few days
+    // either side of the new year aren't a month apart.
+    pub(crate) fn add_months(self, months: u32) -> Result<CalendarDate, DateError> {
+        if months == 0 {
+            return Ok(self);
+        }
+        let mut date = self;
+        if arith::is_epagomenal(date.calendar, date.month) {
+            date.month -= 1;
+            date.day += 30;
+        }
+        let mut stepped = 0;
+        while stepped < months {
+            let in_year = date.calendar.months_in_year(date.year)
+                .ok_or(DateError::UnsupportedCalendar(date.calendar))?;
+            if date.month < in_year {
+                date.month += 1;
+            } else {
+                date.year = date.year.checked_add(1).ok_or(DateError::OutOfRange)?;
+                date.month = 1;
+            }
+            if !arith::is_epagomenal(date.calendar, date.month) {
+                stepped += 1;
+            }
+        }
+        date.clamp_day()
+    }
+
+    // The number of whole years from this date to `later`.
+    pub(crate) fn whole_years_until(self, later: CalendarDate) -> Result<i64, DateError> {
+        let end = later.to_julian_day()?;
+        let mut years = i64::from(later.year) - i64::from(self.year);
+        while years > 0 && self.add_years(years)?.to_julian_day()? > end {
+            years -= 1;
+        }
+        Ok(years.max(0))
+    }
+
+    // The number of whole months from this date to `later`, which must be
+    // less than a year after it.
+    pub(crate) fn whole_months_until(self, later: CalendarDate) -> Result<u32, DateError> {
+        let end = later.to_julian_day()?;
+        let mut months = 0;
+        while self.add_months(months + 1)?.to_julian_day()? <= end {
+            months += 1;
+        }
+        Ok(months)
+    }
+
+    fn clamp_day(self) -> Result<CalendarDate, DateError> {
+        let days = self.calendar.days_in_month(self.year, self.month)
+            .ok_or(DateError::InvalidDate(self))?;
+        Ok(CalendarDate { day: self.day.min(days), ..self })
+    }
+}
@@ -0,0 +1,81 @@
+use super::Calendar;
+
+// Which calendar people in a region use day to day, where it isn't the
+// Gregorian. Regions are ISO 3166 codes, as in locale tags.
+const REGION_CALENDARS: &[(&str, Calendar)] = &[
+    ("ET", Calendar::Ethiopian),
+    ("SA", Calendar::Islamic),
+    ("TH", Calendar::Buddhist)
+];
+
+// The region to assume for a locale tag that gives only a language, for
+// languages whose speakers mostly live in one of the regions above.
+const LANGUAGE_REGIONS: &[(&str, &str)] = &[
+    ("am", "ET"),
+    ("th", "TH"),
+    ("ti", "ET")
+];
+
+impl Calendar {
+    // A sensible calendar to start with for someone whose locale is
+    // `locale`, before they've chosen one: "am-ET" gives Ethiopian, "th-TH"
+    // Buddhist, and "en-US" Gregorian. The locale is a BCP 47 tag, or a
+    // POSIX one like "am_ET.UTF-8". A calendar asked for explicitly in the
+    // tag, as in "th-TH-u-ca-gregory", wins. Anything unrecognized gives
+    // Gregorian.
+    //
+    // The mapping is the two tables above, so supporting another region is
+    // a matter of adding a row.
+    pub fn default_for_locale(locale: &str) -> Calendar {
+        let tag = locale.split(['.', '@']).next().unwrap_or("");
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next().unwrap_or("");
+
+        let mut region = None;
+        while let Some(subtag) = subtags.next() {
+            if subtag.len() == 1 {
+                // A singleton starts an extension; "u" is the one for
+                // Unicode locale keywords, like "ca" for the calendar.
+                if subtag.eq_ignore_ascii_case("u") {
+                    if let Some(calendar) = calendar_keyword(&mut subtags) {
+                        return calendar;
+                    }
+                }
+                break;
+            }
+            let is_region = (subtag.len() == 2 && subtag.bytes().all(|b| b.is_ascii_alphabetic()))
+                || (subtag.len() == 3 && subtag.bytes().all(|b| b.is_ascii_digit()));
+            if is_region && region.is_none() {
+                region = Some(subtag);
+            }
+        }
+
+        let region = region.or_else(|| {
+            LANGUAGE_REGIONS.iter()
+                .find(|&&(lang, _)| lang.eq_ignore_ascii_case(language))
+                .map(|&(_, region)| region)
+        });
+        region
+            .and_then(|region| {
+                REGION_CALENDARS.iter().find(|&&(code, _)| code.eq_ignore_ascii_case(region))
+            })
+            .map_or(Calendar::Gregorian, |&(_, calendar)| calendar)
+    }
+}
+
+// Reads the "ca" keyword from the subtags of a Unicode locale extension, if
+// it names a calendar we have. The keyword values are CLDR's, which mostly
+// match our names or aliases, except "gregory", and the several variants of
+// the Islamic calendar, which all come out as ours.
+fn calendar_keyword<'a>(subtags: &mut impl Iterator<Item = &'a str>) -> Option<Calendar> {
+    let mut subtags = subtags.skip_while(|subtag| !subtag.eq_ignore_ascii_case("ca"));
+    subtags.next()?;
+    let value = subtags.next()?;
+    if value.eq_ignore_ascii_case("gregory") {
+        return Some(Calendar::Gregorian);
+    }
+    if value.get(..7).is_some_and(|start| start.eq_ignore_ascii_case("islamic")) {
+        return Some(Calendar::Islamic);
+    }
+    value.parse().ok()
+}
@@ -0,0 +1,158 @@
+use core::fmt;
+
+mod arith;
+mod date;
+mod locale;
+mod parse;
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use self::date::{CalendarDate, DateError};
+pub use self::parse::ParseCalendarError;
+
+// The calendar system a user has chosen to see dates in, from the chapter's
+//
+//     let calendar =
+//         match settings.get_string("calendar") {
+//             "gregorian" => Calendar::Gregorian,
+//             "chinese" => Calendar::Chinese,
+//             "ethiopian" => Calendar::Ethiopian,
+//             other => return parse_error("calendar", other)
+//         };
+//
+// which, with the Settings type, is now just
+//
+//     let calendar: Calendar = settings.get_parsed("calendar")?;
+//
+// More calendars may be added, so code outside this crate that matches on
+// one needs a catch-all arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Calendar {
+    Gregorian,
+    Chinese,
+    Ethiopian,
+    Hebrew,
+    Islamic,
+    Julian,
+    Buddhist
+}
+
+impl Calendar {
+    // Every calendar, in the order a settings screen would list them.
+    pub const ALL: [Calendar; 7] = [
+        Calendar::Gregorian,
+        Calendar::Chinese,
+        Calendar::Ethiopian,
+        Calendar::Hebrew,
+        Calendar::Islamic,
+        Calendar::Julian,
+        Calendar::Buddhist
+    ];
+
+    // The name a setting uses for this calendar: "gregorian". Display
+    // writes the same name, and FromStr reads it back.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Calendar::Gregorian => "gregorian",
+            Calendar::Chinese => "chinese",
+            Calendar::Ethiopian => "ethiopian",
+            Calendar::Hebrew => "hebrew",
+            Calendar::Islamic => "islamic",
+            Calendar::Julian => "julian",
+            Calendar::Buddhist => "buddhist"
+        }
+    }
+
+    // Other names the parser accepts for this calendar, from other
+    // traditions or older settings files.
+    pub fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Calendar::Gregorian => &["western"],
+            Calendar::Chinese => &["lunar", "nongli"],
+            Calendar::Ethiopian => &["ge'ez", "geez", "ethiopic"],
+            Calendar::Hebrew => &["jewish"],
+            Calendar::Islamic => &["hijri", "muslim"],
+            Calendar::Julian => &[],
+            Calendar::Buddhist => &["thai"]
+        }
+    }
+
+    // The first day of year 1, as a Julian Day Number: the count of days
+    // since noon on 1 January 4713 BC in the Julian calendar, which every
+    // calendar can be converted to and from. Proleptic calendars are
+    // counted back past their adoption, so the Gregorian epoch is 1 January
+    // 1 AD as if the calendar had been in use then.
+    //
+    // Some of these are a matter of convention. The Chinese calendar is
+    // counted from the traditional start of the Yellow Emperor's reign in
+    // 2637 BC, the Islamic from the civil, Friday, epoch of the Hijra, and
+    // the Buddhist from 543 BC, the Thai reckoning, with years starting on
+    // 1 January as they do in Thailand today.
+    pub fn epoch(self) -> i64 {
+        match self {
+            Calendar::Gregorian => 1_721_426,
+            Calendar::Chinese => 758_326,
+            Calendar::Ethiopian => 1_724_221,
+            Calendar::Hebrew => 347_998,
+            Calendar::Islamic => 1_948_440,
+            Calendar::Julian => 1_721_424,
+            Calendar::Buddhist => 1_523_099
+        }
+    }
+
+    // Whether `year` is a leap year: one with 366 days in the Gregorian,
+    // Julian and Buddhist calendars, a sixth day of Pagume in the Ethiopian,
+    // a thirteenth month in the Hebrew, and a 355th day in the Islamic.
+    // Years are numbered as in CalendarDate. None for the Chinese calendar,
+    // whose years depend on the sun and moon.
+    pub fn is_leap_year(self, year: i32) -> Option<bool> {
+        arith::is_leap_year(self, i64::from(year))
+    }
+
+    // The number of months in `year`, counting Pagume as the Ethiopian
+    // thirteenth. None for the Chinese calendar.
+    pub fn months_in_year(self, year: i32) -> Option<u8> {
+        arith::months_in_year(self, i64::from(year))
+    }
+
+    // The number of days in `month` of `year`, with months numbered from 1
+    // as in CalendarDate. None if the year has no such month, or for the
+    // Chinese calendar.
+    pub fn days_in_month(self, year: i32, month: u8) -> Option<u8> {
+        arith::days_in_month(self, i64::from(year), month)
+    }
+
+    // The names of the months, in order, in a year that has all of them.
+    // Only some years have the last Ethiopian month, Pagume, and the
+    // Hebrew year, which starts at Tishrei, only has Adar I and Adar II in
+    // leap years; common years have a single Adar in their place. Chinese
+    // months are numbered rather than named, and a leap month, which can
+    // follow any of them, takes the name of the month before it.
+    pub fn month_names(self) -> &'static [&'static str] {
+        match self {
+            Calendar::Gregorian | Calendar::Julian | Calendar::Buddhist =>
+                &["January", "February", "March", "April", "May", "June", "July",
+                  "August", "September", "October", "November", "December"],
+            Calendar::Chinese =>
+                &["Zhengyue", "Eryue", "Sanyue", "Siyue", "Wuyue", "Liuyue", "Qiyue",
+                  "Bayue", "Jiuyue", "Shiyue", "Shiyiyue", "Layue"],
+            Calendar::Ethiopian =>
+                &["Meskerem", "Tikimt", "Hidar", "Tahsas", "Tir", "Yekatit", "Megabit",
+                  "Miyazya", "Ginbot", "Sene", "Hamle", "Nehase", "Pagume"],
+            Calendar::Hebrew =>
+                &["Tishrei", "Cheshvan", "Kislev", "Tevet", "Shevat", "Adar I", "Adar II",
+                  "Nisan", "Iyar", "Sivan", "Tammuz", "Av", "Elul"],
+            Calendar::Islamic =>
+                &["Muharram", "Safar", "Rabi' al-Awwal", "Rabi' al-Thani", "Jumada al-Awwal",
+                  "Jumada al-Thani", "Rajab", "Sha'ban", "Ramadan", "Shawwal",
+                  "Dhu al-Qi'dah", "Dhu al-Hijjah"]
+        }
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+use super::Calendar;
+
+// Reads a calendar by its name or one of its aliases, ignoring ASCII case and
+// surrounding whitespace, so "Gregorian" in a hand-edited settings file
+// still works, and so does "ge'ez".
+impl FromStr for Calendar {
+    type Err = ParseCalendarError;
+
+    fn from_str(s: &str) -> Result<Calendar, ParseCalendarError> {
+        let name = s.trim();
+        Calendar::ALL.iter()
+            .cloned()
+            .find(|calendar| {
+                name.eq_ignore_ascii_case(calendar.name())
+                    || calendar.aliases().iter().any(|alias| name.eq_ignore_ascii_case(alias))
+            })
+            .ok_or_else(|| ParseCalendarError { input: String::from(s), expected: EXPECTED })
+    }
+}
+
+// The canonical names only, in the order of Calendar::ALL; the aliases are
+// for old settings, not for suggesting to people.
+const EXPECTED: &[&str] = &{
+    let mut names = [""; Calendar::ALL.len()];
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Calendar::ALL[i].name();
+        i += 1;
+    }
+    names
+};
+
+// The error returned when a string doesn't name a calendar. It keeps the
+// input and the names that would have been accepted, so the message can
+// tell the user what to write instead of just that they got it wrong.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCalendarError {
+    input: String,
+    expected: &'static [&'static str]
+}
+
+impl ParseCalendarError {
+    // The string that failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    // The names that are accepted.
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+}
+
+impl fmt::Display for ParseCalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown calendar {:?}; expected one of ", self.input)?;
+        for (i, name) in self.expected.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ParseCalendarError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn every_calendar_is_expected_by_name() {
+        assert_eq!(EXPECTED.len(), Calendar::ALL.len());
+        for (&calendar, &name) in Calendar::ALL.iter().zip(EXPECTED) {
+            assert_eq!(name, calendar.name());
+            assert_eq!(name.parse(), Ok(calendar));
+        }
+    }
+
+    #[test]
+    fn the_error_lists_every_name() {
+        let error = "mayan".parse::<Calendar>().unwrap_err();
+        assert_eq!(error.input(), "mayan");
+        assert_eq!(error.to_string(),
+                   "unknown calendar \"mayan\"; expected one of gregorian, chinese, ethiopian, \
+                    hebrew, islamic, julian, buddhist");
+    }
+}
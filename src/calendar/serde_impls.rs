@@ -0,0 +1,40 @@
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::Calendar;
+
+// A calendar is serialized as its canonical name, "ethiopian", and read back
+// the way FromStr reads it, so an alias like "ge'ez" or a name written with
+// different capitalization still loads. That way a config file keeps
+// working when a name changes, and is rewritten with the new one next time
+// it's saved.
+
+impl Serialize for Calendar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+struct CalendarVisitor;
+
+impl<'de> Visitor<'de> for CalendarVisitor {
+    type Value = Calendar;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the name of a calendar")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Calendar, E> {
+        value.parse().map_err(|err: super::ParseCalendarError| {
+            E::unknown_variant(value, err.expected())
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Calendar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Calendar, D::Error> {
+        deserializer.deserialize_str(CalendarVisitor)
+    }
+}
@@ -0,0 +1,430 @@
+use core::fmt;
+
+use super::hit::{cross, polygon_contains, segment_distance, ON_LINE_TOLERANCE};
+use super::intersect::edges;
+use super::measure::signed_area;
+use super::{Point, Shape, ShapeKind};
+
+// Union, intersection and difference of areas, for rects and polygons that
+// don't cross themselves. The result is a list of polygons, since combining
+// two shapes can leave several separate pieces, or none. Each goes
+// clockwise on screen, like Rect::corners(), without points in the middle
+// of straight sides.
+//
+// A polygon can't have a hole in it, so a hole, such as a rectangle cut out
+// of the middle of another leaves, is joined to the outline around it by a
+// cut of no width: the polygon goes along the cut, around the hole the
+// other way and back. It encloses the right area, and contains_point() and
+// area() treat it correctly, but drawing its outline draws the cut too.
+//
+// Outlines that cross are found exactly. Points within ON_LINE_TOLERANCE
+// of each other are taken to be the same point, and of an edge to be on it,
+// so rectangles that share a side or a corner combine cleanly.
+impl Shape {
+    pub fn union(&self, other: &Shape) -> Result<Vec<Shape>, BooleanError> {
+        combine(self, other, Operation::Union)
+    }
+
+    pub fn intersection(&self, other: &Shape) -> Result<Vec<Shape>, BooleanError> {
+        combine(self, other, Operation::Intersection)
+    }
+
+    // The area of this shape that isn't in `other`.
+    pub fn difference(&self, other: &Shape) -> Result<Vec<Shape>, BooleanError> {
+        combine(self, other, Operation::Difference)
+    }
+}
+
+// The error returned when a boolean operation is given a shape other than a
+// rect or a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BooleanError {
+    kind: ShapeKind
+}
+
+impl BooleanError {
+    // The kind of shape that was given.
+    pub fn kind(&self) -> ShapeKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for BooleanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a {} can't be combined with other shapes; only rects and polygons can", self.kind)
+    }
+}
+
+impl core::error::Error for BooleanError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference
+}
+
+// Where a piece of one outline lies relative to the other shape: inside
+// it, outside it, or on its outline, going the same way or the opposite
+// way.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Place {
+    Inside,
+    Outside,
+    Along,
+    Against
+}
+
+type Segment = (Point, Point);
+
+// Both outlines are cut wherever they meet, so each piece is wholly inside
+// the other shape, outside it or on its outline. The result's outline is
+// made of the pieces that bound it, which are joined up into loops. With
+// both outlines going clockwise, the loops around areas do too, and the
+// loops around holes go the other way.
+fn combine(a: &Shape, b: &Shape, operation: Operation) -> Result<Vec<Shape>, BooleanError> {
+    let (a, b) = (outline(a)?, outline(b)?);
+    let (pieces_a, pieces_b) = cut(&a, &b);
+
+    let mut kept = Vec::new();
+    for (p, q) in pieces_a {
+        let place = place((p, q), &b);
+        let keep = match operation {
+            Operation::Union => matches!(place, Place::Outside | Place::Along),
+            Operation::Intersection => matches!(place, Place::Inside | Place::Along),
+            Operation::Difference => matches!(place, Place::Outside | Place::Against)
+        };
+        if keep {
+            kept.push((p, q));
+        }
+    }
+    // Pieces on both outlines were dealt with above, so only the pieces of
+    // b off a's outline count here. b's outline bounds a difference from
+    // the other side, so its pieces go backwards.
+    for (p, q) in pieces_b {
+        match (operation, place((p, q), &a)) {
+            (Operation::Union, Place::Outside) | (Operation::Intersection, Place::Inside) =>
+                kept.push((p, q)),
+            (Operation::Difference, Place::Inside) =>
+                kept.push((q, p)),
+            _ =>
+                ()
+        }
+    }
+
+    let (outlines, holes): (Vec<_>, Vec<_>) = join(kept)
+        .into_iter()
+        .map(|points| simplify(&points))
+        .filter(|points| points.len() >= 3)
+        .partition(|points| signed_area(points) > 0.0);
+    Ok(bridge(outlines, holes).into_iter().map(Shape::Polygon).collect())
+}
+
+// The shape's outline, clockwise, without repeated points, or no points if
+// it encloses no area.
+fn outline(shape: &Shape) -> Result<Vec<Point>, BooleanError> {
+    let mut points = match *shape {
+        Shape::Rect(ref rect) => rect.corners().to_vec(),
+        Shape::Polygon(ref points) => points.clone(),
+        _ => return Err(BooleanError { kind: shape.kind() })
+    };
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let area = signed_area(&points);
+    if points.len() < 3 || area.abs() <= ON_LINE_TOLERANCE {
+        return Ok(Vec::new());
+    }
+    if area < 0.0 {
+        points.reverse();
+    }
+    Ok(points)
+}
+
+// Cuts each outline's edges at every point where the other outline meets
+// them: where edges cross, and where a corner of one lies on an edge of the
+// other. A crossing point is worked out once for both edges, and points
+// that round to nearly the same place are then made the same, so the
+// pieces' ends match exactly for join().
+fn cut(a: &[Point], b: &[Point]) -> (Vec<Segment>, Vec<Segment>) {
+    let mut cuts_a = vec![Vec::new(); a.len()];
+    let mut cuts_b = vec![Vec::new(); b.len()];
+    for (i, (p, q)) in edges(a).enumerate() {
+        for (j, (r, s)) in edges(b).enumerate() {
+            if let Some(x) = crossing(p, q, r, s) {
+                cuts_a[i].push(x);
+                cuts_b[j].push(x);
+            }
+        }
+    }
+    for (points, others, cuts) in [(a, b, &mut cuts_a), (b, a, &mut cuts_b)] {
+        for ((p, q), cuts) in edges(points).zip(cuts.iter_mut()) {
+            cuts.extend(others.iter().filter(|&&v| segment_distance(p, q, v) <= ON_LINE_TOLERANCE));
+        }
+    }
+
+    let mut seen = Vec::new();
+    let mut cut_edges = |points: &[Point], cuts: Vec<Vec<Point>>| {
+        let mut pieces = Vec::new();
+        for ((p, q), mut cuts) in edges(points).zip(cuts) {
+            let along = q - p;
+            cuts.sort_by(|&c, &d| (c - p).dot(along).total_cmp(&(d - p).dot(along)));
+            let mut start = same_as_seen(&mut seen, p);
+            for c in cuts.into_iter().chain(Some(q)) {
+                let end = same_as_seen(&mut seen, c);
+                if end != start {
+                    pieces.push((start, end));
+                    start = end;
+                }
+            }
+        }
+        pieces
+    };
+    (cut_edges(a, cuts_a), cut_edges(b, cuts_b))
+}
+
+// Where the edges cross, if they do somewhere other than at an end of
+// either. Ends that lie on the other edge are cut at by cut() itself.
+fn crossing(p: Point, q: Point, r: Point, s: Point) -> Option<Point> {
+    let touching = [(r, s, p), (r, s, q), (p, q, r), (p, q, s)]
+        .iter()
+        .any(|&(a, b, v)| segment_distance(a, b, v) <= ON_LINE_TOLERANCE);
+    if touching {
+        return None;
+    }
+    let (d1, d2) = (cross(r, s, p), cross(r, s, q));
+    let (d3, d4) = (cross(p, q, r), cross(p, q, s));
+    if (d1 > 0.0) != (d2 > 0.0) && (d3 > 0.0) != (d4 > 0.0) {
+        Some(p + (q - p) * (d1 / (d1 - d2)))
+    } else {
+        None
+    }
+}
+
+// The first point seen within ON_LINE_TOLERANCE of `p`, or `p` itself if
+// it's the first near there.
+fn same_as_seen(seen: &mut Vec<Point>, p: Point) -> Point {
+    match seen.iter().find(|seen| seen.distance_to(p) <= ON_LINE_TOLERANCE) {
+        Some(&seen) => seen,
+        None => {
+            seen.push(p);
+            p
+        }
+    }
+}
+
+fn place((p, q): Segment, outline: &[Point]) -> Place {
+    let middle = (p + q) * 0.5;
+    for (r, s) in edges(outline) {
+        if segment_distance(r, s, middle) <= ON_LINE_TOLERANCE {
+            return if (q - p).dot(s - r) > 0.0 { Place::Along } else { Place::Against };
+        }
+    }
+    if polygon_contains(outline, middle) { Place::Inside } else { Place::Outside }
+}
+
+// Joins pieces end to start into closed loops. Where more than one piece
+// starts at the end of the last, as where two areas touch at a corner, the
+// one turning furthest towards the inside is taken, which keeps the areas
+// apart.
+fn join(mut pieces: Vec<Segment>) -> Vec<Vec<Point>> {
+    let mut loops = Vec::new();
+    while let Some((start, mut end)) = pieces.pop() {
+        let mut points = vec![start];
+        let mut previous = start;
+        while end != start {
+            let incoming = end - previous;
+            let turn = |&(_, next): &Segment| {
+                let outgoing = next - end;
+                (incoming.x * outgoing.y - incoming.y * outgoing.x).atan2(incoming.dot(outgoing))
+            };
+            let next = pieces.iter()
+                .enumerate()
+                .filter(|(_, piece)| piece.0 == end)
+                .max_by(|(_, a), (_, b)| turn(a).total_cmp(&turn(b)))
+                .map(|(index, _)| index);
+            let index = match next {
+                Some(index) => index,
+                // An outline that doesn't close can only come of rounding;
+                // what there is of it is dropped.
+                None => break
+            };
+            points.push(end);
+            previous = end;
+            end = pieces.swap_remove(index).1;
+        }
+        if end == start {
+            loops.push(points);
+        }
+    }
+    loops
+}
+
+// Drops the points in the middle of straight sides, which cutting the
+// outlines left behind.
+fn simplify(points: &[Point]) -> Vec<Point> {
+    let mut points = points.to_vec();
+    let mut i = 0;
+    while i < points.len() && points.len() >= 3 {
+        let n = points.len();
+        let (a, b, c) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let straight = cross(a, c, b).abs() <= ON_LINE_TOLERANCE * a.distance_to(c)
+            && (b - a).dot(c - b) > 0.0;
+        if straight {
+            points.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    points
+}
+
+// Joins each hole to the smallest outline around it, with a cut from one
+// of its corners to a corner of the outline that crosses no edge of either
+// or of any other hole. The shortest such cut is used.
+fn bridge(mut outlines: Vec<Vec<Point>>, holes: Vec<Vec<Point>>) -> Vec<Vec<Point>> {
+    let mut holes_in: Vec<Vec<Vec<Point>>> = vec![Vec::new(); outlines.len()];
+    for hole in holes {
+        let around = outlines.iter()
+            .enumerate()
+            .filter(|(_, outline)| polygon_contains(outline, hole[0]))
+            .min_by(|(_, a), (_, b)| signed_area(a).total_cmp(&signed_area(b)))
+            .map(|(index, _)| index);
+        if let Some(index) = around {
+            holes_in[index].push(hole);
+        }
+    }
+
+    for (outline, mut holes) in outlines.iter_mut().zip(holes_in) {
+        while let Some(hole) = holes.pop() {
+            let blocks = |o: Point, h: Point| {
+                edges(outline).chain(edges(&hole)).chain(holes.iter().flat_map(|hole| edges(hole)))
+                    .any(|(p, q)| crossing(o, h, p, q).is_some())
+            };
+            let mut best: Option<(f64, usize, usize)> = None;
+            for (i, &o) in outline.iter().enumerate() {
+                for (j, &h) in hole.iter().enumerate() {
+                    let length = o.distance_to(h);
+                    if best.is_none_or(|(shortest, _, _)| length < shortest) && !blocks(o, h) {
+                        best = Some((length, i, j));
+                    }
+                }
+            }
+            // Failing to find a cut can only come of rounding; the hole is
+            // left out.
+            if let Some((_, i, j)) = best {
+                let around_hole = hole[j..].iter().chain(&hole[..=j]).cloned();
+                let back = Some(outline[i]);
+                let joined: Vec<Point> = around_hole.chain(back).collect();
+                outline.splice(i + 1..i + 1, joined);
+            }
+        }
+    }
+    outlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Rect;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Shape {
+        Shape::Rect(Rect::new(Point::new(x0, y0), Point::new(x1, y1)))
+    }
+
+    fn area(shapes: &[Shape]) -> f64 {
+        shapes.iter().map(Shape::area).sum()
+    }
+
+    fn corners(shape: &Shape) -> usize {
+        match *shape {
+            Shape::Polygon(ref points) => points.len(),
+            _ => panic!("expected a polygon, got a {}", shape.kind())
+        }
+    }
+
+    fn contains(shapes: &[Shape], x: f64, y: f64) -> bool {
+        shapes.iter().any(|shape| shape.contains_point(Point::new(x, y)))
+    }
+
+    // A rect cut out of the middle of another leaves one polygon, joined to
+    // the hole by a cut, that doesn't contain the hole.
+    #[test]
+    fn difference_with_a_hole() {
+        let pieces = rect(0.0, 0.0, 10.0, 10.0).difference(&rect(4.0, 4.0, 6.0, 6.0)).unwrap();
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(area(&pieces), 96.0);
+        assert!(!contains(&pieces, 5.0, 5.0));
+        assert!(contains(&pieces, 1.0, 1.0));
+        assert!(contains(&pieces, 5.0, 8.0));
+    }
+
+    #[test]
+    fn identical_rects() {
+        let a = rect(0.0, 0.0, 4.0, 3.0);
+        for pieces in [a.union(&a).unwrap(), a.intersection(&a).unwrap()] {
+            assert_eq!(pieces.len(), 1);
+            assert_eq!(corners(&pieces[0]), 4);
+            assert_eq!(area(&pieces), 12.0);
+        }
+        assert!(a.difference(&a).unwrap().is_empty());
+    }
+
+    // Rects sharing a side make one rect between them, with no corners left
+    // in the middle of its long sides, and have nothing in common.
+    #[test]
+    fn adjacent_rects() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 1.0), rect(2.0, 0.0, 5.0, 1.0));
+        let union = a.union(&b).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(corners(&union[0]), 4);
+        assert_eq!(area(&union), 5.0);
+        assert!(a.intersection(&b).unwrap().is_empty());
+        let difference = a.difference(&b).unwrap();
+        assert_eq!(difference.len(), 1);
+        assert_eq!(area(&difference), 2.0);
+    }
+
+    // Rects touching only at a corner stay two separate pieces.
+    #[test]
+    fn rects_touching_at_a_corner() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 2.0), rect(2.0, 2.0, 3.0, 3.0));
+        let union = a.union(&b).unwrap();
+        assert_eq!(union.len(), 2);
+        assert!(union.iter().all(|piece| corners(piece) == 4));
+        assert_eq!(area(&union), 5.0);
+        assert!(a.intersection(&b).unwrap().is_empty());
+        assert_eq!(area(&a.difference(&b).unwrap()), 4.0);
+    }
+
+    // A strip right across a rect splits what's left of it in two.
+    #[test]
+    fn difference_splits_in_two() {
+        let pieces = rect(0.0, 0.0, 10.0, 2.0).difference(&rect(4.0, -1.0, 6.0, 3.0)).unwrap();
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|piece| corners(piece) == 4 && piece.area() == 8.0));
+        assert!(contains(&pieces, 2.0, 1.0));
+        assert!(contains(&pieces, 8.0, 1.0));
+        assert!(!contains(&pieces, 5.0, 1.0));
+    }
+
+    #[test]
+    fn overlapping_rects() {
+        let (a, b) = (rect(0.0, 0.0, 4.0, 4.0), rect(2.0, 2.0, 6.0, 6.0));
+        let union = a.union(&b).unwrap();
+        assert_eq!(union.len(), 1);
+        assert_eq!(corners(&union[0]), 8);
+        assert_eq!(area(&union), 28.0);
+        assert_eq!(area(&a.intersection(&b).unwrap()), 4.0);
+        assert_eq!(area(&a.difference(&b).unwrap()), 12.0);
+    }
+
+    #[test]
+    fn only_rects_and_polygons_combine() {
+        let line = Shape::Line(Point::new(0.0, 0.0), Point::new(1.0, 1.0));
+        let error = rect(0.0, 0.0, 1.0, 1.0).union(&line).unwrap_err();
+        assert_eq!(error.kind(), ShapeKind::Line);
+    }
+}
@@ -0,0 +1,149 @@
+use super::{Point, Rect, Shape};
+
+// Where a run of text lies on the canvas, which only the text's layout
+// knows. Any closure from a span's start and end offsets to its box will do:
+//
+//     let bounds = shape.bounding_box_with(&|start, end| layout.span_rect(start, end));
+pub trait TextMetrics {
+    fn span_box(&self, start: usize, end: usize) -> Rect;
+}
+
+impl<F> TextMetrics for F
+    where F: Fn(usize, usize) -> Rect
+{
+    fn span_box(&self, start: usize, end: usize) -> Rect {
+        self(start, end)
+    }
+}
+
+// The metrics bounding_box() uses, which don't know where any text is.
+struct NoText;
+
+impl TextMetrics for NoText {
+    fn span_box(&self, _start: usize, _end: usize) -> Rect {
+        Rect::default()
+    }
+}
+
+impl Shape {
+    // The smallest axis-aligned rectangle containing the shape. Without
+    // metrics to place it, a text span's box is the empty one at the origin;
+    // use bounding_box_with() for the real thing. So is an empty polygon's,
+    // and an empty group's. A group's box is around the members that have a
+    // position, so a text span or an empty shape in one doesn't stretch it
+    // to the origin.
+    pub fn bounding_box(&self) -> Rect {
+        self.position().unwrap_or_default()
+    }
+
+    // bounding_box(), asking `metrics` where text spans are.
+    pub fn bounding_box_with(&self, metrics: &impl TextMetrics) -> Rect {
+        self.placed_box(Some(metrics)).unwrap_or_default()
+    }
+
+    // bounding_box(), or None if the shape has no position.
+    pub(super) fn position(&self) -> Option<Rect> {
+        self.placed_box(None::<&NoText>)
+    }
+
+    // The shape's box, or None if it has no position: a text span when
+    // there are no metrics to place it, an empty polygon, or a group of
+    // nothing but those.
+    fn placed_box<M: TextMetrics>(&self, metrics: Option<&M>) -> Option<Rect> {
+        Some(match *self {
+            Shape::Rect(rect) =>
+                rect,
+            Shape::Ellipse { center, radius_x, radius_y, rotation } => {
+                // How far a turned ellipse reaches along each axis.
+                let (sin, cos) = rotation.sin_cos();
+                let half_width = (radius_x * cos).hypot(radius_y * sin);
+                let half_height = (radius_x * sin).hypot(radius_y * cos);
+                Rect::new(Point::new(center.x - half_width, center.y - half_height),
+                          Point::new(center.x + half_width, center.y + half_height))
+            }
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                enclosing(&[top_left, top_right, bottom_right, bottom_left]),
+            Shape::TextSpan(start, end) =>
+                metrics?.span_box(start, end),
+            Shape::Polygon(ref points) if points.is_empty() =>
+                return None,
+            Shape::Polygon(ref points) =>
+                enclosing(points),
+            Shape::Line(a, b) =>
+                Rect::new(a, b),
+            Shape::Group(ref shapes) => {
+                let corners: Vec<Point> = shapes.iter()
+                    .filter_map(|shape| shape.placed_box(metrics))
+                    .flat_map(|rect| rect.corners().to_vec())
+                    .collect();
+                if corners.is_empty() {
+                    return None;
+                }
+                enclosing(&corners)
+            }
+        })
+    }
+}
+
+// The smallest rectangle containing all of `points`, or the empty one at the
+// origin if there are none.
+pub(super) fn enclosing(points: &[Point]) -> Rect {
+    let (first, rest) = match points.split_first() {
+        Some(split) => split,
+        None => return Rect::default()
+    };
+    rest.iter().fold(Rect::new(*first, *first), |rect, p| {
+        Rect::new(Point::new(rect.top_left.x.min(p.x), rect.top_left.y.min(p.y)),
+                  Point::new(rect.bottom_right.x.max(p.x), rect.bottom_right.y.max(p.y)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Rect {
+        Rect::new(Point::new(x0, y0), Point::new(x1, y1))
+    }
+
+    // Text measured as one unit high and a unit wide per character, on the
+    // line at y = 20.
+    fn metrics(start: usize, end: usize) -> Rect {
+        rect(start as f64, 20.0, end as f64, 21.0)
+    }
+
+    #[test]
+    fn a_group_is_boxed_around_members_with_a_position() {
+        let group = Shape::Group(vec![Shape::Rect(rect(5.0, 5.0, 8.0, 6.0)),
+                                      Shape::TextSpan(2, 4),
+                                      Shape::Polygon(Vec::new()),
+                                      Shape::Group(Vec::new()),
+                                      Shape::Line(Point::new(9.0, 7.0), Point::new(10.0, 5.5))]);
+        assert_eq!(group.bounding_box(), rect(5.0, 5.0, 10.0, 7.0));
+        assert_eq!(group.bounding_box_with(&metrics), rect(2.0, 5.0, 10.0, 21.0));
+    }
+
+    #[test]
+    fn shapes_without_a_position() {
+        let text = Shape::TextSpan(2, 4);
+        assert_eq!(text.bounding_box(), Rect::default());
+        assert_eq!(text.bounding_box_with(&metrics), rect(2.0, 20.0, 4.0, 21.0));
+        let nothing = Shape::Group(vec![Shape::TextSpan(0, 1), Shape::Polygon(Vec::new())]);
+        assert_eq!(nothing.bounding_box(), Rect::default());
+        assert_eq!(nothing.bounding_box_with(&metrics), rect(0.0, 20.0, 1.0, 21.0));
+        assert_eq!(Shape::Group(Vec::new()).bounding_box(), Rect::default());
+    }
+
+    #[test]
+    fn a_turned_ellipse_reaches_further_along_the_axes() {
+        let ellipse = Shape::Ellipse {
+            center: Point::new(1.0, 1.0),
+            radius_x: 2.0,
+            radius_y: 1.0,
+            rotation: std::f64::consts::FRAC_PI_2
+        };
+        let bounds = ellipse.bounding_box();
+        assert!(bounds.top_left.approx_eq(&Point::new(0.0, -1.0), 1e-9));
+        assert!(bounds.bottom_right.approx_eq(&Point::new(2.0, 3.0), 1e-9));
+    }
+}
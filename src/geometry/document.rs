@@ -0,0 +1,656 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use super::hit::ON_LINE_TOLERANCE;
+use super::{Matrix2x3, Point, QuadTree, Rect, Selection, Shape, Snap};
+
+// A canvas: the shapes on it, in z-order from the bottom up so later ones
+// are drawn on top, and what the user has selected.
+//
+// The selection is kept two ways. selection() is the selected region, the
+// text or area that gets highlighted, which is what the chapter's
+//
+//     match document.selection() { ... }
+//
+// paints. selected() is the shapes that region picks out, for operations
+// on them. Setting one sets the other: set_selection() and select_in_rect()
+// select every shape the region touches, and select_at() selects the shape
+// clicked on, with its own area as the region.
+//
+// Finding shapes by position goes through a QuadTree of their bounding
+// boxes, which every change to the shapes keeps up to date, so clicking on
+// a large document doesn't test every shape in it.
+//
+// Dragging a shape, with begin_drag(), drag_to(), drag_rotation() and
+// end_drag(), follows the document's Snap settings, which start off.
+#[derive(Clone, Debug, Default)]
+pub struct Document {
+    shapes: Vec<(ShapeId, Shape)>,
+    next_id: u64,
+    selection: Option<Selection>,
+    selected: Vec<ShapeId>,
+    snap: Snap,
+    drag: Option<Drag>,
+    // Where each shape is in `shapes`.
+    z_indices: HashMap<ShapeId, usize>,
+    index: QuadTree<ShapeId>
+}
+
+// A drag in progress. The shape is put back together from how it was when
+// the drag began each time the drag moves on, so the snapping applies to
+// how far it has gone in all rather than to each step, and a drag made of
+// many small steps moves as far as one big one.
+#[derive(Clone, Debug, PartialEq)]
+struct Drag {
+    id: ShapeId,
+    original: Shape,
+    offset: Point,
+    angle: f64
+}
+
+impl Drag {
+    // The dragged shape: the original turned by the snapped angle about the
+    // center of its bounding box, then moved by the offset and, if it has
+    // moved, nudged so the top-left corner of its bounding box is on the
+    // grid.
+    fn shape(&self, snap: &Snap) -> Shape {
+        let mut shape = self.original.clone();
+        let angle = snap.rotation(self.angle);
+        if angle != 0.0 {
+            let Rect { top_left, bottom_right } = shape.bounding_box();
+            let center = (top_left + bottom_right) * 0.5;
+            shape.transform(&Matrix2x3::rotation(angle).around(center));
+        }
+        if self.offset != Point::ORIGIN {
+            let corner = shape.bounding_box().top_left + self.offset;
+            let moved = snap.point(corner) - shape.bounding_box().top_left;
+            shape.translate(moved.x, moved.y);
+        }
+        shape
+    }
+}
+
+// A shape's identity within its document. Unlike its position in the
+// z-order, it stays the same when the shape is raised or lowered or others
+// are removed, and it's never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShapeId(u64);
+
+impl Document {
+    pub fn new() -> Document {
+        Document::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    // Adds a shape on top of the others.
+    pub fn add(&mut self, shape: Shape) -> ShapeId {
+        let id = self.new_id();
+        self.index.insert(shape.bounding_box(), id);
+        self.z_indices.insert(id, self.shapes.len());
+        self.shapes.push((id, shape));
+        id
+    }
+
+    // Takes a shape out of the document, and out of the selected shapes if
+    // it was one. The selected region stays as it is.
+    pub fn remove(&mut self, id: ShapeId) -> Option<Shape> {
+        let index = self.z_index(id)?;
+        self.selected.retain(|&selected| selected != id);
+        let (_, shape) = self.shapes.remove(index);
+        self.index.remove(&shape.bounding_box(), &id);
+        self.z_indices.remove(&id);
+        self.renumber(index);
+        Some(shape)
+    }
+
+    pub fn get(&self, id: ShapeId) -> Option<&Shape> {
+        self.z_index(id).map(|index| &self.shapes[index].1)
+    }
+
+    // The shape, to change as it likes. Its place in the index is updated
+    // when the ShapeMut is dropped.
+    pub fn get_mut(&mut self, id: ShapeId) -> Option<ShapeMut<'_>> {
+        let index = self.z_index(id)?;
+        let bounds = self.shapes[index].1.bounding_box();
+        Some(ShapeMut { document: self, index, bounds })
+    }
+
+    // The shapes from the bottom up, in the order they're drawn.
+    pub fn shapes(&self) -> impl DoubleEndedIterator<Item = (ShapeId, &Shape)> + '_ {
+        self.shapes.iter().map(|&(id, ref shape)| (id, shape))
+    }
+
+    // Where a shape is in the z-order, counting up from 0 at the bottom.
+    pub fn z_index(&self, id: ShapeId) -> Option<usize> {
+        self.z_indices.get(&id).cloned()
+    }
+
+    // The z-order operations move a shape within the stack, and return
+    // false if there's no such shape.
+
+    pub fn bring_to_front(&mut self, id: ShapeId) -> bool {
+        let top = self.shapes.len().saturating_sub(1);
+        self.move_to(id, |_| top)
+    }
+
+    pub fn send_to_back(&mut self, id: ShapeId) -> bool {
+        self.move_to(id, |_| 0)
+    }
+
+    // Moves a shape one step up, over the shape just above it.
+    pub fn raise(&mut self, id: ShapeId) -> bool {
+        let top = self.shapes.len().saturating_sub(1);
+        self.move_to(id, |index| (index + 1).min(top))
+    }
+
+    // Moves a shape one step down, under the shape just below it.
+    pub fn lower(&mut self, id: ShapeId) -> bool {
+        self.move_to(id, |index| index.saturating_sub(1))
+    }
+
+    fn move_to(&mut self, id: ShapeId, to: impl FnOnce(usize) -> usize) -> bool {
+        let from = match self.z_index(id) {
+            Some(index) => index,
+            None => return false
+        };
+        let to = to(from);
+        let entry = self.shapes.remove(from);
+        self.shapes.insert(to, entry);
+        self.renumber(from.min(to));
+        true
+    }
+
+    // Replaces the shapes with a group of them, keeping their order, where
+    // the topmost of them was. Any of them that were selected are replaced in
+    // the selection by the group. Returns None, changing nothing, if none of
+    // the ids are in the document.
+    pub fn group(&mut self, ids: &[ShapeId]) -> Option<ShapeId> {
+        let mut indices: Vec<usize> = ids.iter().filter_map(|&id| self.z_index(id)).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        let (&bottom, &top) = (indices.first()?, indices.last()?);
+
+        let mut members = Vec::new();
+        let mut was_selected = false;
+        // Taking the shapes out from the top down leaves the indices of
+        // those still to go where they were.
+        for &index in indices.iter().rev() {
+            let (id, shape) = self.shapes.remove(index);
+            self.index.remove(&shape.bounding_box(), &id);
+            self.z_indices.remove(&id);
+            if let Some(position) = self.selected.iter().position(|&selected| selected == id) {
+                self.selected.remove(position);
+                was_selected = true;
+            }
+            members.push(shape);
+        }
+        members.reverse();
+
+        let id = self.new_id();
+        let group = Shape::Group(members);
+        self.index.insert(group.bounding_box(), id);
+        // The group goes where the topmost member was, less the members
+        // below it that have been taken out.
+        self.shapes.insert(top + 1 - indices.len(), (id, group));
+        self.renumber(bottom);
+        if was_selected {
+            self.sort_selected(id);
+        }
+        Some(id)
+    }
+
+    // Groups the selected shapes, leaving the group selected.
+    pub fn group_selection(&mut self) -> Option<ShapeId> {
+        let selected = self.selected.clone();
+        self.group(&selected)
+    }
+
+    // Replaces a group with the shapes in it, in the same place in the
+    // z-order, and returns their new ids. If the group was selected, they
+    // are instead. Returns None if there's no such shape or it isn't a
+    // group.
+    pub fn ungroup(&mut self, id: ShapeId) -> Option<Vec<ShapeId>> {
+        let index = self.z_index(id)?;
+        let bounds = self.shapes[index].1.bounding_box();
+        let members = match self.shapes[index].1 {
+            Shape::Group(ref mut members) => mem::take(members),
+            _ => return None
+        };
+        self.index.remove(&bounds, &id);
+        self.z_indices.remove(&id);
+        let ids: Vec<_> = members.iter().map(|_| self.new_id()).collect();
+        for (&id, shape) in ids.iter().zip(&members) {
+            self.index.insert(shape.bounding_box(), id);
+        }
+        self.shapes.splice(index..=index, ids.iter().cloned().zip(members));
+        self.renumber(index);
+        if let Some(position) = self.selected.iter().position(|&selected| selected == id) {
+            self.selected.remove(position);
+            for &member in &ids {
+                self.sort_selected(member);
+            }
+        }
+        Some(ids)
+    }
+
+    fn new_id(&mut self) -> ShapeId {
+        let id = ShapeId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    // Brings z_indices up to date after the shapes from `from` upwards have
+    // moved.
+    fn renumber(&mut self, from: usize) {
+        for (index, &(id, _)) in self.shapes.iter().enumerate().skip(from) {
+            self.z_indices.insert(id, index);
+        }
+    }
+
+    // Adds a shape to the selected shapes, where it goes in z-order.
+    fn sort_selected(&mut self, id: ShapeId) {
+        let z_index = self.z_index(id);
+        let position = self.selected.partition_point(|&selected| self.z_index(selected) < z_index);
+        self.selected.insert(position, id);
+    }
+
+    pub fn snap(&self) -> Snap {
+        self.snap
+    }
+
+    pub fn set_snap(&mut self, snap: Snap) {
+        self.snap = snap;
+    }
+
+    // Starts dragging a shape, ending any drag already under way. Returns
+    // false if there's no such shape.
+    pub fn begin_drag(&mut self, id: ShapeId) -> bool {
+        self.drag = self.get(id).map(|shape| {
+            Drag { id, original: shape.clone(), offset: Point::ORIGIN, angle: 0.0 }
+        });
+        self.drag.is_some()
+    }
+
+    // The shape being dragged, if any.
+    pub fn dragging(&self) -> Option<ShapeId> {
+        self.drag.as_ref().map(|drag| drag.id)
+    }
+
+    // Moves the dragged shape to (dx, dy) from where it was when the drag
+    // began. With a grid to snap to, the shape keeps its size and lands with
+    // the top-left corner of its bounding box on the grid point nearest where
+    // it would have been. Returns false if nothing is being dragged, or the
+    // shape has gone.
+    pub fn drag_to(&mut self, dx: f64, dy: f64) -> bool {
+        match self.drag {
+            Some(ref mut drag) => drag.offset = Point::new(dx, dy),
+            None => return false
+        }
+        self.update_drag()
+    }
+
+    // Turns the dragged shape clockwise by `angle` radians in all since the
+    // drag began, about the center of its bounding box, snapped to a multiple
+    // of the snap angle if there is one. Returns false if nothing is being
+    // dragged, or the shape has gone.
+    pub fn drag_rotation(&mut self, angle: f64) -> bool {
+        match self.drag {
+            Some(ref mut drag) => drag.angle = angle,
+            None => return false
+        }
+        self.update_drag()
+    }
+
+    // Finishes the drag, leaving the shape where it is, and returns which
+    // shape it was.
+    pub fn end_drag(&mut self) -> Option<ShapeId> {
+        self.drag.take().map(|drag| drag.id)
+    }
+
+    fn update_drag(&mut self) -> bool {
+        let (id, shape) = match self.drag {
+            Some(ref drag) => (drag.id, drag.shape(&self.snap)),
+            None => return false
+        };
+        match self.get_mut(id) {
+            Some(mut dragged) => {
+                *dragged = shape;
+                true
+            }
+            None => false
+        }
+    }
+
+    // The topmost shape containing `p`, which is the one a click there hits.
+    pub fn shape_at(&self, p: Point) -> Option<ShapeId> {
+        self.near(&Rect::new(p, p))
+            .into_iter()
+            .filter(|&id| self.shapes[self.z_indices[&id]].1.contains_point(p))
+            .max_by_key(|id| self.z_indices[id])
+    }
+
+    pub fn selection(&self) -> Option<&Selection> {
+        self.selection.as_ref()
+    }
+
+    // Selects a region, along with every shape it touches. Selecting text,
+    // or nothing, selects no shapes.
+    pub fn set_selection(&mut self, selection: Option<Selection>) {
+        self.selected = match selection {
+            Some(ref selection) => self.touching(selection),
+            None => Vec::new()
+        };
+        self.selection = selection;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.set_selection(None);
+    }
+
+    // The selected shapes, in z-order.
+    pub fn selected(&self) -> &[ShapeId] {
+        &self.selected
+    }
+
+    // Selects the topmost shape under `p`, as a click would, with its area
+    // as the selected region, or its bounding box for a line. Clicking where
+    // there's no shape clears the selection.
+    pub fn select_at(&mut self, p: Point) -> Option<ShapeId> {
+        let id = self.shape_at(p);
+        self.selected.clear();
+        self.selection = None;
+        if let Some(id) = id {
+            let shape = self.get(id).expect("shape_at() returned a shape in the document");
+            self.selection = Selection::try_from(shape.clone())
+                .ok()
+                .or_else(|| Some(Selection::Rect(shape.bounding_box())));
+            self.selected.push(id);
+        }
+        id
+    }
+
+    // Selects everything in a rectangular area, as dragging out a marquee
+    // does: the area itself and every shape it touches.
+    pub fn select_in_rect(&mut self, rect: Rect) -> &[ShapeId] {
+        self.set_selection(Some(Selection::Rect(rect)));
+        &self.selected
+    }
+
+    // The shapes a selected region touches, in z-order. Any selection but
+    // text is a single region, and the index holds each shape once, so no
+    // shape is found twice.
+    fn touching(&self, selection: &Selection) -> Vec<ShapeId> {
+        if let Selection::Text(_) = *selection {
+            return Vec::new();
+        }
+        let mut touching = Vec::new();
+        for region in selection.to_shapes() {
+            for id in self.near(&region.bounding_box()) {
+                if region.intersects(&self.shapes[self.z_indices[&id]].1) {
+                    touching.push(id);
+                }
+            }
+        }
+        touching.sort_by_key(|id| self.z_indices[id]);
+        touching
+    }
+
+    // The shapes whose bounding boxes come within ON_LINE_TOLERANCE of
+    // `rect`, which the shapes touching it are among.
+    fn near(&self, rect: &Rect) -> Vec<ShapeId> {
+        let margin = Point::new(ON_LINE_TOLERANCE, ON_LINE_TOLERANCE);
+        let area = Rect::new(rect.top_left - margin, rect.bottom_right + margin);
+        self.index.query_rect(&area).into_iter().cloned().collect()
+    }
+}
+
+// Two documents are equal if they have the same shapes, under the same ids
+// and in the same order, and the same selection, snapping and drag, however
+// their indexes happen to be laid out.
+impl PartialEq for Document {
+    fn eq(&self, other: &Document) -> bool {
+        self.shapes == other.shapes
+            && self.next_id == other.next_id
+            && self.selection == other.selection
+            && self.selected == other.selected
+            && self.snap == other.snap
+            && self.drag == other.drag
+    }
+}
+
+// A shape in a document, borrowed to be changed through Deref and DerefMut.
+// When it's dropped, the document's index is updated if the shape's
+// bounding box has changed.
+pub struct ShapeMut<'a> {
+    document: &'a mut Document,
+    index: usize,
+    // The bounding box the shape is indexed under.
+    bounds: Rect
+}
+
+impl Deref for ShapeMut<'_> {
+    type Target = Shape;
+
+    fn deref(&self) -> &Shape {
+        &self.document.shapes[self.index].1
+    }
+}
+
+impl DerefMut for ShapeMut<'_> {
+    fn deref_mut(&mut self) -> &mut Shape {
+        &mut self.document.shapes[self.index].1
+    }
+}
+
+impl Drop for ShapeMut<'_> {
+    fn drop(&mut self) {
+        let (id, ref shape) = self.document.shapes[self.index];
+        let bounds = shape.bounding_box();
+        if bounds != self.bounds {
+            self.document.index.remove(&self.bounds, &id);
+            self.document.index.insert(bounds, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::super::TextSelection;
+    use super::*;
+
+    fn square() -> Shape {
+        Shape::Rect(Rect::new(Point::new(0.0, 0.0), Point::new(5.0, 5.0)))
+    }
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Rect {
+        Rect::new(Point::new(x0, y0), Point::new(x1, y1))
+    }
+
+    // Three overlapping squares from the bottom up, a to c, and a circle off
+    // to one side.
+    fn layered() -> (Document, [ShapeId; 4]) {
+        let mut document = Document::new();
+        let a = document.add(square());
+        let b = document.add(square().translated(2.0, 2.0));
+        let c = document.add(square().translated(4.0, 4.0));
+        let d = document.add(Shape::Ellipse {
+            center: Point::new(20.0, 2.0),
+            radius_x: 1.0,
+            radius_y: 1.0,
+            rotation: 0.0
+        });
+        (document, [a, b, c, d])
+    }
+
+    fn order(document: &Document) -> Vec<ShapeId> {
+        for (index, (id, _)) in document.shapes().enumerate() {
+            assert_eq!(document.z_index(id), Some(index));
+        }
+        document.shapes().map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn select_at_picks_the_topmost_shape() {
+        let (mut document, [a, b, c, _]) = layered();
+        assert_eq!(document.select_at(Point::new(4.5, 4.5)), Some(c));
+        assert_eq!(document.selected(), [c]);
+        assert_eq!(document.selection(), Some(&Selection::Rect(rect(4.0, 4.0, 9.0, 9.0))));
+        assert_eq!(document.select_at(Point::new(3.0, 3.0)), Some(b));
+        assert_eq!(document.select_at(Point::new(1.0, 1.0)), Some(a));
+        assert_eq!(document.selected(), [a]);
+
+        assert_eq!(document.select_at(Point::new(15.0, 15.0)), None);
+        assert!(document.selected().is_empty());
+        assert_eq!(document.selection(), None);
+    }
+
+    // A line encloses no area, so clicking it selects its bounding box.
+    #[test]
+    fn select_at_a_line_selects_its_bounding_box() {
+        let mut document = Document::new();
+        let id = document.add(Shape::Line(Point::new(0.0, 0.0), Point::new(4.0, 2.0)));
+        assert_eq!(document.select_at(Point::new(2.0, 1.0)), Some(id));
+        assert_eq!(document.selection(), Some(&Selection::Rect(rect(0.0, 0.0, 4.0, 2.0))));
+    }
+
+    #[test]
+    fn select_in_rect_selects_every_shape_it_touches() {
+        let (mut document, [a, b, c, d]) = layered();
+        assert_eq!(document.select_in_rect(rect(6.0, 6.0, 30.0, 8.0)), [b, c]);
+        assert_eq!(document.select_in_rect(rect(-1.0, -1.0, 30.0, 30.0)), [a, b, c, d]);
+        assert_eq!(document.select_in_rect(rect(12.0, 12.0, 13.0, 13.0)), []);
+        // A rect touching a side picks the shape up.
+        assert_eq!(document.select_in_rect(rect(9.0, 5.0, 10.0, 6.0)), [c]);
+
+        document.set_selection(Some(Selection::Ellipse {
+            center: Point::new(20.0, 0.0),
+            radius_x: 2.0,
+            radius_y: 1.5,
+            rotation: 0.0
+        }));
+        assert_eq!(document.selected(), [d]);
+        document.set_selection(Some(Selection::Text(TextSelection::from(0..3))));
+        assert!(document.selected().is_empty());
+        document.clear_selection();
+        assert_eq!(document.selection(), None);
+    }
+
+    // The selected shapes stay in z-order, whatever order the index finds
+    // them in.
+    #[test]
+    fn selected_shapes_are_in_z_order() {
+        let (mut document, [a, b, c, d]) = layered();
+        document.bring_to_front(a);
+        document.send_to_back(d);
+        assert_eq!(document.select_in_rect(rect(-1.0, -1.0, 30.0, 30.0)), [d, b, c, a]);
+    }
+
+    #[test]
+    fn z_order_operations_keep_z_index_in_step() {
+        let (mut document, [a, b, c, d]) = layered();
+        assert!(document.bring_to_front(a));
+        assert_eq!(order(&document), [b, c, d, a]);
+        assert!(document.send_to_back(d));
+        assert_eq!(order(&document), [d, b, c, a]);
+        assert!(document.raise(b));
+        assert_eq!(order(&document), [d, c, b, a]);
+        assert!(document.lower(b));
+        assert_eq!(order(&document), [d, b, c, a]);
+
+        // At the ends of the stack there's nowhere further to go.
+        assert!(document.raise(a));
+        assert!(document.lower(d));
+        assert!(document.bring_to_front(a));
+        assert_eq!(order(&document), [d, b, c, a]);
+
+        document.remove(b);
+        assert!(!document.raise(b));
+        assert!(!document.bring_to_front(b));
+        assert_eq!(order(&document), [d, c, a]);
+        assert_eq!(document.shape_at(Point::new(4.5, 4.5)), Some(a));
+    }
+
+    #[test]
+    fn a_drag_of_small_steps_snaps_its_total() {
+        let mut document = Document::new();
+        let id = document.add(square());
+        document.set_snap(Snap::new().with_grid(10.0));
+
+        assert!(document.begin_drag(id));
+        for step in 1..=20 {
+            assert!(document.drag_to(step as f64, 0.0));
+        }
+        assert_eq!(document.end_drag(), Some(id));
+        assert_eq!(document.get(id),
+                   Some(&Shape::Rect(Rect::new(Point::new(20.0, 0.0), Point::new(25.0, 5.0)))));
+        assert_eq!(document.shape_at(Point::new(22.0, 2.0)), Some(id));
+        assert_eq!(document.shape_at(Point::new(2.0, 2.0)), None);
+    }
+
+    #[test]
+    fn a_drag_snaps_to_the_nearest_grid_point() {
+        let mut document = Document::new();
+        let id = document.add(square());
+        document.set_snap(Snap::new().with_grid(10.0));
+
+        document.begin_drag(id);
+        document.drag_to(4.0, 6.0);
+        assert_eq!(document.get(id), Some(&square().translated(0.0, 10.0)));
+        // Dragging back to the start puts the shape back.
+        document.drag_to(0.0, 0.0);
+        assert_eq!(document.get(id), Some(&square()));
+    }
+
+    #[test]
+    fn a_rotation_of_small_steps_snaps_its_total() {
+        let mut document = Document::new();
+        let id = document.add(Shape::Ellipse {
+            center: Point::new(0.0, 0.0),
+            radius_x: 4.0,
+            radius_y: 2.0,
+            rotation: 0.0
+        });
+        document.set_snap(Snap::new().with_angle(PI / 12.0));
+
+        document.begin_drag(id);
+        for step in 1..=20 {
+            assert!(document.drag_rotation(step as f64 * 0.05));
+        }
+        document.end_drag();
+        // A turn of 1 radian in all snaps to 4 steps of 15 degrees.
+        match document.get(id) {
+            Some(&Shape::Ellipse { center, radius_x, radius_y, rotation }) => {
+                assert!(center.approx_eq(&Point::new(0.0, 0.0), 1e-9));
+                assert!((radius_x - 4.0).abs() < 1e-9 && (radius_y - 2.0).abs() < 1e-9);
+                assert!((rotation.rem_euclid(PI) - PI / 3.0).abs() < 1e-9);
+            }
+            other => panic!("expected an ellipse, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn dragging_without_a_drag_does_nothing() {
+        let mut document = Document::new();
+        let id = document.add(square());
+        assert!(!document.drag_to(1.0, 1.0));
+        assert!(!document.drag_rotation(1.0));
+        assert_eq!(document.end_drag(), None);
+
+        document.begin_drag(id);
+        document.remove(id);
+        assert!(!document.drag_to(1.0, 1.0));
+        assert!(!document.begin_drag(id));
+        assert_eq!(document.dragging(), None);
+    }
+}
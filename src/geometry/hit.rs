@@ -0,0 +1,183 @@
+use super::{Point, Rect, Shape};
+
+// How far from a line a point can be, in canvas units, and still count as
+// on it, to allow for rounding in the point's coordinates.
+pub(super) const ON_LINE_TOLERANCE: f64 = 1e-9;
+
+impl Shape {
+    // Whether `p` lies inside the shape or on its edge. Nothing is inside a
+    // line, so for one this asks whether `p` lies on it. A text span's
+    // position depends on layout this module doesn't know, so it never
+    // contains any point.
+    pub fn contains_point(&self, p: Point) -> bool {
+        match *self {
+            Shape::Rect(ref rect) =>
+                rect_contains(rect, p),
+            Shape::Ellipse { center, radius_x, radius_y, rotation } => {
+                if radius_x <= 0.0 || radius_y <= 0.0 {
+                    return false;
+                }
+                let local = ellipse_normalize(center, radius_x, radius_y, rotation, p);
+                local.x * local.x + local.y * local.y <= 1.0
+            }
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                polygon_contains(&[top_left, top_right, bottom_right, bottom_left], p),
+            Shape::TextSpan(..) =>
+                false,
+            Shape::Polygon(ref points) =>
+                polygon_contains(points, p),
+            Shape::Line(a, b) =>
+                segment_distance(a, b, p) <= ON_LINE_TOLERANCE,
+            Shape::Group(ref shapes) =>
+                shapes.iter().any(|shape| shape.contains_point(p))
+        }
+    }
+}
+
+pub(super) fn rect_contains(rect: &Rect, p: Point) -> bool {
+    rect.top_left.x <= p.x && p.x <= rect.bottom_right.x
+        && rect.top_left.y <= p.y && p.y <= rect.bottom_right.y
+}
+
+// Maps `p` into the space where the ellipse is the unit circle at the origin,
+// by undoing its rotation and then its radii.
+pub(super) fn ellipse_normalize(center: Point, radius_x: f64, radius_y: f64, rotation: f64, p: Point)
+    -> Point
+{
+    let (sin, cos) = rotation.sin_cos();
+    let (dx, dy) = (p.x - center.x, p.y - center.y);
+    Point::new((dx * cos + dy * sin) / radius_x, (dy * cos - dx * sin) / radius_y)
+}
+
+// Whether `p` is inside the polygon, by its winding number: how many times
+// the outline goes around `p`, counting one way as positive and the other
+// as negative. Anything but zero is inside. For a polygon that doesn't cross
+// itself that's the same as the even-odd rule of ray casting, but it
+// doesn't need the special cases ray casting has for a ray passing exactly
+// through a corner. Points on the outline itself also count as inside.
+pub(super) fn polygon_contains(points: &[Point], p: Point) -> bool {
+    let n = points.len();
+    if n < 3 {
+        return false;
+    }
+    let mut winding = 0;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        if segment_distance(a, b, p) <= ON_LINE_TOLERANCE {
+            return true;
+        }
+        // Count the edges crossing the horizontal line through `p` to its
+        // right: upwards if `p` is left of the edge, downwards if right.
+        if a.y <= p.y {
+            if b.y > p.y && cross(a, b, p) > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= p.y && cross(a, b, p) < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding != 0
+}
+
+// Twice the signed area of the triangle a, b, p: positive if p is to the
+// left of the line from a to b (in maths orientation), negative if to the
+// right, zero if on it.
+pub(super) fn cross(a: Point, b: Point, p: Point) -> f64 {
+    (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y)
+}
+
+// The distance from `p` to the nearest point of the segment from `a` to `b`.
+pub(super) fn segment_distance(a: Point, b: Point, p: Point) -> f64 {
+    let along = b - a;
+    let length_squared = along.dot(along);
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        ((p - a).dot(along) / length_squared).clamp(0.0, 1.0)
+    };
+    p.distance_to(a + along * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_4;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point> {
+        coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    // A U shape, open at the top between x = 3 and x = 7.
+    fn u_shape() -> Vec<Point> {
+        points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (7.0, 10.0),
+                 (7.0, 3.0), (3.0, 3.0), (3.0, 10.0), (0.0, 10.0)])
+    }
+
+    #[test]
+    fn a_point_in_the_notch_of_a_concave_polygon_is_outside() {
+        let u = u_shape();
+        assert!(!polygon_contains(&u, Point::new(5.0, 6.0)));
+        assert!(!polygon_contains(&u, Point::new(5.0, 10.0)));
+        assert!(polygon_contains(&u, Point::new(1.5, 8.0)));
+        assert!(polygon_contains(&u, Point::new(8.5, 8.0)));
+        assert!(polygon_contains(&u, Point::new(5.0, 1.5)));
+        assert!(!polygon_contains(&u, Point::new(11.0, 5.0)));
+        // The same outline wound the other way round.
+        let mut reversed = u;
+        reversed.reverse();
+        assert!(!polygon_contains(&reversed, Point::new(5.0, 6.0)));
+        assert!(polygon_contains(&reversed, Point::new(1.5, 8.0)));
+    }
+
+    #[test]
+    fn points_on_the_outline_are_inside() {
+        let u = Shape::Polygon(u_shape());
+        // Corners, including the inner corners of the notch.
+        assert!(u.contains_point(Point::new(0.0, 0.0)));
+        assert!(u.contains_point(Point::new(7.0, 3.0)));
+        assert!(u.contains_point(Point::new(3.0, 10.0)));
+        // Edges, including one level with the horizontal line counted
+        // across.
+        assert!(u.contains_point(Point::new(10.0, 5.0)));
+        assert!(u.contains_point(Point::new(5.0, 3.0)));
+        assert!(u.contains_point(Point::new(3.0, 6.0)));
+        assert!(!u.contains_point(Point::new(5.0, 3.0 + 1e-6)));
+
+        let rect = Shape::Rect(Rect::new(Point::new(0.0, 0.0), Point::new(4.0, 2.0)));
+        assert!(rect.contains_point(Point::new(4.0, 2.0)));
+        assert!(rect.contains_point(Point::new(2.0, 0.0)));
+        assert!(!rect.contains_point(Point::new(4.0 + 1e-6, 1.0)));
+    }
+
+    #[test]
+    fn a_rotated_ellipse_contains_points_along_its_turned_axes() {
+        // Long along the diagonal from top left to bottom right.
+        let ellipse = Shape::Ellipse {
+            center: Point::new(1.0, 1.0),
+            radius_x: 4.0,
+            radius_y: 1.0,
+            rotation: FRAC_PI_4
+        };
+        assert!(ellipse.contains_point(Point::new(3.5, 3.5)));
+        assert!(ellipse.contains_point(Point::new(-1.5, -1.5)));
+        assert!(!ellipse.contains_point(Point::new(4.0, 4.0)));
+        // Across the diagonal it's only a unit from the center.
+        assert!(ellipse.contains_point(Point::new(1.5, 0.5)));
+        assert!(!ellipse.contains_point(Point::new(2.0, 0.0)));
+        // Points the unturned ellipse would contain.
+        assert!(!ellipse.contains_point(Point::new(4.5, 1.0)));
+        assert!(!ellipse.contains_point(Point::new(-2.5, 1.0)));
+    }
+
+    #[test]
+    fn a_line_contains_only_points_within_the_tolerance() {
+        let line = Shape::Line(Point::new(0.0, 0.0), Point::new(10.0, 5.0));
+        assert!(line.contains_point(Point::new(4.0, 2.0)));
+        assert!(line.contains_point(Point::new(4.0, 2.0 + ON_LINE_TOLERANCE / 2.0)));
+        assert!(!line.contains_point(Point::new(4.0, 2.0 + ON_LINE_TOLERANCE * 10.0)));
+        // Nearer the end than the tolerance, but past it.
+        assert!(line.contains_point(Point::new(10.0 + ON_LINE_TOLERANCE / 2.0, 5.0)));
+        assert!(!line.contains_point(Point::new(10.0 + ON_LINE_TOLERANCE * 10.0, 5.0)));
+        assert!(!line.contains_point(Point::new(12.0, 6.0)));
+    }
+}
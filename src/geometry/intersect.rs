@@ -0,0 +1,242 @@
+use std::f64::consts::PI;
+
+use super::hit::{cross, ellipse_normalize, polygon_contains, segment_distance, ON_LINE_TOLERANCE};
+use super::{Point, Shape};
+
+// How many sides intersects() gives the polygon it stands in for an ellipse
+// with when testing two ellipses against each other.
+const ELLIPSE_SIDES: usize = 64;
+
+impl Shape {
+    // Whether the two shapes overlap or touch, so that marquee selection can
+    // find every shape a rectangle reaches. Rects, trapezoids and polygons
+    // are tested exactly, against each other and against ellipses and
+    // lines. Two ellipses are tested with one of them as a 64-sided polygon
+    // inside it, so ellipses that only just touch may be missed. Text spans
+    // have no position without layout, so they intersect nothing. A group
+    // intersects whatever any of its shapes does.
+    pub fn intersects(&self, other: &Shape) -> bool {
+        if let Shape::Group(ref shapes) = *self {
+            return shapes.iter().any(|shape| shape.intersects(other));
+        }
+        if let Shape::Group(ref shapes) = *other {
+            return shapes.iter().any(|shape| self.intersects(shape));
+        }
+        match (Outline::of(self), Outline::of(other)) {
+            (Outline::Nothing, _) | (_, Outline::Nothing) =>
+                false,
+            (Outline::Polygon(a), Outline::Polygon(b)) =>
+                polygons_intersect(&a, &b),
+            (Outline::Polygon(points), Outline::Segment(p, q))
+            | (Outline::Segment(p, q), Outline::Polygon(points)) =>
+                polygon_meets_segment(&points, p, q),
+            (Outline::Segment(a, b), Outline::Segment(c, d)) =>
+                segments_intersect(a, b, c, d),
+            (Outline::Ellipse(ellipse), Outline::Polygon(points))
+            | (Outline::Polygon(points), Outline::Ellipse(ellipse)) =>
+                ellipse_meets_polygon(ellipse, &points),
+            (Outline::Ellipse(ellipse), Outline::Segment(p, q))
+            | (Outline::Segment(p, q), Outline::Ellipse(ellipse)) =>
+                ellipse.distance_from_segment(p, q) <= 1.0,
+            (Outline::Ellipse(a), Outline::Ellipse(b)) =>
+                ellipse_meets_polygon(a, &b.to_polygon())
+        }
+    }
+}
+
+// A shape reduced to one of the few forms intersects() has to deal with.
+enum Outline {
+    Polygon(Vec<Point>),
+    Segment(Point, Point),
+    Ellipse(Ellipse),
+    Nothing
+}
+
+impl Outline {
+    fn of(shape: &Shape) -> Outline {
+        match *shape {
+            Shape::Rect(ref rect) =>
+                Outline::Polygon(rect.corners().to_vec()),
+            Shape::Ellipse { center, radius_x, radius_y, rotation } =>
+                Outline::ellipse(center, radius_x, radius_y, rotation),
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                Outline::Polygon(vec![top_left, top_right, bottom_right, bottom_left]),
+            Shape::TextSpan(..) | Shape::Group(_) =>
+                Outline::Nothing,
+            Shape::Polygon(ref points) => match points.len() {
+                0 => Outline::Nothing,
+                1 => Outline::Segment(points[0], points[0]),
+                2 => Outline::Segment(points[0], points[1]),
+                _ => Outline::Polygon(points.clone())
+            },
+            Shape::Line(a, b) =>
+                Outline::Segment(a, b)
+        }
+    }
+
+    // An ellipse squashed flat along either axis is a line across it.
+    fn ellipse(center: Point, radius_x: f64, radius_y: f64, rotation: f64) -> Outline {
+        let (radius_x, radius_y) = (radius_x.max(0.0), radius_y.max(0.0));
+        if radius_x == 0.0 || radius_y == 0.0 {
+            let (sin, cos) = rotation.sin_cos();
+            let (dx, dy) = (radius_x * cos - radius_y * sin, radius_x * sin + radius_y * cos);
+            return Outline::Segment(Point::new(center.x - dx, center.y - dy),
+                                    Point::new(center.x + dx, center.y + dy));
+        }
+        Outline::Ellipse(Ellipse { center, radius_x, radius_y, rotation })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Ellipse {
+    center: Point,
+    radius_x: f64,
+    radius_y: f64,
+    rotation: f64
+}
+
+impl Ellipse {
+    // Maps `p` into the space where this ellipse is the unit circle at the
+    // origin. That only turns and stretches the canvas, which doesn't change
+    // whether two shapes meet, and makes the tests below simple.
+    fn normalize(&self, p: Point) -> Point {
+        ellipse_normalize(self.center, self.radius_x, self.radius_y, self.rotation, p)
+    }
+
+    // The distance from the center to the segment, in normalized units, so
+    // that anything up to 1 is inside or on the ellipse.
+    fn distance_from_segment(&self, p: Point, q: Point) -> f64 {
+        segment_distance(self.normalize(p), self.normalize(q), Point::ORIGIN)
+    }
+
+    fn to_polygon(self) -> Vec<Point> {
+        (0..ELLIPSE_SIDES)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / ELLIPSE_SIDES as f64;
+                let (x, y) = (self.radius_x * angle.cos(), self.radius_y * angle.sin());
+                let (sin, cos) = self.rotation.sin_cos();
+                Point::new(self.center.x + x * cos - y * sin, self.center.y + x * sin + y * cos)
+            })
+            .collect()
+    }
+}
+
+// The edges of a closed polygon, including the one back to the start.
+pub(super) fn edges(points: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    points.iter().zip(points.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+}
+
+fn polygons_intersect(a: &[Point], b: &[Point]) -> bool {
+    // Either an edge of one crosses an edge of the other, or they don't and
+    // one lies wholly inside the other.
+    edges(a).any(|(p, q)| edges(b).any(|(r, s)| segments_intersect(p, q, r, s)))
+        || polygon_contains(a, b[0])
+        || polygon_contains(b, a[0])
+}
+
+fn polygon_meets_segment(points: &[Point], p: Point, q: Point) -> bool {
+    polygon_contains(points, p) || edges(points).any(|(a, b)| segments_intersect(a, b, p, q))
+}
+
+fn ellipse_meets_polygon(ellipse: Ellipse, points: &[Point]) -> bool {
+    let normalized: Vec<Point> = points.iter().map(|&p| ellipse.normalize(p)).collect();
+    polygon_contains(&normalized, Point::ORIGIN)
+        || edges(points).any(|(p, q)| ellipse.distance_from_segment(p, q) <= 1.0)
+}
+
+// Whether segment ab and segment cd share a point: either they cross, with
+// each one's ends on opposite sides of the other, or an end of one lies on
+// the other.
+fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let crosses = |p: Point, q: Point, r: Point, s: Point| {
+        let (side_r, side_s) = (cross(p, q, r), cross(p, q, s));
+        (side_r > 0.0 && side_s < 0.0) || (side_r < 0.0 && side_s > 0.0)
+    };
+    if crosses(a, b, c, d) && crosses(c, d, a, b) {
+        return true;
+    }
+    segment_distance(a, b, c) <= ON_LINE_TOLERANCE
+        || segment_distance(a, b, d) <= ON_LINE_TOLERANCE
+        || segment_distance(c, d, a) <= ON_LINE_TOLERANCE
+        || segment_distance(c, d, b) <= ON_LINE_TOLERANCE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Rect;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Shape {
+        Shape::Rect(Rect::new(Point::new(x0, y0), Point::new(x1, y1)))
+    }
+
+    // Checks both ways round, since intersects() matches on the pair.
+    fn meet(a: &Shape, b: &Shape) -> bool {
+        let there = a.intersects(b);
+        assert_eq!(there, b.intersects(a), "{:?} and {:?} disagree", a, b);
+        there
+    }
+
+    #[test]
+    fn rects_meet_when_they_overlap_or_touch() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        assert!(meet(&a, &rect(1.0, 1.0, 3.0, 3.0)));
+        // Sharing an edge, part of one and a single corner.
+        assert!(meet(&a, &rect(2.0, 0.0, 4.0, 2.0)));
+        assert!(meet(&a, &rect(0.5, 2.0, 1.5, 3.0)));
+        assert!(meet(&a, &rect(2.0, 2.0, 3.0, 3.0)));
+        assert!(!meet(&a, &rect(2.0 + 1e-6, 0.0, 4.0, 2.0)));
+        assert!(!meet(&a, &rect(3.0, 3.0, 4.0, 4.0)));
+        // Level with it, but off to one side.
+        assert!(!meet(&a, &rect(5.0, 0.5, 6.0, 1.5)));
+    }
+
+    #[test]
+    fn a_rect_near_a_circles_bounding_box_corner() {
+        let circle = Shape::Ellipse {
+            center: Point::new(0.0, 0.0),
+            radius_x: 1.0,
+            radius_y: 1.0,
+            rotation: 0.0
+        };
+        // Both overlap the circle's bounding box, but only the first
+        // reaches the curve: its corner is 0.85 from the center, the
+        // second's 1.06.
+        assert!(meet(&circle, &rect(0.6, 0.6, 2.0, 2.0)));
+        assert!(!meet(&circle, &rect(0.75, 0.75, 2.0, 2.0)));
+        // Touching it where it meets its bounding box.
+        assert!(meet(&circle, &rect(1.0, -0.5, 2.0, 0.5)));
+        // A rect around the whole circle, with no edge reaching it.
+        assert!(meet(&circle, &rect(-5.0, -5.0, 5.0, 5.0)));
+    }
+
+    #[test]
+    fn a_polygon_inside_another_meets_it() {
+        let outer = Shape::Polygon(vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0),
+                                        Point::new(5.0, 10.0)]);
+        let inner = Shape::Polygon(vec![Point::new(4.0, 2.0), Point::new(6.0, 2.0),
+                                        Point::new(6.0, 4.0), Point::new(4.0, 4.0)]);
+        assert!(meet(&outer, &inner));
+        assert!(meet(&outer, &rect(4.0, 2.0, 6.0, 4.0)));
+        let beside = Shape::Polygon(vec![Point::new(9.0, 8.0), Point::new(12.0, 8.0),
+                                         Point::new(12.0, 10.0)]);
+        assert!(!meet(&outer, &beside));
+    }
+
+    #[test]
+    fn a_line_across_a_polygon_with_both_ends_outside_meets_it() {
+        let square = rect(0.0, 0.0, 4.0, 4.0);
+        let across = Shape::Line(Point::new(-1.0, 2.0), Point::new(5.0, 3.0));
+        assert!(meet(&square, &across));
+        let diamond = Shape::Polygon(vec![Point::new(2.0, 0.0), Point::new(4.0, 2.0),
+                                          Point::new(2.0, 4.0), Point::new(0.0, 2.0)]);
+        assert!(meet(&diamond, &across));
+        // From the square's corner, stopping short of the diamond.
+        let short = Shape::Line(Point::new(0.0, 0.0), Point::new(0.9, 0.9));
+        assert!(!meet(&diamond, &short));
+        assert!(meet(&square, &short));
+        assert!(!meet(&square, &Shape::Line(Point::new(-1.0, 5.0), Point::new(5.0, 6.0))));
+        // Wholly inside, with no edge crossed.
+        assert!(meet(&square, &Shape::Line(Point::new(1.0, 1.0), Point::new(3.0, 2.0))));
+    }
+}
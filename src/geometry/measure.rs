@@ -0,0 +1,85 @@
+use std::f64::consts::PI;
+
+use super::{Point, Shape};
+
+impl Shape {
+    // The area the shape covers. Lines and text spans cover none. A group's
+    // is the total of its shapes', so where they overlap it's counted more
+    // than once.
+    pub fn area(&self) -> f64 {
+        match *self {
+            Shape::Rect(ref rect) =>
+                rect.width() * rect.height(),
+            Shape::Ellipse { radius_x, radius_y, .. } =>
+                PI * radius_x * radius_y,
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                polygon_area(&[top_left, top_right, bottom_right, bottom_left]),
+            Shape::TextSpan(..) =>
+                0.0,
+            Shape::Polygon(ref points) =>
+                polygon_area(points),
+            Shape::Line(..) =>
+                0.0,
+            Shape::Group(ref shapes) =>
+                shapes.iter().map(Shape::area).sum()
+        }
+    }
+
+    // The length of the shape's outline. A line's is its length; a text
+    // span has none; a group's is the total of its shapes'.
+    pub fn perimeter(&self) -> f64 {
+        match *self {
+            Shape::Rect(ref rect) =>
+                2.0 * (rect.width() + rect.height()),
+            Shape::Ellipse { radius_x, radius_y, .. } =>
+                ellipse_perimeter(radius_x, radius_y),
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                polygon_perimeter(&[top_left, top_right, bottom_right, bottom_left]),
+            Shape::TextSpan(..) =>
+                0.0,
+            Shape::Polygon(ref points) =>
+                polygon_perimeter(points),
+            Shape::Line(a, b) =>
+                a.distance_to(b),
+            Shape::Group(ref shapes) =>
+                shapes.iter().map(Shape::perimeter).sum()
+        }
+    }
+}
+
+fn polygon_area(points: &[Point]) -> f64 {
+    signed_area(points).abs()
+}
+
+// The shoelace formula, which works for any polygon that doesn't cross
+// itself: its area, positive if its points go clockwise on screen, as
+// Rect::corners() does, and negative if anticlockwise.
+pub(super) fn signed_area(points: &[Point]) -> f64 {
+    let n = points.len();
+    let twice_signed: f64 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    twice_signed / 2.0
+}
+
+fn polygon_perimeter(points: &[Point]) -> f64 {
+    let n = points.len();
+    if n < 2 {
+        return 0.0;
+    }
+    (0..n).map(|i| points[i].distance_to(points[(i + 1) % n])).sum()
+}
+
+// An ellipse's perimeter has no closed form. Ramanujan's second
+// approximation is within a few parts per million for any ellipse short of
+// a very flat one, and exact for a circle.
+fn ellipse_perimeter(a: f64, b: f64) -> f64 {
+    if a + b == 0.0 {
+        return 0.0;
+    }
+    let h = ((a - b) / (a + b)).powi(2);
+    PI * (a + b) * (1.0 + 3.0 * h / (10.0 + (4.0 - 3.0 * h).sqrt()))
+}
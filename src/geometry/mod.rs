@@ -0,0 +1,145 @@
+// Shapes on a canvas, from the chapter's selection example, which panics on
+// a selection it doesn't expect:
+//
+//     // There are many Shapes, but we only support "selecting"
+//     // either some text, or everything in a rectangular area.
+//     // You can't select an ellipse or trapezoid.
+//     match document.selection() {
+//         Shape::TextSpan(start, end) => paint_text_selection(start, end),
+//         Shape::Rectangle(rect) => paint_rect_selection(rect),
+//         _ => panic!("unexpected selection type")
+//     }
+//
+// Here a selection is a Selection instead, built from a shape with
+// Selection::try_from(), which covers every shape that encloses an area and
+// returns an error for the rest:
+//
+//     match Selection::try_from(shape)? {
+//         Selection::Text(text) => {
+//             for range in text.ranges() {
+//                 paint_text_selection(range.start, range.end);
+//             }
+//         }
+//         Selection::Rect(rect) => paint_rect_selection(rect),
+//         Selection::Ellipse { center, radius_x, radius_y, rotation } => ...,
+//         Selection::Polygon(points) => ...
+//     }
+//
+// Coordinates are f64s in screen order, with y growing downwards. The
+// module needs `std` for the floating-point functions.
+
+use core::fmt;
+
+mod boolean;
+mod bounds;
+mod document;
+mod hit;
+mod intersect;
+mod measure;
+mod paint;
+mod point;
+mod point3d;
+mod quadtree;
+mod rect;
+mod region;
+mod selection;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod snap;
+mod svg;
+mod svg_parse;
+mod text_selection;
+mod transform;
+
+pub use self::boolean::BooleanError;
+pub use self::bounds::TextMetrics;
+pub use self::document::{Document, ShapeId, ShapeMut};
+pub use self::paint::{AsciiPainter, Painter};
+pub use self::point::Point;
+pub use self::point3d::Point3d;
+pub use self::quadtree::QuadTree;
+pub use self::rect::Rect;
+pub use self::region::{
+    classify, classify3d, classify3d_within, classify_within, Axis, Plane, Region, Region3d
+};
+pub use self::selection::{Selection, SelectionError};
+pub use self::snap::{snap_angle, snap_point, snap_shape, Snap};
+pub use self::svg_parse::{ParseSvgError, SvgErrorKind};
+pub use self::text_selection::{LineColumn, TextSelection};
+pub use self::transform::Matrix2x3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Rect(Rect),
+    // An ellipse with radius_x along the x axis and radius_y along the y
+    // axis before it's turned by `rotation`, in radians clockwise on screen.
+    Ellipse {
+        center: Point,
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64
+    },
+    // A quadrilateral whose top and bottom sides are parallel, given by its
+    // corners.
+    Trapezoid {
+        top_left: Point,
+        top_right: Point,
+        bottom_right: Point,
+        bottom_left: Point
+    },
+    // A run of text, by the character offsets of its start and end. Where
+    // it appears on the canvas depends on the text's layout.
+    TextSpan(usize, usize),
+    // A closed polygon through the given points in order, which may be
+    // concave but shouldn't cross itself.
+    Polygon(Vec<Point>),
+    Line(Point, Point),
+    // Shapes that move, scale and select together. Groups can nest.
+    Group(Vec<Shape>)
+}
+
+// Which variant a Shape is, without its data, for messages and errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShapeKind {
+    Rect,
+    Ellipse,
+    Trapezoid,
+    TextSpan,
+    Polygon,
+    Line,
+    Group
+}
+
+impl ShapeKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ShapeKind::Rect => "rect",
+            ShapeKind::Ellipse => "ellipse",
+            ShapeKind::Trapezoid => "trapezoid",
+            ShapeKind::TextSpan => "text span",
+            ShapeKind::Polygon => "polygon",
+            ShapeKind::Line => "line",
+            ShapeKind::Group => "group"
+        }
+    }
+}
+
+impl fmt::Display for ShapeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl Shape {
+    pub fn kind(&self) -> ShapeKind {
+        match *self {
+            Shape::Rect(_) => ShapeKind::Rect,
+            Shape::Ellipse { .. } => ShapeKind::Ellipse,
+            Shape::Trapezoid { .. } => ShapeKind::Trapezoid,
+            Shape::TextSpan(..) => ShapeKind::TextSpan,
+            Shape::Polygon(_) => ShapeKind::Polygon,
+            Shape::Line(..) => ShapeKind::Line,
+            Shape::Group(_) => ShapeKind::Group
+        }
+    }
+}
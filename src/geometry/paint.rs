@@ -0,0 +1,279 @@
+// Drawing a document, with the painting left to a backend. This stands in
+// for the chapter's free functions paint_text_selection(), paint_rect_selection(),
+// paint_outline() and optimized_paint(), so the code that decides what to
+// draw doesn't depend on what it's drawn with:
+//
+//     match self.get_selection() {
+//         rect @ Shape::Rect(..) => optimized_paint(&rect),
+//         other_shape => paint_outline(other_shape.get_outline())
+//     }
+
+use std::fmt;
+
+use super::hit::segment_distance;
+use super::{Document, Point, Rect, Selection, Shape};
+
+// A rendering backend. Only the outline is required; the rest default to
+// painting outlines, so a backend overrides what it can do better.
+pub trait Painter {
+    // Draws the outline of any shape.
+    fn paint_outline(&mut self, shape: &Shape);
+
+    // Draws a rectangle's outline, for backends with a faster way to do so
+    // than a general shape's.
+    fn optimized_paint(&mut self, rect: &Rect) {
+        self.paint_outline(&Shape::Rect(*rect));
+    }
+
+    // Highlights selected text, by the character offsets of its start and
+    // end.
+    fn paint_text_selection(&mut self, start: usize, end: usize);
+
+    // Highlights a selected rectangular area.
+    fn paint_rect_selection(&mut self, rect: &Rect) {
+        self.paint_area_selection(&Shape::Rect(*rect));
+    }
+
+    // Highlights any other selected area: an ellipse or a polygon.
+    fn paint_area_selection(&mut self, shape: &Shape) {
+        self.paint_outline(shape);
+    }
+}
+
+impl Selection {
+    pub fn paint<P: Painter + ?Sized>(&self, painter: &mut P) {
+        match *self {
+            Selection::Text(ref text) => {
+                for range in text.ranges() {
+                    painter.paint_text_selection(range.start, range.end);
+                }
+            }
+            Selection::Rect(ref rect) =>
+                painter.paint_rect_selection(rect),
+            Selection::Ellipse { .. } | Selection::Polygon(_) => {
+                for shape in self.to_shapes() {
+                    painter.paint_area_selection(&shape);
+                }
+            }
+        }
+    }
+}
+
+impl Document {
+    // Paints every shape, in order, and then the selection over them.
+    pub fn paint<P: Painter + ?Sized>(&self, painter: &mut P) {
+        for (_, shape) in self.shapes() {
+            paint_shape(shape, painter);
+        }
+        if let Some(selection) = self.selection() {
+            selection.paint(painter);
+        }
+    }
+}
+
+// Paints the shapes in a group one by one, so each still gets its own
+// treatment.
+fn paint_shape<P: Painter + ?Sized>(shape: &Shape, painter: &mut P) {
+    match *shape {
+        Shape::Rect(ref rect) => painter.optimized_paint(rect),
+        Shape::Group(ref shapes) => {
+            for shape in shapes {
+                paint_shape(shape, painter);
+            }
+        }
+        ref other_shape => painter.paint_outline(other_shape)
+    }
+}
+
+// A Painter that draws on a grid of characters, for checking what a
+// document looks like in a terminal or a test. Each character is a square
+// one canvas unit across, so the cell in column x and row y covers from
+// (x, y) to (x + 1, y + 1). Outlines are drawn with '#' and selected areas
+// filled with '*'. Text is taken to run across the grid a character per
+// cell, wrapping at the right edge, and selected text is drawn as '_'.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsciiPainter {
+    width: usize,
+    cells: Vec<char>
+}
+
+impl AsciiPainter {
+    // A blank grid `width` characters across and `height` down.
+    pub fn new(width: usize, height: usize) -> AsciiPainter {
+        AsciiPainter { width, cells: vec![' '; width * height] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    // The character at column `x` and row `y`.
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        if x >= self.width {
+            return None;
+        }
+        self.cells.get(y * self.width + x).cloned()
+    }
+
+    fn set(&mut self, x: usize, y: usize, c: char) {
+        if x < self.width {
+            if let Some(cell) = self.cells.get_mut(y * self.width + x) {
+                *cell = c;
+            }
+        }
+    }
+
+    fn center(x: usize, y: usize) -> Point {
+        Point::new(x as f64 + 0.5, y as f64 + 0.5)
+    }
+
+    // Every cell, by column and row.
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height()).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+}
+
+impl Painter for AsciiPainter {
+    // A cell is on the outline of an area if its center is inside but the
+    // center of a neighbouring cell isn't, and on a line if its center is
+    // within half a cell of it.
+    fn paint_outline(&mut self, shape: &Shape) {
+        let on_outline = |x: usize, y: usize| match *shape {
+            Shape::TextSpan(..) =>
+                false,
+            Shape::Line(a, b) =>
+                segment_distance(a, b, AsciiPainter::center(x, y)) <= 0.5,
+            _ => {
+                let inside = |x: f64, y: f64| shape.contains_point(Point::new(x + 0.5, y + 0.5));
+                let (x, y) = (x as f64, y as f64);
+                inside(x, y)
+                    && !(inside(x - 1.0, y) && inside(x + 1.0, y)
+                         && inside(x, y - 1.0) && inside(x, y + 1.0))
+            }
+        };
+        let cells: Vec<_> = self.positions().filter(|&(x, y)| on_outline(x, y)).collect();
+        for (x, y) in cells {
+            self.set(x, y, '#');
+        }
+    }
+
+    // Draws the four sides directly rather than testing every cell.
+    fn optimized_paint(&mut self, rect: &Rect) {
+        if rect.bottom_right.x < 0.5 || rect.bottom_right.y < 0.5 {
+            return;
+        }
+        let cell = |v: f64| v.floor().max(0.0) as usize;
+        let (left, top) = (cell(rect.top_left.x), cell(rect.top_left.y));
+        let (right, bottom) = (cell(rect.bottom_right.x - 0.5), cell(rect.bottom_right.y - 0.5));
+        // Cells off the grid aren't drawn, so there's no need to visit them.
+        for x in left..=right.min(self.width) {
+            self.set(x, top, '#');
+            self.set(x, bottom, '#');
+        }
+        for y in top..=bottom.min(self.height()) {
+            self.set(left, y, '#');
+            self.set(right, y, '#');
+        }
+    }
+
+    fn paint_text_selection(&mut self, start: usize, end: usize) {
+        if self.width == 0 {
+            return;
+        }
+        for offset in start..end.min(self.cells.len()) {
+            self.set(offset % self.width, offset / self.width, '_');
+        }
+    }
+
+    // Fills the blank cells whose centers are inside the area, leaving the
+    // outlines of shapes under the selection visible.
+    fn paint_area_selection(&mut self, shape: &Shape) {
+        let cells: Vec<_> = self.positions()
+            .filter(|&(x, y)| shape.contains_point(AsciiPainter::center(x, y)))
+            .collect();
+        for (x, y) in cells {
+            if self.get(x, y) == Some(' ') {
+                self.set(x, y, '*');
+            }
+        }
+    }
+}
+
+// The grid, a row per line, with trailing spaces left in so every line is
+// the same width.
+impl fmt::Display for AsciiPainter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            for &c in row {
+                fmt::Write::write_char(f, c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TextSelection;
+    use super::*;
+
+    fn paint(document: &Document, width: usize, height: usize) -> String {
+        let mut painter = AsciiPainter::new(width, height);
+        document.paint(&mut painter);
+        painter.to_string()
+    }
+
+    #[test]
+    fn a_rect_with_a_rect_selected_inside_it() {
+        let mut document = Document::new();
+        document.add(Shape::Rect(Rect::new(Point::new(1.0, 1.0), Point::new(9.0, 5.0))));
+        document.set_selection(Some(Selection::Rect(Rect::new(Point::new(3.0, 2.0),
+                                                              Point::new(7.0, 4.0)))));
+        let expected = ["            ",
+                        " ########   ",
+                        " # **** #   ",
+                        " # **** #   ",
+                        " ########   ",
+                        "            "];
+        assert_eq!(paint(&document, 12, 6), expected.join("\n"));
+    }
+
+    // A selection overlapping a shape fills around its outline, which stays
+    // visible, and shapes in a group are each drawn.
+    #[test]
+    fn a_group_under_a_polygon_selection() {
+        let mut document = Document::new();
+        document.add(Shape::Group(vec![
+            Shape::Line(Point::new(0.5, 0.5), Point::new(5.5, 0.5)),
+            Shape::Rect(Rect::new(Point::new(0.0, 2.0), Point::new(4.0, 5.0)))
+        ]));
+        document.set_selection(Some(Selection::Polygon(vec![Point::new(2.0, 1.0),
+                                                            Point::new(8.0, 1.0),
+                                                            Point::new(8.0, 4.0),
+                                                            Point::new(2.0, 4.0)])));
+        let expected = ["######  ",
+                        "  ******",
+                        "####****",
+                        "# *#****",
+                        "####    "];
+        assert_eq!(paint(&document, 8, 5), expected.join("\n"));
+    }
+
+    #[test]
+    fn a_text_selection_wraps_at_the_edge() {
+        let mut document = Document::new();
+        let text = TextSelection::from_ranges(vec![2..5, 10..12]);
+        document.set_selection(Some(Selection::Text(text)));
+        let expected = ["  ___   ",
+                        "  __    "];
+        assert_eq!(paint(&document, 8, 2), expected.join("\n"));
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+// A point on the canvas. Coordinates are in screen order: x grows to the
+// right and y grows downwards, so the top of a shape has the smaller y.
+//
+// A point doubles as a vector, the offset from the origin to it, so points
+// can be added, subtracted and scaled: `b - a` is the offset from a to b,
+// and `a + (b - a) * 0.5` is the point halfway between them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn dot(self, other: Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    // The length of the point as a vector: its distance from the origin.
+    pub fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    // Whether each coordinate is within `epsilon` of the other point's.
+    pub fn approx_eq(&self, other: &Point, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+
+    pub fn is_origin(&self, epsilon: f64) -> bool {
+        self.approx_eq(&Point::ORIGIN, epsilon)
+    }
+
+    pub fn distance_to(self, other: Point) -> f64 {
+        (other - self).length()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: f64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Mul<Point> for f64 {
+    type Output = Point;
+
+    fn mul(self, p: Point) -> Point {
+        p * self
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
@@ -0,0 +1,106 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+// A point in space, from the chapter's sphere example:
+//
+//     match sphere.center() {
+//         &Point3d { x, y, z } => ...
+//     }
+//
+// Like Point, it doubles as a vector and supports the same arithmetic, along
+// with the cross product, which only exists in three dimensions.
+//
+// The chapter's pattern &Point3d { x: 0.0, y: 0.0, z: 0.0 } only matches a
+// center exactly at the origin, and one worked out with floating point is
+// rarely exact. Match with a guard instead:
+//
+//     match sphere.center() {
+//         center if center.is_origin(1e-9) => ...,
+//         &Point3d { x, y, z } => ...
+//     }
+//
+// and classify with classify3d_within() rather than classify3d().
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point3d {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64
+}
+
+impl Point3d {
+    pub const ORIGIN: Point3d = Point3d { x: 0.0, y: 0.0, z: 0.0 };
+
+    pub fn new(x: f64, y: f64, z: f64) -> Point3d {
+        Point3d { x, y, z }
+    }
+
+    pub fn dot(self, other: Point3d) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    // The vector at right angles to both, following the right-hand rule,
+    // whose length is the area of the parallelogram they span.
+    pub fn cross(self, other: Point3d) -> Point3d {
+        Point3d::new(self.y * other.z - self.z * other.y,
+                     self.z * other.x - self.x * other.z,
+                     self.x * other.y - self.y * other.x)
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    // Whether each coordinate is within `epsilon` of the other point's.
+    pub fn approx_eq(&self, other: &Point3d, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
+
+    pub fn is_origin(&self, epsilon: f64) -> bool {
+        self.approx_eq(&Point3d::ORIGIN, epsilon)
+    }
+
+    pub fn distance_to(self, other: Point3d) -> f64 {
+        (other - self).length()
+    }
+}
+
+impl Add for Point3d {
+    type Output = Point3d;
+
+    fn add(self, other: Point3d) -> Point3d {
+        Point3d::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3d {
+    type Output = Point3d;
+
+    fn sub(self, other: Point3d) -> Point3d {
+        Point3d::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f64> for Point3d {
+    type Output = Point3d;
+
+    fn mul(self, factor: f64) -> Point3d {
+        Point3d::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Mul<Point3d> for f64 {
+    type Output = Point3d;
+
+    fn mul(self, p: Point3d) -> Point3d {
+        p * self
+    }
+}
+
+impl Neg for Point3d {
+    type Output = Point3d;
+
+    fn neg(self) -> Point3d {
+        Point3d::new(-self.x, -self.y, -self.z)
+    }
+}
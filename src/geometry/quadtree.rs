@@ -0,0 +1,333 @@
+use std::mem;
+
+use super::hit::rect_contains;
+use super::{Point, Rect};
+
+// A spatial index of items by their bounding boxes, so that finding what's
+// at a point or in an area looks at the items near it rather than all of
+// them.
+//
+// Each node covers a square and, once it holds too many items, splits it
+// into four quarters. An item is kept in the smallest node whose square
+// contains its whole box. The root grows outwards as items are inserted
+// beyond it, so there's no need to know the extent of the canvas up front.
+// Boxes with a NaN or infinite coordinate can't be placed in a square; they
+// are kept to one side and checked by every query.
+//
+// Items are found by equality, so each should be inserted once, like an id.
+#[derive(Clone, Debug)]
+pub struct QuadTree<T> {
+    root: Option<Node<T>>,
+    unbounded: Vec<(Rect, T)>,
+    len: usize
+}
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    bounds: Rect,
+    // In a leaf, all of its items; otherwise those that don't fit in any
+    // one quarter.
+    items: Vec<(Rect, T)>,
+    // The top-left, top-right, bottom-left and bottom-right quarters.
+    children: Option<Box<[Node<T>; 4]>>
+}
+
+// How many items a leaf holds before it splits, and how many times a square
+// can be split, which stops items piled on one spot splitting it forever.
+const MAX_ITEMS: usize = 8;
+const MAX_DEPTH: usize = 24;
+
+impl<T> Default for QuadTree<T> {
+    fn default() -> QuadTree<T> {
+        QuadTree { root: None, unbounded: Vec::new(), len: 0 }
+    }
+}
+
+impl<T: PartialEq> QuadTree<T> {
+    pub fn new() -> QuadTree<T> {
+        QuadTree::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, rect: Rect, item: T) {
+        self.len += 1;
+        if !is_finite(&rect) {
+            self.unbounded.push((rect, item));
+            return;
+        }
+        let root = self.root.get_or_insert_with(|| {
+            // Far from the origin, a side of 1 could be lost to rounding.
+            let magnitude = rect.top_left.x.abs().max(rect.top_left.y.abs());
+            let side = rect.width().max(rect.height()).max(1.0).max(4.0 * f64::EPSILON * magnitude);
+            Node::new(Rect::new(rect.top_left, rect.top_left + Point::new(side, side)))
+        });
+        while !rect_encloses(&root.bounds, &rect) {
+            let width = root.bounds.width();
+            root.grow_towards(&rect);
+            // A root too big to double again keeps whatever it can't
+            // enclose itself, where every query looks.
+            if root.bounds.width().is_nan() || root.bounds.width() <= width {
+                break;
+            }
+        }
+        root.insert(rect, item, 0);
+    }
+
+    // Takes `item` out of the index, given the box it was inserted with.
+    // Returns false if it isn't there.
+    pub fn remove(&mut self, rect: &Rect, item: &T) -> bool {
+        let removed = if !is_finite(rect) {
+            remove_item(&mut self.unbounded, item)
+        } else {
+            self.root.as_mut().is_some_and(|root| root.remove(rect, item))
+        };
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    // The items whose boxes contain `p`, edges included, in no particular
+    // order.
+    pub fn query_point(&self, p: Point) -> Vec<&T> {
+        self.query_rect(&Rect::new(p, p))
+    }
+
+    // The items whose boxes overlap `rect` or touch its edges, in no
+    // particular order.
+    pub fn query_rect(&self, rect: &Rect) -> Vec<&T> {
+        let mut found: Vec<&T> = self.unbounded.iter()
+            .filter(|(bounds, _)| overlaps(bounds, rect))
+            .map(|(_, item)| item)
+            .collect();
+        if let Some(ref root) = self.root {
+            root.query(rect, &mut found);
+        }
+        found
+    }
+}
+
+impl<T: PartialEq> Node<T> {
+    fn new(bounds: Rect) -> Node<T> {
+        Node { bounds, items: Vec::new(), children: None }
+    }
+
+    fn insert(&mut self, rect: Rect, item: T, depth: usize) {
+        if let Some(ref mut children) = self.children {
+            match children.iter_mut().find(|child| rect_encloses(&child.bounds, &rect)) {
+                Some(child) => child.insert(rect, item, depth + 1),
+                None => self.items.push((rect, item))
+            }
+            return;
+        }
+        self.items.push((rect, item));
+        if self.items.len() > MAX_ITEMS && depth < MAX_DEPTH {
+            self.children = Some(Box::new(quarters(&self.bounds).map(Node::new)));
+            for (rect, item) in mem::take(&mut self.items) {
+                self.insert(rect, item, depth);
+            }
+        }
+    }
+
+    // Quarters share their edges, so a box lying along one fits in more
+    // than one quarter, and which it went into can change as the tree
+    // grows. Every quarter it fits in is searched.
+    fn remove(&mut self, rect: &Rect, item: &T) -> bool {
+        if remove_item(&mut self.items, item) {
+            return true;
+        }
+        match self.children {
+            Some(ref mut children) =>
+                children.iter_mut()
+                    .any(|child| rect_encloses(&child.bounds, rect) && child.remove(rect, item)),
+            None =>
+                false
+        }
+    }
+
+    fn query<'a>(&'a self, rect: &Rect, found: &mut Vec<&'a T>) {
+        found.extend(self.items.iter()
+                     .filter(|(bounds, _)| overlaps(bounds, rect))
+                     .map(|(_, item)| item));
+        if let Some(ref children) = self.children {
+            for child in children.iter() {
+                if overlaps(&child.bounds, rect) {
+                    child.query(rect, found);
+                }
+            }
+        }
+    }
+
+    // Doubles the square towards `rect`, making the node as it was one
+    // quarter of the new one.
+    fn grow_towards(&mut self, rect: &Rect) {
+        let side = self.bounds.width();
+        let left = rect.top_left.x < self.bounds.top_left.x;
+        let up = rect.top_left.y < self.bounds.top_left.y;
+        let top_left = self.bounds.top_left
+            - Point::new(if left { side } else { 0.0 }, if up { side } else { 0.0 });
+        let bounds = Rect::new(top_left, top_left + Point::new(2.0 * side, 2.0 * side));
+
+        let mut children = quarters(&bounds).map(Node::new);
+        // Growing left or up leaves the old square on the right or bottom.
+        let old = left as usize | (up as usize) << 1;
+        children[old] = mem::replace(self, Node::new(bounds));
+        self.children = Some(Box::new(children));
+    }
+}
+
+fn quarters(bounds: &Rect) -> [Rect; 4] {
+    let Rect { top_left, bottom_right } = *bounds;
+    let middle = Point::new((top_left.x + bottom_right.x) / 2.0, (top_left.y + bottom_right.y) / 2.0);
+    [Rect::new(top_left, middle),
+     Rect::new(Point::new(middle.x, top_left.y), Point::new(bottom_right.x, middle.y)),
+     Rect::new(Point::new(top_left.x, middle.y), Point::new(middle.x, bottom_right.y)),
+     Rect::new(middle, bottom_right)]
+}
+
+fn remove_item<T: PartialEq>(items: &mut Vec<(Rect, T)>, item: &T) -> bool {
+    match items.iter().position(|(_, other)| other == item) {
+        Some(index) => {
+            items.swap_remove(index);
+            true
+        }
+        None => false
+    }
+}
+
+fn is_finite(rect: &Rect) -> bool {
+    rect.top_left.x.is_finite() && rect.top_left.y.is_finite()
+        && rect.bottom_right.x.is_finite() && rect.bottom_right.y.is_finite()
+}
+
+// Whether `inner` lies wholly inside `outer`, edges included.
+fn rect_encloses(outer: &Rect, inner: &Rect) -> bool {
+    rect_contains(outer, inner.top_left) && rect_contains(outer, inner.bottom_right)
+}
+
+// Whether two rectangles share any point, edges included.
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.top_left.x <= b.bottom_right.x && b.top_left.x <= a.bottom_right.x
+        && a.top_left.y <= b.bottom_right.y && b.top_left.y <= a.bottom_right.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Rect {
+        Rect::new(Point::new(x0, y0), Point::new(x1, y1))
+    }
+
+    fn sorted(mut found: Vec<&u32>) -> Vec<u32> {
+        found.sort();
+        found.into_iter().cloned().collect()
+    }
+
+    // A tree over the square from (0, 0) to (16, 16), split once by a unit
+    // square in each corner and more in the top-left quarter.
+    fn split_tree() -> QuadTree<u32> {
+        let mut tree = QuadTree::new();
+        tree.insert(rect(0.0, 0.0, 16.0, 16.0), 0);
+        let corners = [(0.0, 0.0), (15.0, 0.0), (0.0, 15.0), (15.0, 15.0)];
+        for (i, &(x, y)) in corners.iter().chain(&[(1.0, 1.0); 6]).enumerate() {
+            tree.insert(rect(x, y, x + 1.0, y + 1.0), i as u32 + 1);
+        }
+        assert!(tree.root.as_ref().unwrap().children.is_some());
+        tree
+    }
+
+    #[test]
+    fn insert_and_query() {
+        let tree = split_tree();
+        assert_eq!(tree.len(), 11);
+        assert_eq!(sorted(tree.query_point(Point::new(15.5, 0.5))), [0, 2]);
+        assert_eq!(sorted(tree.query_point(Point::new(1.0, 1.0))), [0, 1, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(sorted(tree.query_rect(&rect(14.0, 14.0, 20.0, 20.0))), [0, 4]);
+        assert!(sorted(tree.query_rect(&rect(20.0, 20.0, 30.0, 30.0))).is_empty());
+    }
+
+    #[test]
+    fn remove_takes_out_one_item() {
+        let mut tree = split_tree();
+        assert!(tree.remove(&rect(15.0, 0.0, 16.0, 1.0), &2));
+        assert!(!tree.remove(&rect(15.0, 0.0, 16.0, 1.0), &2));
+        assert_eq!(tree.len(), 10);
+        assert_eq!(sorted(tree.query_point(Point::new(15.5, 0.5))), [0]);
+        assert!(!tree.remove(&rect(0.0, 0.0, 1.0, 1.0), &99));
+    }
+
+    // Boxes lying along the lines between quarters, or on the point where
+    // they meet, touch two or four quarters at once.
+    #[test]
+    fn boxes_on_a_quarter_boundary() {
+        let mut tree = split_tree();
+        let on_lines = [(rect(8.0, 2.0, 8.0, 6.0), 20),
+                        (rect(2.0, 8.0, 6.0, 8.0), 21),
+                        (rect(8.0, 8.0, 8.0, 8.0), 22),
+                        (rect(7.0, 7.0, 9.0, 9.0), 23)];
+        for &(bounds, id) in &on_lines {
+            tree.insert(bounds, id);
+        }
+        assert_eq!(sorted(tree.query_point(Point::new(8.0, 8.0))), [0, 22, 23]);
+        assert_eq!(sorted(tree.query_point(Point::new(8.0, 4.0))), [0, 20]);
+        assert_eq!(sorted(tree.query_rect(&rect(3.0, 8.0, 3.0, 12.0))), [0, 21]);
+        for &(bounds, id) in &on_lines {
+            assert!(tree.remove(&bounds, &id), "{}", id);
+        }
+        assert_eq!(tree.len(), 11);
+        assert_eq!(sorted(tree.query_point(Point::new(8.0, 8.0))), [0]);
+    }
+
+    // The root doubles towards boxes beyond it, in any direction, keeping
+    // what it held; boxes on its old edges can still be found and removed.
+    #[test]
+    fn the_root_grows_to_take_in_far_boxes() {
+        let mut tree = split_tree();
+        tree.insert(rect(-100.0, -50.0, -99.0, -49.0), 30);
+        tree.insert(rect(1000.0, 2000.0, 1001.0, 2001.0), 31);
+        tree.insert(rect(16.0, 0.0, 16.0, 16.0), 32);
+        let root = tree.root.as_ref().unwrap();
+        assert!(rect_encloses(&root.bounds, &rect(-100.0, -50.0, 1001.0, 2001.0)));
+        assert_eq!(root.bounds.width(), root.bounds.height());
+
+        assert_eq!(sorted(tree.query_point(Point::new(-99.5, -49.5))), [30]);
+        assert_eq!(sorted(tree.query_point(Point::new(1000.5, 2000.5))), [31]);
+        assert_eq!(sorted(tree.query_point(Point::new(15.5, 15.5))), [0, 4]);
+        assert_eq!(sorted(tree.query_point(Point::new(16.0, 8.0))), [0, 32]);
+        assert!(tree.remove(&rect(16.0, 0.0, 16.0, 16.0), &32));
+        assert!(tree.remove(&rect(0.0, 0.0, 16.0, 16.0), &0));
+        assert!(tree.remove(&rect(15.0, 15.0, 16.0, 16.0), &4));
+        assert_eq!(tree.len(), 11);
+    }
+
+    #[test]
+    fn boxes_that_cant_be_placed_are_always_checked() {
+        let mut tree = split_tree();
+        tree.insert(rect(f64::NEG_INFINITY, 0.0, 0.0, 1.0), 40);
+        tree.insert(rect(f64::NAN, 0.0, 0.0, 1.0), 41);
+        assert_eq!(tree.len(), 13);
+        assert_eq!(sorted(tree.query_point(Point::new(-1e300, 0.5))), [40]);
+        assert!(tree.remove(&rect(f64::NEG_INFINITY, 0.0, 0.0, 1.0), &40));
+        assert!(tree.remove(&rect(f64::NAN, 0.0, 0.0, 1.0), &41));
+        assert_eq!(tree.len(), 11);
+    }
+
+    #[test]
+    fn far_from_the_origin() {
+        let mut tree = QuadTree::new();
+        for i in 0..20 {
+            let x = 1e300 + f64::from(i) * 1e285;
+            tree.insert(rect(x, 1e300, x, 1e300), i);
+        }
+        assert_eq!(tree.len(), 20);
+        assert_eq!(tree.query_rect(&rect(1e300, 1e300, 2e300, 2e300)).len(), 20);
+    }
+}
@@ -0,0 +1,39 @@
+use super::Point;
+
+// An axis-aligned rectangle, given by its top-left and bottom-right corners.
+// Rect::new() puts the corners in that order whichever way round they're
+// given; building one directly with them the wrong way round makes a
+// rectangle with negative width or height, which nothing here expects.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub top_left: Point,
+    pub bottom_right: Point
+}
+
+impl Rect {
+    // The rectangle with `a` and `b` at opposite corners.
+    pub fn new(a: Point, b: Point) -> Rect {
+        Rect {
+            top_left: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            bottom_right: Point::new(a.x.max(b.x), a.y.max(b.y))
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.bottom_right.x - self.top_left.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.bottom_right.y - self.top_left.y
+    }
+
+    // The corners in order around the rectangle, clockwise on screen from
+    // the top left.
+    pub fn corners(&self) -> [Point; 4] {
+        let Rect { top_left, bottom_right } = *self;
+        [top_left,
+         Point::new(bottom_right.x, top_left.y),
+         bottom_right,
+         Point::new(top_left.x, bottom_right.y)]
+    }
+}
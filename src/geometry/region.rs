@@ -0,0 +1,190 @@
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+use std::fmt;
+
+use super::{Point, Point3d};
+
+// The chapter's describe_point(), which sorts a point by the signs of its
+// coordinates:
+//
+//     match (x.cmp(&0), y.cmp(&0)) {
+//         (Equal, Equal) => "at the origin",
+//         (_, Equal) => "on the x axis",
+//         (Equal, _) => "on the y axis",
+//         (Greater, Greater) => "in the first quadrant",
+//         (Less, Greater) => "in the second quadrant",
+//         _ => "somewhere else"
+//     }
+//
+// classify() and classify3d() do the same, returning which region it is
+// rather than a phrase, so callers can match on it, and covering the
+// quadrants it lumps together as "somewhere else". Each region displays as
+// the phrase.
+//
+// Quadrants and octants are numbered the usual mathematical way, by sign:
+// the first quadrant has x and y both positive, and the numbers go
+// anticlockwise from there. With y growing downwards on screen, that puts
+// the first quadrant at the bottom right.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z
+}
+
+impl Axis {
+    pub fn name(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z"
+        }
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+// A plane through two of the axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Plane {
+    XY,
+    XZ,
+    YZ
+}
+
+impl Plane {
+    pub fn name(self) -> &'static str {
+        match self {
+            Plane::XY => "xy",
+            Plane::XZ => "xz",
+            Plane::YZ => "yz"
+        }
+    }
+}
+
+impl fmt::Display for Plane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+// Where a point in the plane is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Region {
+    Origin,
+    OnAxis(Axis),
+    // The quadrant, from 1 to 4.
+    Quadrant(u8)
+}
+
+// Where a point in space is. OnAxis is only for points on an axis but not at
+// the origin, and OnPlane only for those on a plane but on neither of its
+// axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Region3d {
+    Origin,
+    OnAxis(Axis),
+    OnPlane(Plane),
+    // The octant, from 1 to 8: 1 to 4 are the quadrants of the xy plane
+    // with z positive, and 5 to 8 the same with z negative.
+    Octant(u8)
+}
+
+pub fn classify(p: Point) -> Region {
+    classify_within(p, 0.0)
+}
+
+// classify(), counting any coordinate within `epsilon` of zero as zero, so
+// a point worked out as (1e-17, 2.0) is still on the y axis.
+pub fn classify_within(p: Point, epsilon: f64) -> Region {
+    let sign = |v| sign(v, epsilon);
+    match (sign(p.x), sign(p.y)) {
+        (Equal, Equal) => Region::Origin,
+        (_, Equal) => Region::OnAxis(Axis::X),
+        (Equal, _) => Region::OnAxis(Axis::Y),
+        (x, y) => Region::Quadrant(quadrant(x, y))
+    }
+}
+
+pub fn classify3d(p: Point3d) -> Region3d {
+    classify3d_within(p, 0.0)
+}
+
+// classify3d(), counting any coordinate within `epsilon` of zero as zero.
+pub fn classify3d_within(p: Point3d, epsilon: f64) -> Region3d {
+    let sign = |v| sign(v, epsilon);
+    match (sign(p.x), sign(p.y), sign(p.z)) {
+        (Equal, Equal, Equal) => Region3d::Origin,
+        (_, Equal, Equal) => Region3d::OnAxis(Axis::X),
+        (Equal, _, Equal) => Region3d::OnAxis(Axis::Y),
+        (Equal, Equal, _) => Region3d::OnAxis(Axis::Z),
+        (_, _, Equal) => Region3d::OnPlane(Plane::XY),
+        (_, Equal, _) => Region3d::OnPlane(Plane::XZ),
+        (Equal, _, _) => Region3d::OnPlane(Plane::YZ),
+        (x, y, Greater) => Region3d::Octant(quadrant(x, y)),
+        (x, y, _) => Region3d::Octant(quadrant(x, y) + 4)
+    }
+}
+
+// Which side of zero `v` is on, with anything within `epsilon` of it as
+// Equal. Zero of either sign is Equal, and so is NaN, so a point with an
+// unknown coordinate is put on the axis or plane rather than on an
+// arbitrary side of it.
+fn sign(v: f64, epsilon: f64) -> Ordering {
+    if v > epsilon {
+        Greater
+    } else if v < -epsilon {
+        Less
+    } else {
+        Equal
+    }
+}
+
+// The quadrant for the signs of x and y, neither of them zero.
+fn quadrant(x: Ordering, y: Ordering) -> u8 {
+    match (x, y) {
+        (Greater, Greater) => 1,
+        (Less, Greater) => 2,
+        (Less, Less) => 3,
+        _ => 4
+    }
+}
+
+fn ordinal(n: u8) -> &'static str {
+    ["first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth"]
+        .get(usize::from(n).wrapping_sub(1))
+        .cloned()
+        .unwrap_or("unknown")
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Region::Origin =>
+                f.write_str("at the origin"),
+            Region::OnAxis(axis) =>
+                write!(f, "on the {} axis", axis),
+            Region::Quadrant(n) =>
+                write!(f, "in the {} quadrant", ordinal(n))
+        }
+    }
+}
+
+impl fmt::Display for Region3d {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Region3d::Origin =>
+                f.write_str("at the origin"),
+            Region3d::OnAxis(axis) =>
+                write!(f, "on the {} axis", axis),
+            Region3d::OnPlane(plane) =>
+                write!(f, "on the {} plane", plane),
+            Region3d::Octant(n) =>
+                write!(f, "in the {} octant", ordinal(n))
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use super::{Point, Rect, Shape, ShapeKind, TextSelection};
+
+// What the user has selected: some text, or the area inside a shape. A
+// trapezoid selects as the polygon through its corners, so painting code
+// has one fewer case to handle.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Selection {
+    Text(TextSelection),
+    Rect(Rect),
+    Ellipse {
+        center: Point,
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64
+    },
+    Polygon(Vec<Point>)
+}
+
+// The error returned when a shape can't be a selection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectionError {
+    // A kind of shape that has no one area to select: a line, which
+    // encloses nothing, or a group, whose shapes are selected as shapes
+    // rather than as a region.
+    Unsupported(ShapeKind),
+    // A polygon with fewer than three points, which encloses nothing either.
+    Degenerate(Shape)
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SelectionError::Unsupported(kind) =>
+                write!(f, "a {} can't be selected", kind),
+            SelectionError::Degenerate(ref shape) =>
+                write!(f, "a {} with no area can't be selected", shape.kind())
+        }
+    }
+}
+
+impl core::error::Error for SelectionError {}
+
+impl TryFrom<Shape> for Selection {
+    type Error = SelectionError;
+
+    fn try_from(shape: Shape) -> Result<Selection, SelectionError> {
+        match shape {
+            Shape::Rect(rect) =>
+                Ok(Selection::Rect(rect)),
+            Shape::Ellipse { center, radius_x, radius_y, rotation } =>
+                Ok(Selection::Ellipse { center, radius_x, radius_y, rotation }),
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                Ok(Selection::Polygon(vec![top_left, top_right, bottom_right, bottom_left])),
+            Shape::TextSpan(start, end) =>
+                Ok(Selection::Text(TextSelection::from(start..end))),
+            Shape::Polygon(points) => {
+                if points.len() < 3 {
+                    return Err(SelectionError::Degenerate(Shape::Polygon(points)));
+                }
+                Ok(Selection::Polygon(points))
+            }
+            Shape::Line(..) | Shape::Group(_) =>
+                Err(SelectionError::Unsupported(shape.kind()))
+        }
+    }
+}
+
+impl Selection {
+    // The selection as shapes, for hit testing, bounding boxes and drawing:
+    // a text span for each range of selected text, or the one shape of the
+    // selected area.
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        match *self {
+            Selection::Text(ref text) =>
+                text.ranges().iter().map(|range| Shape::TextSpan(range.start, range.end)).collect(),
+            Selection::Rect(rect) =>
+                vec![Shape::Rect(rect)],
+            Selection::Ellipse { center, radius_x, radius_y, rotation } =>
+                vec![Shape::Ellipse { center, radius_x, radius_y, rotation }],
+            Selection::Polygon(ref points) =>
+                vec![Shape::Polygon(points.clone())]
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::{Point, Rect, Shape};
+
+// A point is serialized as the pair [x, y].
+impl Serialize for Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.x, self.y).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Point, D::Error> {
+        let (x, y) = <(f64, f64)>::deserialize(deserializer)?;
+        Ok(Point::new(x, y))
+    }
+}
+
+// A shape is serialized as a flat object tagged with its type, using the
+// attribute names of the SVG element it would be, so in JSON
+//
+//     {"type": "rect", "x": 0, "y": 0, "width": 4, "height": 3}
+//     {"type": "ellipse", "cx": 6, "cy": 6, "rx": 2, "ry": 1, "rotation": 0.5}
+//     {"type": "trapezoid", "points": [[1, 0], [3, 0], [4, 2], [0, 2]]}
+//     {"type": "text-span", "start": 3, "end": 8}
+//     {"type": "polygon", "points": [[0, 0], [4, 0], [2, 3]]}
+//     {"type": "line", "x1": -1, "y1": 0, "x2": 3, "y2": 9}
+//     {"type": "group", "shapes": [{"type": "line", "x2": 1, "y2": 1}]}
+//
+// An ellipse's rotation, in radians, may be left out when it's zero, as may
+// any coordinate that SVG defaults to zero. Sizes and radii can't be
+// negative. Errors name the field at fault;
+// where in the file it is comes from the format, as serde_json's line and
+// column.
+
+impl Serialize for Shape {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match *self {
+            Shape::Rect(rect) => {
+                map.serialize_entry("type", "rect")?;
+                map.serialize_entry("x", &rect.top_left.x)?;
+                map.serialize_entry("y", &rect.top_left.y)?;
+                map.serialize_entry("width", &rect.width())?;
+                map.serialize_entry("height", &rect.height())?;
+            }
+            Shape::Ellipse { center, radius_x, radius_y, rotation } => {
+                map.serialize_entry("type", "ellipse")?;
+                map.serialize_entry("cx", &center.x)?;
+                map.serialize_entry("cy", &center.y)?;
+                map.serialize_entry("rx", &radius_x)?;
+                map.serialize_entry("ry", &radius_y)?;
+                if rotation != 0.0 {
+                    map.serialize_entry("rotation", &rotation)?;
+                }
+            }
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } => {
+                map.serialize_entry("type", "trapezoid")?;
+                map.serialize_entry("points", &[top_left, top_right, bottom_right, bottom_left])?;
+            }
+            Shape::TextSpan(start, end) => {
+                map.serialize_entry("type", "text-span")?;
+                map.serialize_entry("start", &start)?;
+                map.serialize_entry("end", &end)?;
+            }
+            Shape::Polygon(ref points) => {
+                map.serialize_entry("type", "polygon")?;
+                map.serialize_entry("points", points)?;
+            }
+            Shape::Line(a, b) => {
+                map.serialize_entry("type", "line")?;
+                map.serialize_entry("x1", &a.x)?;
+                map.serialize_entry("y1", &a.y)?;
+                map.serialize_entry("x2", &b.x)?;
+                map.serialize_entry("y2", &b.y)?;
+            }
+            Shape::Group(ref shapes) => {
+                map.serialize_entry("type", "group")?;
+                map.serialize_entry("shapes", shapes)?;
+            }
+        }
+        map.end()
+    }
+}
+
+const TYPES: &[&str] = &["rect", "ellipse", "trapezoid", "text-span", "polygon", "line", "group"];
+
+const NUMBER_FIELDS: &[&str] =
+    &["x", "y", "width", "height", "cx", "cy", "rx", "ry", "rotation", "x1", "y1", "x2", "y2"];
+
+const FIELDS: &[&str] = &["type", "x", "y", "width", "height", "cx", "cy", "rx", "ry", "rotation",
+                          "x1", "y1", "x2", "y2", "start", "end", "points", "shapes"];
+
+// Every field any type of shape has, collected before the type is known,
+// since "type" needn't come first.
+#[derive(Default)]
+struct Fields {
+    kind: Option<String>,
+    numbers: [Option<f64>; NUMBER_FIELDS.len()],
+    start: Option<usize>,
+    end: Option<usize>,
+    points: Option<Vec<Point>>,
+    shapes: Option<Vec<Shape>>
+}
+
+impl Fields {
+    fn number<E: de::Error>(&self, name: &'static str, default: Option<f64>) -> Result<f64, E> {
+        let index = NUMBER_FIELDS.iter().position(|&field| field == name)
+            .expect("not a number field");
+        self.numbers[index].or(default).ok_or_else(|| E::missing_field(name))
+    }
+
+    // A number that can't be negative, as a rect's width and height and an
+    // ellipse's radii can't be in SVG either.
+    fn length<E: de::Error>(&self, name: &'static str) -> Result<f64, E> {
+        let length = self.number(name, None)?;
+        if length < 0.0 {
+            let unexpected = de::Unexpected::Float(length);
+            return Err(E::invalid_value(unexpected, &"a length that isn't negative"));
+        }
+        Ok(length)
+    }
+
+    fn into_shape<E: de::Error>(self) -> Result<Shape, E> {
+        let kind = self.kind.as_deref().ok_or_else(|| E::missing_field("type"))?;
+        Ok(match kind {
+            "rect" => {
+                let (x, y) = (self.number("x", Some(0.0))?, self.number("y", Some(0.0))?);
+                let (width, height) = (self.length("width")?, self.length("height")?);
+                Shape::Rect(Rect::new(Point::new(x, y), Point::new(x + width, y + height)))
+            }
+            "ellipse" =>
+                Shape::Ellipse {
+                    center: Point::new(self.number("cx", Some(0.0))?, self.number("cy", Some(0.0))?),
+                    radius_x: self.length("rx")?,
+                    radius_y: self.length("ry")?,
+                    rotation: self.number("rotation", Some(0.0))?
+                },
+            "trapezoid" => match *self.points.ok_or_else(|| E::missing_field("points"))? {
+                [top_left, top_right, bottom_right, bottom_left] =>
+                    Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left },
+                ref points =>
+                    return Err(E::invalid_length(points.len(), &"the 4 corners of a trapezoid"))
+            },
+            "text-span" =>
+                Shape::TextSpan(self.start.ok_or_else(|| E::missing_field("start"))?,
+                                self.end.ok_or_else(|| E::missing_field("end"))?),
+            "polygon" =>
+                Shape::Polygon(self.points.ok_or_else(|| E::missing_field("points"))?),
+            "line" =>
+                Shape::Line(Point::new(self.number("x1", Some(0.0))?, self.number("y1", Some(0.0))?),
+                            Point::new(self.number("x2", Some(0.0))?, self.number("y2", Some(0.0))?)),
+            "group" =>
+                Shape::Group(self.shapes.ok_or_else(|| E::missing_field("shapes"))?),
+            other =>
+                return Err(E::unknown_variant(other, TYPES))
+        })
+    }
+}
+
+struct ShapeVisitor;
+
+impl<'de> Visitor<'de> for ShapeVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a shape object with a \"type\" field")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Shape, A::Error> {
+        let mut fields = Fields::default();
+        while let Some(key) = map.next_key::<String>()? {
+            let duplicate = match key.as_str() {
+                "type" => fields.kind.replace(map.next_value()?).is_some(),
+                "start" => fields.start.replace(map.next_value()?).is_some(),
+                "end" => fields.end.replace(map.next_value()?).is_some(),
+                "points" => fields.points.replace(map.next_value()?).is_some(),
+                "shapes" => fields.shapes.replace(map.next_value()?).is_some(),
+                name => match NUMBER_FIELDS.iter().position(|&field| field == name) {
+                    Some(index) => fields.numbers[index].replace(map.next_value()?).is_some(),
+                    None => return Err(de::Error::unknown_field(name, FIELDS))
+                }
+            };
+            if duplicate {
+                return Err(de::Error::custom(format_args!("duplicate field `{}`", key)));
+            }
+        }
+        fields.into_shape()
+    }
+}
+
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Shape, D::Error> {
+        deserializer.deserialize_map(ShapeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str) -> Result<Shape, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn shapes() -> Vec<Shape> {
+        let p = Point::new;
+        vec![Shape::Rect(Rect::new(p(1.0, 2.0), p(5.0, 5.0))),
+             Shape::Ellipse { center: p(6.0, 6.0), radius_x: 2.0, radius_y: 1.0, rotation: 0.5 },
+             Shape::Ellipse { center: p(0.0, 0.0), radius_x: 2.0, radius_y: 1.0, rotation: 0.0 },
+             Shape::Trapezoid {
+                 top_left: p(1.0, 0.0),
+                 top_right: p(3.0, 0.0),
+                 bottom_right: p(4.0, 2.0),
+                 bottom_left: p(0.0, 2.0)
+             },
+             Shape::TextSpan(3, 8),
+             Shape::Polygon(vec![p(0.0, 0.0), p(4.0, 0.0), p(2.0, 3.0)]),
+             Shape::Line(p(-1.0, 0.0), p(3.0, 9.0)),
+             Shape::Group(vec![Shape::Line(p(0.0, 0.0), p(1.0, 1.0)), Shape::TextSpan(0, 1)])]
+    }
+
+    #[test]
+    fn every_shape_round_trips() {
+        for shape in shapes() {
+            let json = serde_json::to_string(&shape).unwrap();
+            assert_eq!(from_json(&json).unwrap(), shape, "{}", json);
+        }
+    }
+
+    #[test]
+    fn fields_are_named_as_in_svg() {
+        assert_eq!(serde_json::to_string(&shapes()[0]).unwrap(),
+                   r#"{"type":"rect","x":1.0,"y":2.0,"width":4.0,"height":3.0}"#);
+        assert_eq!(serde_json::to_string(&shapes()[2]).unwrap(),
+                   r#"{"type":"ellipse","cx":0.0,"cy":0.0,"rx":2.0,"ry":1.0}"#);
+        // Coordinates SVG defaults to zero can be left out, and "type" needn't
+        // come first.
+        assert_eq!(from_json(r#"{"width":4,"height":3,"type":"rect"}"#).unwrap(),
+                   Shape::Rect(Rect::new(Point::ORIGIN, Point::new(4.0, 3.0))));
+    }
+
+    #[test]
+    fn missing_fields_are_named() {
+        for (json, field) in [(r#"{"x":1,"width":4,"height":3}"#, "type"),
+                              (r#"{"type":"rect","x":1,"height":3}"#, "width"),
+                              (r#"{"type":"ellipse","rx":1}"#, "ry"),
+                              (r#"{"type":"text-span","start":1}"#, "end"),
+                              (r#"{"type":"polygon"}"#, "points"),
+                              (r#"{"type":"group"}"#, "shapes")] {
+            let error = from_json(json).unwrap_err().to_string();
+            let expected = format!("missing field `{}`", field);
+            assert!(error.starts_with(&expected), "{}: {}", json, error);
+        }
+    }
+
+    #[test]
+    fn other_mistakes_are_errors() {
+        for (json, expected) in [(r#"{"type":"circle","cx":1}"#, "unknown variant `circle`"),
+                                 (r#"{"type":"rect","width":4,"height":3,"r":1}"#,
+                                  "unknown field `r`"),
+                                 (r#"{"type":"rect","x":1,"x":2,"width":4,"height":3}"#,
+                                  "duplicate field `x`"),
+                                 (r#"{"type":"trapezoid","points":[[0,0],[1,0],[1,1]]}"#,
+                                  "invalid length 3")] {
+            let error = from_json(json).unwrap_err().to_string();
+            assert!(error.starts_with(expected), "{}: {}", json, error);
+        }
+    }
+
+    // Rect::new() would quietly turn a negative size around, moving the rect.
+    #[test]
+    fn negative_sizes_and_radii_are_rejected() {
+        for json in [r#"{"type":"rect","x":5,"width":-3,"height":4}"#,
+                     r#"{"type":"rect","width":3,"height":-0.5}"#,
+                     r#"{"type":"ellipse","rx":-1,"ry":1}"#,
+                     r#"{"type":"ellipse","rx":1,"ry":-1}"#] {
+            let error = from_json(json).unwrap_err().to_string();
+            assert!(error.starts_with("invalid value: floating point"), "{}: {}", json, error);
+            assert!(error.contains("expected a length that isn't negative"), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{Point, Rect, Shape};
+
+// How interactive edits line shapes up: positions to a grid of squares
+// `grid` units across, and turns to multiples of `angle` radians. Either is
+// off while it's None, as both are by default.
+//
+//     let snap = Snap::new().with_grid(10.0).with_angle(PI / 12.0);
+//     assert_eq!(snap.point(Point::new(13.0, 27.0)), Point::new(10.0, 30.0));
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Snap {
+    pub grid: Option<f64>,
+    pub angle: Option<f64>
+}
+
+impl Snap {
+    // Snapping that leaves everything where it is.
+    pub fn new() -> Snap {
+        Snap::default()
+    }
+
+    pub fn with_grid(self, grid: f64) -> Snap {
+        Snap { grid: Some(grid), ..self }
+    }
+
+    pub fn with_angle(self, angle: f64) -> Snap {
+        Snap { angle: Some(angle), ..self }
+    }
+
+    pub fn point(&self, p: Point) -> Point {
+        self.grid.map_or(p, |grid| snap_point(p, grid))
+    }
+
+    pub fn rotation(&self, angle: f64) -> f64 {
+        self.angle.map_or(angle, |step| snap_angle(angle, step))
+    }
+
+    // The shape with every point that defines it on the grid, as
+    // snap_shape() does, and an ellipse's rotation snapped too.
+    pub fn shape(&self, shape: &Shape) -> Shape {
+        let point = |p: Point| self.point(p);
+        let length = |v: f64| self.grid.map_or(v, |grid| snap_to(v, grid));
+        match *shape {
+            Shape::Rect(rect) =>
+                Shape::Rect(Rect::new(point(rect.top_left), point(rect.bottom_right))),
+            Shape::Ellipse { center, radius_x, radius_y, rotation } =>
+                Shape::Ellipse {
+                    center: point(center),
+                    radius_x: length(radius_x),
+                    radius_y: length(radius_y),
+                    rotation: self.rotation(rotation)
+                },
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                Shape::Trapezoid {
+                    top_left: point(top_left),
+                    top_right: point(top_right),
+                    bottom_right: point(bottom_right),
+                    bottom_left: point(bottom_left)
+                },
+            Shape::TextSpan(start, end) =>
+                Shape::TextSpan(start, end),
+            Shape::Polygon(ref points) =>
+                Shape::Polygon(points.iter().map(|&p| point(p)).collect()),
+            Shape::Line(a, b) =>
+                Shape::Line(point(a), point(b)),
+            Shape::Group(ref shapes) =>
+                Shape::Group(shapes.iter().map(|shape| self.shape(shape)).collect())
+        }
+    }
+}
+
+// The grid point nearest `p`, on a grid of squares `grid` units across with
+// a corner at the origin. A grid that isn't a positive, finite size leaves
+// `p` where it is.
+pub fn snap_point(p: Point, grid: f64) -> Point {
+    Point::new(snap_to(p.x, grid), snap_to(p.y, grid))
+}
+
+// The multiple of `step` nearest `angle`, or `angle` itself if `step` isn't
+// a positive, finite angle.
+pub fn snap_angle(angle: f64, step: f64) -> f64 {
+    snap_to(angle, step)
+}
+
+// The shape with every point that defines it snapped to the grid: a
+// rectangle's corners, an ellipse's center and radii, a polygon's or
+// trapezoid's vertices, a line's ends, and every shape in a group. Text
+// spans aren't placed on the canvas, so they're left alone. Sides and radii
+// shorter than half the grid can snap to nothing.
+pub fn snap_shape(shape: &Shape, grid: f64) -> Shape {
+    Snap::new().with_grid(grid).shape(shape)
+}
+
+fn snap_to(v: f64, step: f64) -> f64 {
+    if step > 0.0 && step.is_finite() {
+        (v / step).round() * step
+    } else {
+        v
+    }
+}
@@ -0,0 +1,211 @@
+// SVG output, for looking at shapes while debugging and for rendering
+// previews on a server. Only geometry is written; shapes are drawn as black
+// outlines and the selection as a translucent blue highlight over them.
+
+use std::fmt::{self, Write};
+
+use super::bounds::enclosing;
+use super::{Document, Point, Rect, Selection, Shape};
+
+const SHAPE_STYLE: &str = r#"fill="none" stroke="black""#;
+const SELECTION_STYLE: &str = concat!(
+    r#"class="selection" fill="royalblue" fill-opacity="0.25" "#,
+    r#"stroke="royalblue" stroke-dasharray="4 2""#
+);
+
+impl Shape {
+    // The shape as an SVG element, such as
+    // <rect x="0" y="0" width="4" height="3" fill="none" stroke="black"/>.
+    // A text span has no geometry of its own, so it's an empty group that
+    // records its offsets in data-start and data-end attributes. A group of
+    // shapes is a <g> around their elements.
+    pub fn to_svg_element(&self) -> String {
+        let mut out = String::new();
+        self.write_svg_element(&mut out, false)
+            .expect("writing to a String can't fail");
+        out
+    }
+
+    // Writes the element, styled as the selection if `selected`. The
+    // selection is marked with class="selection" so Document::from_svg()
+    // can tell it apart.
+    fn write_svg_element(&self, out: &mut impl Write, selected: bool) -> fmt::Result {
+        let style = if selected { SELECTION_STYLE } else { SHAPE_STYLE };
+        match *self {
+            Shape::Rect(rect) =>
+                write!(out, r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+                       rect.top_left.x, rect.top_left.y, rect.width(), rect.height(), style),
+            Shape::Ellipse { center, radius_x, radius_y, rotation } => {
+                write!(out, r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}""#,
+                       center.x, center.y, radius_x, radius_y)?;
+                if rotation != 0.0 {
+                    write!(out, r#" transform="rotate({} {} {})""#,
+                           rotation.to_degrees(), center.x, center.y)?;
+                }
+                write!(out, " {}/>", style)
+            }
+            Shape::Trapezoid { top_left, top_right, bottom_right, bottom_left } =>
+                write_polygon(out, &[top_left, top_right, bottom_right, bottom_left], style),
+            Shape::TextSpan(start, end) => {
+                let class = if selected { "text-span selection" } else { "text-span" };
+                write!(out, r#"<g class="{}" data-start="{}" data-end="{}"/>"#, class, start, end)
+            }
+            Shape::Polygon(ref points) =>
+                write_polygon(out, points, style),
+            Shape::Line(a, b) =>
+                write!(out, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#, a.x, a.y, b.x, b.y, style),
+            Shape::Group(ref shapes) => {
+                out.write_str("<g>")?;
+                for shape in shapes {
+                    shape.write_svg_element(out, selected)?;
+                }
+                out.write_str("</g>")
+            }
+        }
+    }
+}
+
+fn write_polygon(out: &mut impl Write, points: &[Point], style: &str) -> fmt::Result {
+    out.write_str(r#"<polygon points=""#)?;
+    for (i, p) in points.iter().enumerate() {
+        let separator = if i == 0 { "" } else { " " };
+        write!(out, "{}{},{}", separator, p.x, p.y)?;
+    }
+    write!(out, r#"" {}/>"#, style)
+}
+
+impl Document {
+    // The whole document as a standalone SVG image, one element per shape in
+    // drawing order and then the selection on top. The view box fits
+    // everything with a position; a document with nothing but text gets
+    // none.
+    pub fn to_svg(&self) -> String {
+        let selection = self.selection().map_or(Vec::new(), Selection::to_shapes);
+        let drawn = || self.shapes().map(|(_, shape)| shape).chain(&selection);
+
+        let mut out = String::new();
+        self.write_svg(&mut out, view_box(drawn()), &selection)
+            .expect("writing to a String can't fail");
+        out
+    }
+
+    fn write_svg(&self, out: &mut impl Write, view: Option<Rect>, selection: &[Shape])
+        -> fmt::Result
+    {
+        out.write_str(r#"<svg xmlns="http://www.w3.org/2000/svg""#)?;
+        if let Some(view) = view {
+            write!(out, r#" viewBox="{} {} {} {}""#,
+                   view.top_left.x, view.top_left.y, view.width(), view.height())?;
+        }
+        out.write_str(">\n")?;
+        for (_, shape) in self.shapes() {
+            out.write_str("  ")?;
+            shape.write_svg_element(out, false)?;
+            out.write_str("\n")?;
+        }
+        for shape in selection {
+            out.write_str("  ")?;
+            shape.write_svg_element(out, true)?;
+            out.write_str("\n")?;
+        }
+        out.write_str("</svg>\n")
+    }
+}
+
+// The smallest rectangle around all of the shapes that have a position.
+fn view_box<'a>(shapes: impl Iterator<Item = &'a Shape>) -> Option<Rect> {
+    let corners: Vec<_> = shapes
+        .filter_map(Shape::position)
+        .flat_map(|rect| rect.corners().to_vec())
+        .collect();
+    if corners.is_empty() {
+        return None;
+    }
+    Some(enclosing(&corners))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::super::TextSelection;
+
+    fn p(x: f64, y: f64) -> Point {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn each_shape_is_written_as_its_element() {
+        let rect = Shape::Rect(Rect::new(p(1.0, 2.0), p(5.0, 5.5)));
+        assert_eq!(rect.to_svg_element(),
+                   r#"<rect x="1" y="2" width="4" height="3.5" fill="none" stroke="black"/>"#);
+        let ellipse = |rotation| Shape::Ellipse {
+            center: p(3.0, 4.0),
+            radius_x: 2.0,
+            radius_y: 1.0,
+            rotation
+        };
+        assert_eq!(ellipse(0.0).to_svg_element(),
+                   r#"<ellipse cx="3" cy="4" rx="2" ry="1" fill="none" stroke="black"/>"#);
+        assert_eq!(ellipse(FRAC_PI_2).to_svg_element(),
+                   concat!(r#"<ellipse cx="3" cy="4" rx="2" ry="1" transform="rotate(90 3 4)" "#,
+                           r#"fill="none" stroke="black"/>"#));
+        let trapezoid = Shape::Trapezoid {
+            top_left: p(1.0, 0.0),
+            top_right: p(3.0, 0.0),
+            bottom_right: p(4.0, 2.0),
+            bottom_left: p(0.0, 2.0)
+        };
+        assert_eq!(trapezoid.to_svg_element(),
+                   r#"<polygon points="1,0 3,0 4,2 0,2" fill="none" stroke="black"/>"#);
+        assert_eq!(Shape::TextSpan(3, 8).to_svg_element(),
+                   r#"<g class="text-span" data-start="3" data-end="8"/>"#);
+        let polygon = Shape::Polygon(vec![p(0.0, 0.0), p(-1.5, 2.0), p(1.0, 3.0)]);
+        assert_eq!(polygon.to_svg_element(),
+                   r#"<polygon points="0,0 -1.5,2 1,3" fill="none" stroke="black"/>"#);
+        assert_eq!(Shape::Polygon(Vec::new()).to_svg_element(),
+                   r#"<polygon points="" fill="none" stroke="black"/>"#);
+        let line = Shape::Line(p(0.0, 1.0), p(2.0, 3.0));
+        assert_eq!(line.to_svg_element(),
+                   r#"<line x1="0" y1="1" x2="2" y2="3" fill="none" stroke="black"/>"#);
+        let group = Shape::Group(vec![line, Shape::Group(vec![Shape::TextSpan(0, 1)])]);
+        assert_eq!(group.to_svg_element(),
+                   concat!(r#"<g><line x1="0" y1="1" x2="2" y2="3" fill="none" stroke="black"/>"#,
+                           r#"<g><g class="text-span" data-start="0" data-end="1"/></g></g>"#));
+        assert_eq!(Shape::Group(Vec::new()).to_svg_element(), "<g></g>");
+    }
+
+    #[test]
+    fn a_document_of_text_has_no_view_box() {
+        let mut document = Document::new();
+        document.add(Shape::TextSpan(0, 4));
+        document.add(Shape::Group(vec![Shape::TextSpan(6, 9)]));
+        document.set_selection(Some(Selection::Text(TextSelection::from(1..3))));
+        assert_eq!(document.to_svg(),
+                   concat!(r#"<svg xmlns="http://www.w3.org/2000/svg">"#, "\n",
+                           r#"  <g class="text-span" data-start="0" data-end="4"/>"#, "\n",
+                           r#"  <g><g class="text-span" data-start="6" data-end="9"/></g>"#, "\n",
+                           r#"  <g class="text-span selection" data-start="1" data-end="3"/>"#,
+                           "\n</svg>\n"));
+        assert_eq!(Document::new().to_svg(),
+                   concat!(r#"<svg xmlns="http://www.w3.org/2000/svg">"#, "\n</svg>\n"));
+    }
+
+    #[test]
+    fn the_selection_is_drawn_last_and_highlighted() {
+        let mut document = Document::new();
+        document.add(Shape::Rect(Rect::new(p(0.0, 0.0), p(4.0, 3.0))));
+        document.add(Shape::TextSpan(0, 4));
+        document.select_in_rect(Rect::new(p(2.0, 2.0), p(6.0, 5.0)));
+        assert_eq!(document.to_svg(),
+                   concat!(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 6 5">"#, "\n",
+                           r#"  <rect x="0" y="0" width="4" height="3" "#,
+                           r#"fill="none" stroke="black"/>"#, "\n",
+                           r#"  <g class="text-span" data-start="0" data-end="4"/>"#, "\n",
+                           r#"  <rect x="2" y="2" width="4" height="3" class="selection" "#,
+                           r#"fill="royalblue" fill-opacity="0.25" stroke="royalblue" "#,
+                           r#"stroke-dasharray="4 2"/>"#,
+                           "\n</svg>\n"));
+    }
+}
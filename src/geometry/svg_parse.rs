@@ -0,0 +1,534 @@
+// Reading shapes back from SVG, so documents saved with Document::to_svg()
+// can be loaded again. Only the subset that maps onto Shape is understood:
+// <rect>, <ellipse>, <polygon> and <line>, and <g> for groups of them, each
+// with an optional transform attribute, plus the <g class="text-span">
+// elements to_svg() writes for text. Anything else that draws, such as
+// <path> or <circle>, is an error rather than being dropped, and every error
+// carries the byte range of the input it's about, so an editor can point at
+// the offending markup.
+//
+// This isn't an XML parser. Comments, processing instructions and doctypes
+// are skipped, text between elements is ignored, and entities in attribute
+// values aren't expanded; none of those appear in the SVG written here.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+
+use super::{Document, Matrix2x3, Point, Rect, Selection, SelectionError, Shape};
+
+// The error returned when SVG can't be read as shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseSvgError {
+    kind: SvgErrorKind,
+    span: Range<usize>
+}
+
+impl ParseSvgError {
+    pub fn kind(&self) -> &SvgErrorKind {
+        &self.kind
+    }
+
+    // The byte offsets of the markup the error is about: the whole tag for
+    // an unsupported element or a missing attribute, the value for a bad
+    // one.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgErrorKind {
+    // Markup that isn't well-formed, as far as this parser can tell.
+    Malformed,
+    // An element this module has no shape for, such as <path>.
+    UnsupportedElement(String),
+    // A required attribute that's missing, such as a rect's width.
+    MissingAttribute(&'static str),
+    // An attribute that should be a number, or a list of them, and isn't.
+    InvalidNumber,
+    // A transform attribute that isn't a list of matrix(), translate(),
+    // scale() and rotate().
+    InvalidTransform,
+    // The element marked as the selection is a shape that can't be one.
+    InvalidSelection(SelectionError)
+}
+
+impl fmt::Display for ParseSvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            SvgErrorKind::Malformed =>
+                f.write_str("malformed markup")?,
+            SvgErrorKind::UnsupportedElement(ref name) =>
+                write!(f, "unsupported element <{}>", name)?,
+            SvgErrorKind::MissingAttribute(name) =>
+                write!(f, "missing attribute {:?}", name)?,
+            SvgErrorKind::InvalidNumber =>
+                f.write_str("invalid number")?,
+            SvgErrorKind::InvalidTransform =>
+                f.write_str("unsupported transform")?,
+            SvgErrorKind::InvalidSelection(ref err) =>
+                write!(f, "invalid selection: {}", err)?
+        }
+        write!(f, " at bytes {}..{}", self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for ParseSvgError {}
+
+fn error(kind: SvgErrorKind, span: Range<usize>) -> ParseSvgError {
+    ParseSvgError { kind, span }
+}
+
+impl Document {
+    // Reads a document from SVG: every supported element becomes a shape,
+    // in order, except those marked class="selection", which become the
+    // selection. Several text spans marked that way make up one selection
+    // of text; otherwise the last one marked is the selection. The <svg>
+    // element around them is optional.
+    pub fn from_svg(s: &str) -> Result<Document, ParseSvgError> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let mut document = Document::new();
+        let mut selection = None;
+        // How many <svg> elements are open.
+        let mut depth = 0;
+
+        while let Some(tag) = parser.next_tag()? {
+            let element = match tag {
+                Tag::Close { name: "svg", .. } if depth > 0 => {
+                    depth -= 1;
+                    continue;
+                }
+                Tag::Close { span, .. } =>
+                    return Err(error(SvgErrorKind::Malformed, span)),
+                Tag::Open(element) => element
+            };
+            if element.name == "svg" {
+                if !element.self_closing {
+                    depth += 1;
+                }
+                continue;
+            }
+            let shape = parser.shape(&element)?;
+            if element.has_class("selection") {
+                let marked = Selection::try_from(shape)
+                    .map_err(|err| error(SvgErrorKind::InvalidSelection(err), element.span.clone()))?;
+                selection = match (selection, marked) {
+                    (Some(Selection::Text(mut text)), Selection::Text(more)) => {
+                        for range in more.ranges() {
+                            text.add(range.clone());
+                        }
+                        Some(Selection::Text(text))
+                    }
+                    (_, marked) => Some(marked)
+                };
+            } else {
+                document.add(shape);
+            }
+        }
+
+        if depth > 0 {
+            return Err(error(SvgErrorKind::Malformed, s.len()..s.len()));
+        }
+        // Set last, so the selection picks out the shapes after it too.
+        document.set_selection(selection);
+        Ok(document)
+    }
+}
+
+impl Shape {
+    // Reads a single element, as written by to_svg_element().
+    pub fn from_svg_element(s: &str) -> Result<Shape, ParseSvgError> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let element = match parser.next_tag()? {
+            Some(Tag::Open(element)) => element,
+            Some(Tag::Close { span, .. }) => return Err(error(SvgErrorKind::Malformed, span)),
+            None => return Err(error(SvgErrorKind::Malformed, 0..s.len()))
+        };
+        let shape = parser.shape(&element)?;
+        match parser.next_tag()? {
+            None => Ok(shape),
+            Some(Tag::Open(Element { span, .. })) | Some(Tag::Close { span, .. }) =>
+                Err(error(SvgErrorKind::Malformed, span))
+        }
+    }
+}
+
+enum Tag<'a> {
+    Open(Element<'a>),
+    Close {
+        name: &'a str,
+        span: Range<usize>
+    }
+}
+
+// A start tag, or an empty-element one ending "/>".
+struct Element<'a> {
+    name: &'a str,
+    attributes: Vec<Attribute<'a>>,
+    self_closing: bool,
+    span: Range<usize>
+}
+
+struct Attribute<'a> {
+    name: &'a str,
+    value: &'a str,
+    // Where the value is, without its quotes.
+    span: Range<usize>
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize
+}
+
+impl<'a> Parser<'a> {
+    // The shape `element` starts, reading the rest of the element up to its
+    // end tag if it has one: the shapes inside a group, and nothing inside
+    // any other element.
+    fn shape(&mut self, element: &Element<'a>) -> Result<Shape, ParseSvgError> {
+        let mut shape = element.to_shape()?;
+        if !element.self_closing {
+            loop {
+                match self.next_tag()? {
+                    Some(Tag::Close { name, .. }) if name == element.name =>
+                        break,
+                    Some(Tag::Close { span, .. }) =>
+                        return Err(error(SvgErrorKind::Malformed, span)),
+                    Some(Tag::Open(child)) => match shape {
+                        Shape::Group(ref mut shapes) =>
+                            shapes.push(self.shape(&child)?),
+                        _ =>
+                            return Err(error(SvgErrorKind::UnsupportedElement(child.name.to_string()),
+                                             child.span))
+                    },
+                    None =>
+                        return Err(error(SvgErrorKind::Malformed, self.input.len()..self.input.len()))
+                }
+            }
+        }
+        // A group's transform applies to the shapes inside it, so it has to
+        // wait until they've been read.
+        if let Some(transform) = element.attribute("transform") {
+            let matrix = parse_transform(transform.value)
+                .ok_or_else(|| error(SvgErrorKind::InvalidTransform, transform.span.clone()))?;
+            shape.transform(&matrix);
+        }
+        Ok(shape)
+    }
+
+    // The next start or end tag, skipping text, comments, processing
+    // instructions and declarations, or None at the end of the input.
+    fn next_tag(&mut self) -> Result<Option<Tag<'a>>, ParseSvgError> {
+        loop {
+            let start = match self.input[self.pos..].find('<') {
+                Some(offset) => self.pos + offset,
+                None => {
+                    self.pos = self.input.len();
+                    return Ok(None);
+                }
+            };
+            self.pos = start;
+            let rest = &self.input[start..];
+            let skip_to = if rest.starts_with("<!--") {
+                "-->"
+            } else if rest.starts_with("<?") {
+                "?>"
+            } else if rest.starts_with("<!") {
+                ">"
+            } else {
+                break;
+            };
+            match rest.find(skip_to) {
+                Some(end) => self.pos += end + skip_to.len(),
+                None => return Err(error(SvgErrorKind::Malformed, start..self.input.len()))
+            }
+        }
+
+        let start = self.pos;
+        if self.eat("</") {
+            let name = self.name()?;
+            self.skip_whitespace();
+            if !self.eat(">") {
+                return Err(self.malformed());
+            }
+            return Ok(Some(Tag::Close { name, span: start..self.pos }));
+        }
+
+        self.pos += 1;
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+        let self_closing = loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                break true;
+            }
+            if self.eat(">") {
+                break false;
+            }
+            attributes.push(self.attribute()?);
+        };
+        Ok(Some(Tag::Open(Element { name, attributes, self_closing, span: start..self.pos })))
+    }
+
+    // name="value", or with single quotes.
+    fn attribute(&mut self) -> Result<Attribute<'a>, ParseSvgError> {
+        let name = self.name()?;
+        self.skip_whitespace();
+        if !self.eat("=") {
+            return Err(self.malformed());
+        }
+        self.skip_whitespace();
+        let quote = match self.input[self.pos..].chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return Err(self.malformed())
+        };
+        let start = self.pos + 1;
+        let end = match self.input[start..].find(quote) {
+            Some(len) => start + len,
+            None => return Err(error(SvgErrorKind::Malformed, self.pos..self.input.len()))
+        };
+        self.pos = end + 1;
+        Ok(Attribute { name, value: &self.input[start..end], span: start..end })
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseSvgError> {
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || ['-', '_', ':', '.'].contains(&c)))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.malformed());
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let found = self.input[self.pos..].starts_with(expected);
+        if found {
+            self.pos += expected.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    // An error for whatever character the parser has stopped at.
+    fn malformed(&self) -> ParseSvgError {
+        let len = self.input[self.pos..].chars().next().map_or(0, char::len_utf8);
+        error(SvgErrorKind::Malformed, self.pos..self.pos + len)
+    }
+}
+
+impl<'a> Element<'a> {
+    // The shape the element's own attributes describe, before its
+    // transform and, for a group, without the shapes inside it.
+    fn to_shape(&self) -> Result<Shape, ParseSvgError> {
+        Ok(match self.name {
+            "rect" => {
+                let top_left = Point::new(self.number("x", Some(0.0))?, self.number("y", Some(0.0))?);
+                let (width, height) = (self.length("width")?, self.length("height")?);
+                Shape::Rect(Rect::new(top_left, Point::new(top_left.x + width, top_left.y + height)))
+            }
+            "ellipse" =>
+                Shape::Ellipse {
+                    center: Point::new(self.number("cx", Some(0.0))?, self.number("cy", Some(0.0))?),
+                    radius_x: self.length("rx")?,
+                    radius_y: self.length("ry")?,
+                    rotation: 0.0
+                },
+            "polygon" => {
+                let points = self.attribute("points")
+                    .ok_or_else(|| error(SvgErrorKind::MissingAttribute("points"), self.span.clone()))?;
+                let numbers = parse_numbers(points.value)
+                    .filter(|numbers| numbers.len() % 2 == 0)
+                    .ok_or_else(|| error(SvgErrorKind::InvalidNumber, points.span.clone()))?;
+                Shape::Polygon(numbers.chunks(2).map(|xy| Point::new(xy[0], xy[1])).collect())
+            }
+            "line" =>
+                Shape::Line(Point::new(self.number("x1", Some(0.0))?, self.number("y1", Some(0.0))?),
+                            Point::new(self.number("x2", Some(0.0))?, self.number("y2", Some(0.0))?)),
+            "g" if self.has_class("text-span") =>
+                Shape::TextSpan(self.offset("data-start")?, self.offset("data-end")?),
+            "g" =>
+                Shape::Group(Vec::new()),
+            name =>
+                return Err(error(SvgErrorKind::UnsupportedElement(name.to_string()), self.span.clone()))
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&Attribute<'a>> {
+        self.attributes.iter().find(|attribute| attribute.name == name)
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attribute("class").is_some_and(|attribute| {
+            attribute.value.split_whitespace().any(|name| name == class)
+        })
+    }
+
+    // The attribute as a number, or `default` if it's missing. Without a
+    // default it's required.
+    fn number(&self, name: &'static str, default: Option<f64>) -> Result<f64, ParseSvgError> {
+        match self.attribute(name) {
+            Some(attribute) =>
+                parse_number(attribute.value)
+                    .ok_or_else(|| error(SvgErrorKind::InvalidNumber, attribute.span.clone())),
+            None =>
+                default.ok_or_else(|| error(SvgErrorKind::MissingAttribute(name), self.span.clone()))
+        }
+    }
+
+    // A required number that can't be negative, as SVG requires of a
+    // rect's width and height and an ellipse's radii.
+    fn length(&self, name: &'static str) -> Result<f64, ParseSvgError> {
+        let attribute = self.attribute(name)
+            .ok_or_else(|| error(SvgErrorKind::MissingAttribute(name), self.span.clone()))?;
+        parse_number(attribute.value)
+            .filter(|&length| length >= 0.0)
+            .ok_or_else(|| error(SvgErrorKind::InvalidNumber, attribute.span.clone()))
+    }
+
+    // A required character offset.
+    fn offset(&self, name: &'static str) -> Result<usize, ParseSvgError> {
+        let attribute = self.attribute(name)
+            .ok_or_else(|| error(SvgErrorKind::MissingAttribute(name), self.span.clone()))?;
+        attribute.value.trim().parse()
+            .map_err(|_| error(SvgErrorKind::InvalidNumber, attribute.span.clone()))
+    }
+}
+
+// A finite number. SVG lengths can carry units, as in "10px", but nothing
+// here writes them, so they're rejected.
+fn parse_number(s: &str) -> Option<f64> {
+    s.trim().parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+// Numbers separated by whitespace, commas or both, as in "0,0 4,0 4,3".
+fn parse_numbers(s: &str) -> Option<Vec<f64>> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .map(parse_number)
+        .collect()
+}
+
+// A transform list such as "translate(10 20) rotate(45)". As in SVG, the
+// rightmost transform applies first.
+fn parse_transform(s: &str) -> Option<Matrix2x3> {
+    let mut matrix = Matrix2x3::IDENTITY;
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let open = rest.find('(')?;
+        let close = rest.find(')')?;
+        let args = parse_numbers(rest.get(open + 1..close)?)?;
+        let next = match (rest[..open].trim(), args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) =>
+                Matrix2x3 { a, b, c, d, e, f },
+            ("translate", &[dx]) =>
+                Matrix2x3::translation(dx, 0.0),
+            ("translate", &[dx, dy]) =>
+                Matrix2x3::translation(dx, dy),
+            ("scale", &[s]) =>
+                Matrix2x3::scaling(s, s),
+            ("scale", &[sx, sy]) =>
+                Matrix2x3::scaling(sx, sy),
+            ("rotate", &[degrees]) =>
+                Matrix2x3::rotation(degrees.to_radians()),
+            ("rotate", &[degrees, x, y]) =>
+                Matrix2x3::rotation(degrees.to_radians()).around(Point::new(x, y)),
+            _ =>
+                return None
+        };
+        matrix = next.then(&matrix);
+        rest = rest[close + 1..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Some(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TextSelection;
+    use super::*;
+
+    #[test]
+    fn rect_from_its_attributes() {
+        let rect = Shape::from_svg_element(r#"<rect x="1" y="2" width="3" height="4"/>"#).unwrap();
+        assert_eq!(rect, Shape::Rect(Rect::new(Point::new(1.0, 2.0), Point::new(4.0, 6.0))));
+        let empty = Shape::from_svg_element(r#"<rect width="0" height="0"/>"#).unwrap();
+        assert_eq!(empty.area(), 0.0);
+    }
+
+    #[test]
+    fn negative_sizes_and_radii_are_invalid_numbers() {
+        for (svg, value) in [(r#"<rect x="5" width="-3" height="4"/>"#, "-3"),
+                             (r#"<rect width="3" height="-0.5"/>"#, "-0.5"),
+                             (r#"<ellipse rx="-1" ry="1"/>"#, "-1"),
+                             (r#"<ellipse rx="1" ry="-2"/>"#, "-2")] {
+            let err = Shape::from_svg_element(svg).unwrap_err();
+            assert_eq!(*err.kind(), SvgErrorKind::InvalidNumber);
+            assert_eq!(&svg[err.span()], value);
+        }
+    }
+
+    #[test]
+    fn unsupported_elements_are_errors_at_the_element() {
+        let cases = [(r#"<svg><path d="M0 0"/></svg>"#, "path", r#"<path d="M0 0"/>"#),
+                     ("<svg>\n  <circle r=\"1\"></circle></svg>", "circle", r#"<circle r="1">"#),
+                     (r#"<g><circle r="1"/></g>"#, "circle", r#"<circle r="1"/>"#)];
+        for (svg, name, element) in cases {
+            let err = Document::from_svg(svg).unwrap_err();
+            assert_eq!(*err.kind(), SvgErrorKind::UnsupportedElement(name.to_string()));
+            assert_eq!(&svg[err.span()], element);
+        }
+        // Nothing can go inside a shape other than a group.
+        let svg = r#"<rect width="1" height="1"><path/></rect>"#;
+        assert_eq!(&svg[Shape::from_svg_element(svg).unwrap_err().span()], "<path/>");
+    }
+
+    #[test]
+    fn a_document_round_trips_through_svg() {
+        let p = Point::new;
+        let mut document = Document::new();
+        document.add(Shape::Rect(Rect::new(p(0.0, 0.0), p(4.0, 3.0))));
+        document.add(Shape::Ellipse {
+            center: p(6.0, 6.0),
+            radius_x: 2.0,
+            radius_y: 1.0,
+            rotation: 0.5
+        });
+        document.add(Shape::Polygon(vec![p(0.0, 0.0), p(4.0, 0.0), p(2.0, 3.0)]));
+        let line = Shape::Line(p(-1.0, 0.0), p(3.0, 9.0));
+        document.add(Shape::Group(vec![line, Shape::TextSpan(2, 5)]));
+        document.select_in_rect(Rect::new(p(3.0, 2.0), p(7.0, 7.0)));
+
+        let read = Document::from_svg(&document.to_svg()).unwrap();
+        assert_eq!(read.len(), document.len());
+        for ((id, read), (_, shape)) in read.shapes().zip(document.shapes()) {
+            match (read, shape) {
+                (&Shape::Ellipse { center, radius_x, radius_y, rotation },
+                 &Shape::Ellipse { rotation: original, .. }) => {
+                    assert_eq!((center, radius_x, radius_y), (p(6.0, 6.0), 2.0, 1.0));
+                    assert!((rotation - original).abs() < 1e-9, "{}", rotation);
+                }
+                _ =>
+                    assert_eq!(read, shape, "{:?}", id)
+            }
+        }
+        assert_eq!(read.selection(), document.selection());
+        assert_eq!(read.selected(), document.selected());
+    }
+
+    // Text spans marked as the selection are gathered into one selection.
+    #[test]
+    fn a_text_selection_round_trips_through_svg() {
+        let mut document = Document::new();
+        document.add(Shape::TextSpan(0, 10));
+        document.set_selection(Some(Selection::Text(TextSelection::from_ranges(vec![1..3, 5..8]))));
+        let read = Document::from_svg(&document.to_svg()).unwrap();
+        assert_eq!(read, document);
+    }
+}
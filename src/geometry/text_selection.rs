@@ -0,0 +1,279 @@
+use std::cmp;
+use std::ops::Range;
+
+// Selected text, as any number of ranges of character offsets, so that
+// "select all matches" can select every match at once. The ranges are kept
+// sorted and apart: adding one that overlaps or touches another merges the
+// two, and empty ones are dropped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TextSelection {
+    ranges: Vec<Range<usize>>
+}
+
+impl TextSelection {
+    pub fn new() -> TextSelection {
+        TextSelection::default()
+    }
+
+    // The selection covering all of `ranges`. A range given backwards, as a
+    // selection dragged from right to left would be, covers the same text as
+    // it would forwards.
+    pub fn from_ranges<I>(ranges: I) -> TextSelection
+        where I: IntoIterator<Item = Range<usize>>
+    {
+        let mut selection = TextSelection::new();
+        for range in ranges {
+            selection.add(range);
+        }
+        selection
+    }
+
+    // The selected ranges, in order.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // How many characters are selected in all.
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|range| range.end - range.start).sum()
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.ranges.binary_search_by(|range| {
+            if range.end <= offset {
+                cmp::Ordering::Less
+            } else if range.start > offset {
+                cmp::Ordering::Greater
+            } else {
+                cmp::Ordering::Equal
+            }
+        }).is_ok()
+    }
+
+    // Adds `range` to the selection, merging it with any range it overlaps
+    // or touches.
+    pub fn add(&mut self, range: Range<usize>) {
+        let Range { mut start, mut end } = normalize(range);
+        if start == end {
+            return;
+        }
+        // The ranges that touch the new one are the run from the first that
+        // doesn't end before it to the last that doesn't start after it.
+        let first = self.ranges.partition_point(|r| r.end < start);
+        let last = self.ranges.partition_point(|r| r.start <= end);
+        if first < last {
+            start = start.min(self.ranges[first].start);
+            end = end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, Some(start..end));
+    }
+
+    // Takes `range` out of the selection, splitting any range it falls
+    // inside.
+    pub fn remove(&mut self, range: Range<usize>) {
+        let Range { start, end } = normalize(range);
+        if start == end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= start);
+        let last = self.ranges.partition_point(|r| r.start < end);
+        if first == last {
+            return;
+        }
+        let before = self.ranges[first].start..start;
+        let after = end..self.ranges[last - 1].end;
+        let kept = vec![before, after].into_iter().filter(|r| r.start < r.end);
+        self.ranges.splice(first..last, kept);
+    }
+
+    // The selection as ranges of line and column positions in `text`, or
+    // None if it reaches past the end of the text.
+    pub fn to_line_columns(&self, text: &str) -> Option<Vec<Range<LineColumn>>> {
+        let lines = LineStarts::new(text);
+        self.ranges.iter()
+            .map(|range| Some(lines.line_column(range.start)?..lines.line_column(range.end)?))
+            .collect()
+    }
+
+    // The selection covering the given ranges of line and column positions
+    // in `text`, or None if any of them isn't in the text.
+    pub fn from_line_columns<I>(text: &str, ranges: I) -> Option<TextSelection>
+        where I: IntoIterator<Item = Range<LineColumn>>
+    {
+        let lines = LineStarts::new(text);
+        let mut selection = TextSelection::new();
+        for range in ranges {
+            selection.add(lines.offset(range.start)?..lines.offset(range.end)?);
+        }
+        Some(selection)
+    }
+}
+
+impl From<Range<usize>> for TextSelection {
+    fn from(range: Range<usize>) -> TextSelection {
+        TextSelection::from_ranges(Some(range))
+    }
+}
+
+// A position in text by line and column, both counted from zero, and
+// columns in characters, matching the character offsets used elsewhere.
+// Lines are separated by '\n'; a '\r' before one counts as a character on
+// the line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize
+}
+
+impl LineColumn {
+    pub fn new(line: usize, column: usize) -> LineColumn {
+        LineColumn { line, column }
+    }
+
+    // The position of character offset `offset` in `text`, or None if the
+    // text is shorter than that. The offset just past the last character is
+    // allowed, as the end of a range.
+    pub fn from_offset(text: &str, offset: usize) -> Option<LineColumn> {
+        LineStarts::new(text).line_column(offset)
+    }
+
+    // The character offset of this position in `text`, or None if there's
+    // no such line or the line is shorter than that. The column just past a
+    // line's last character, where its '\n' is, is allowed.
+    pub fn to_offset(self, text: &str) -> Option<usize> {
+        LineStarts::new(text).offset(self)
+    }
+}
+
+fn normalize(range: Range<usize>) -> Range<usize> {
+    if range.start <= range.end { range } else { range.end..range.start }
+}
+
+// The character offset at which each line of a text starts, so that
+// converting many positions takes one pass over the text.
+struct LineStarts {
+    starts: Vec<usize>,
+    len: usize
+}
+
+impl LineStarts {
+    fn new(text: &str) -> LineStarts {
+        let mut starts = vec![0];
+        let mut len = 0;
+        for c in text.chars() {
+            len += 1;
+            if c == '\n' {
+                starts.push(len);
+            }
+        }
+        LineStarts { starts, len }
+    }
+
+    fn line_column(&self, offset: usize) -> Option<LineColumn> {
+        if offset > self.len {
+            return None;
+        }
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        Some(LineColumn::new(line, offset - self.starts[line]))
+    }
+
+    fn offset(&self, position: LineColumn) -> Option<usize> {
+        let start = *self.starts.get(position.line)?;
+        // A line ends at its '\n', one before the next line starts.
+        let end = self.starts.get(position.line + 1).map_or(self.len, |next| next - 1);
+        if position.column > end - start {
+            return None;
+        }
+        Some(start + position.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(selection: &TextSelection) -> Vec<Range<usize>> {
+        selection.ranges().to_vec()
+    }
+
+    #[test]
+    fn add_merges_overlapping_and_touching_ranges() {
+        let mut selection = TextSelection::from_ranges(vec![10..15, 2..4, 20..25]);
+        assert_eq!(ranges(&selection), [2..4, 10..15, 20..25]);
+        selection.add(4..6);
+        assert_eq!(ranges(&selection), [2..6, 10..15, 20..25]);
+        selection.add(12..21);
+        assert_eq!(ranges(&selection), [2..6, 10..25]);
+        selection.add(7..8);
+        assert_eq!(ranges(&selection), [2..6, 7..8, 10..25]);
+        selection.add(0..30);
+        assert_eq!(selection, TextSelection::from(0..30));
+        selection.add(5..5);
+        assert_eq!(selection, TextSelection::from(0..30));
+        assert_eq!(selection.len(), 30);
+    }
+
+    // A range dragged out from right to left selects the same text.
+    #[test]
+    fn reversed_ranges_are_turned_around() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let (backwards, hole) = (8..3, 7..5);
+        let mut selection = TextSelection::from(backwards.clone());
+        assert_eq!(selection, TextSelection::from(3..8));
+        selection.remove(hole);
+        assert_eq!(ranges(&selection), [3..5, 7..8]);
+        assert!(TextSelection::from(4..4).is_empty());
+        assert!(!selection.contains(backwards.start));
+    }
+
+    #[test]
+    fn remove_splits_a_range() {
+        let mut selection = TextSelection::from(0..10);
+        selection.remove(3..6);
+        assert_eq!(ranges(&selection), [0..3, 6..10]);
+        assert!(selection.contains(2) && !selection.contains(3) && selection.contains(6));
+        selection.remove(2..8);
+        assert_eq!(ranges(&selection), [0..2, 8..10]);
+        selection.remove(20..30);
+        selection.remove(2..8);
+        assert_eq!(ranges(&selection), [0..2, 8..10]);
+        selection.remove(0..10);
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn line_columns_round_trip() {
+        let text = "héllo\r\nwörld ✓\n\nend";
+        let selection = TextSelection::from_ranges(vec![1..4, 6..9, 14..15, 15..17]);
+        let positions = selection.to_line_columns(text).unwrap();
+        assert_eq!(positions,
+                   [LineColumn::new(0, 1)..LineColumn::new(0, 4),
+                    LineColumn::new(0, 6)..LineColumn::new(1, 2),
+                    LineColumn::new(1, 7)..LineColumn::new(3, 1)]);
+        assert_eq!(TextSelection::from_line_columns(text, positions), Some(selection));
+
+        // The '\r' is a character on the first line, and the end of the text
+        // is a position too.
+        assert_eq!(LineColumn::from_offset(text, 5), Some(LineColumn::new(0, 5)));
+        assert_eq!(LineColumn::from_offset(text, 19), Some(LineColumn::new(3, 3)));
+        for offset in 0..=19 {
+            let position = LineColumn::from_offset(text, offset).unwrap();
+            assert_eq!(position.to_offset(text), Some(offset), "{:?}", position);
+        }
+    }
+
+    #[test]
+    fn positions_past_the_end_are_none() {
+        let text = "ab\ncd";
+        assert_eq!(LineColumn::from_offset(text, 6), None);
+        assert_eq!(TextSelection::from(4..6).to_line_columns(text), None);
+        assert_eq!(LineColumn::new(0, 3).to_offset(text), None);
+        assert_eq!(LineColumn::new(2, 0).to_offset(text), None);
+        let past = LineColumn::new(1, 0)..LineColumn::new(1, 3);
+        assert_eq!(TextSelection::from_line_columns(text, Some(past)), None);
+    }
+}
@@ -0,0 +1,316 @@
+use super::{Point, Rect, Shape};
+
+// An affine transform of the canvas, as the matrix
+//
+//     | a  c  e |
+//     | b  d  f |
+//     | 0  0  1 |
+//
+// which takes (x, y) to (a x + c y + e, b x + d y + f). The names and order
+// are SVG's, so a transform attribute matrix(a b c d e f) means the same.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix2x3 {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64
+}
+
+impl Matrix2x3 {
+    pub const IDENTITY: Matrix2x3 = Matrix2x3 { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub fn translation(dx: f64, dy: f64) -> Matrix2x3 {
+        Matrix2x3 { e: dx, f: dy, ..Matrix2x3::IDENTITY }
+    }
+
+    // Scaling away from the origin, by `sx` horizontally and `sy`
+    // vertically. A negative factor mirrors the canvas.
+    pub fn scaling(sx: f64, sy: f64) -> Matrix2x3 {
+        Matrix2x3 { a: sx, d: sy, ..Matrix2x3::IDENTITY }
+    }
+
+    // Rotation about the origin by `angle` radians. With y growing
+    // downwards, a positive angle turns clockwise on screen.
+    pub fn rotation(angle: f64) -> Matrix2x3 {
+        let (sin, cos) = angle.sin_cos();
+        Matrix2x3 { a: cos, b: sin, c: -sin, d: cos, e: 0.0, f: 0.0 }
+    }
+
+    // This transform, followed by `next`.
+    pub fn then(&self, next: &Matrix2x3) -> Matrix2x3 {
+        Matrix2x3 {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: next.a * self.e + next.c * self.f + next.e,
+            f: next.b * self.e + next.d * self.f + next.f
+        }
+    }
+
+    // This transform done about `pivot` rather than the origin, so that
+    // Matrix2x3::rotation(angle).around(center) spins a shape in place.
+    pub fn around(&self, pivot: Point) -> Matrix2x3 {
+        Matrix2x3::translation(-pivot.x, -pivot.y)
+            .then(self)
+            .then(&Matrix2x3::translation(pivot.x, pivot.y))
+    }
+
+    pub fn apply(&self, p: Point) -> Point {
+        Point::new(self.a * p.x + self.c * p.y + self.e, self.b * p.x + self.d * p.y + self.f)
+    }
+
+    // Whether the transform keeps horizontal lines horizontal and vertical
+    // ones vertical, possibly swapping the two, so a Rect stays a Rect. A
+    // quarter turn leaves rounding error of around 1e-16 where the zeros
+    // should be, which is ignored.
+    fn keeps_axes(&self) -> bool {
+        let tolerance = 1e-12 * self.a.abs().max(self.b.abs()).max(self.c.abs()).max(self.d.abs());
+        (self.b.abs() <= tolerance && self.c.abs() <= tolerance)
+            || (self.a.abs() <= tolerance && self.d.abs() <= tolerance)
+    }
+}
+
+impl Default for Matrix2x3 {
+    fn default() -> Matrix2x3 {
+        Matrix2x3::IDENTITY
+    }
+}
+
+impl Shape {
+    // Moves, turns, stretches or mirrors the shape by `matrix`. Most shapes
+    // keep their variant: a trapezoid's parallel sides stay parallel, and an
+    // ellipse stays an ellipse with new radii and rotation. A Rect stays one
+    // only if the transform keeps its sides upright; otherwise it becomes
+    // the Polygon through its corners. A text span is laid out wherever its
+    // text goes, so it doesn't change.
+    pub fn transform(&mut self, matrix: &Matrix2x3) {
+        let m = matrix;
+        match *self {
+            Shape::Rect(rect) => {
+                if m.keeps_axes() {
+                    *self = Shape::Rect(Rect::new(m.apply(rect.top_left), m.apply(rect.bottom_right)));
+                } else {
+                    *self = Shape::Polygon(rect.corners().iter().map(|&p| m.apply(p)).collect());
+                }
+            }
+            Shape::Ellipse { ref mut center, ref mut radius_x, ref mut radius_y, ref mut rotation } => {
+                let (rx, ry, angle) = transform_ellipse(m, *radius_x, *radius_y, *rotation);
+                *center = m.apply(*center);
+                *radius_x = rx;
+                *radius_y = ry;
+                *rotation = angle;
+            }
+            Shape::Trapezoid {
+                ref mut top_left,
+                ref mut top_right,
+                ref mut bottom_right,
+                ref mut bottom_left
+            } => {
+                for corner in [top_left, top_right, bottom_right, bottom_left] {
+                    *corner = m.apply(*corner);
+                }
+            }
+            Shape::TextSpan(..) => {}
+            Shape::Polygon(ref mut points) => {
+                for p in points {
+                    *p = m.apply(*p);
+                }
+            }
+            Shape::Line(ref mut a, ref mut b) => {
+                *a = m.apply(*a);
+                *b = m.apply(*b);
+            }
+            Shape::Group(ref mut shapes) => {
+                for shape in shapes {
+                    shape.transform(m);
+                }
+            }
+        }
+    }
+
+    // A copy of the shape, transformed by `matrix`.
+    pub fn transformed(&self, matrix: &Matrix2x3) -> Shape {
+        let mut shape = self.clone();
+        shape.transform(matrix);
+        shape
+    }
+
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        self.transform(&Matrix2x3::translation(dx, dy));
+    }
+
+    pub fn translated(&self, dx: f64, dy: f64) -> Shape {
+        self.transformed(&Matrix2x3::translation(dx, dy))
+    }
+
+    // Scales the shape away from the origin. For zooming about another
+    // point, use transform() with Matrix2x3::scaling(sx, sy).around(point).
+    pub fn scale(&mut self, sx: f64, sy: f64) {
+        self.transform(&Matrix2x3::scaling(sx, sy));
+    }
+
+    pub fn scaled(&self, sx: f64, sy: f64) -> Shape {
+        self.transformed(&Matrix2x3::scaling(sx, sy))
+    }
+
+    // Turns the shape clockwise about the origin by `angle` radians.
+    pub fn rotate(&mut self, angle: f64) {
+        self.transform(&Matrix2x3::rotation(angle));
+    }
+
+    pub fn rotated(&self, angle: f64) -> Shape {
+        self.transformed(&Matrix2x3::rotation(angle))
+    }
+}
+
+// The radii and rotation of an ellipse after `m`. The ellipse is the unit
+// circle under the linear map L = M R(rotation) diag(radius_x, radius_y),
+// and writing L as a rotation, then a scaling, then another rotation (its
+// singular value decomposition) gives the new ellipse: the first rotation
+// turns the circle into itself, the scaling gives the radii and the last
+// rotation is the new rotation.
+fn transform_ellipse(m: &Matrix2x3, radius_x: f64, radius_y: f64, rotation: f64) -> (f64, f64, f64) {
+    let (sin, cos) = rotation.sin_cos();
+    let (l11, l21) = (radius_x * (m.a * cos + m.c * sin), radius_x * (m.b * cos + m.d * sin));
+    let (l12, l22) = (radius_y * (m.c * cos - m.a * sin), radius_y * (m.d * cos - m.b * sin));
+
+    let (e, f) = ((l11 + l22) / 2.0, (l11 - l22) / 2.0);
+    let (g, h) = ((l21 + l12) / 2.0, (l21 - l12) / 2.0);
+    let (q, r) = (e.hypot(h), f.hypot(g));
+    let angle = (g.atan2(f) + h.atan2(e)) / 2.0;
+    (q + r, (q - r).abs(), angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_6, PI, SQRT_2};
+
+    use super::super::hit::ellipse_normalize;
+
+    fn close(p: Point, q: Point) -> bool {
+        p.distance_to(q) < 1e-9
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let shift = Matrix2x3::translation(1.0, 0.0);
+        let double = Matrix2x3::scaling(2.0, 2.0);
+        let p = Point::new(1.0, 1.0);
+        assert_eq!(shift.then(&double).apply(p), Point::new(4.0, 2.0));
+        assert_eq!(double.then(&shift).apply(p), Point::new(3.0, 2.0));
+
+        let turn = Matrix2x3::rotation(FRAC_PI_6);
+        let stretch = Matrix2x3 { a: 2.0, b: 0.5, c: -1.0, d: 3.0, e: 4.0, f: -2.0 };
+        for &p in &[Point::new(0.0, 0.0), Point::new(1.0, -2.0), Point::new(-3.5, 0.25)] {
+            assert!(close(turn.then(&stretch).apply(p), stretch.apply(turn.apply(p))));
+            assert!(close(stretch.then(&turn).apply(p), turn.apply(stretch.apply(p))));
+        }
+        assert_eq!(stretch.then(&Matrix2x3::IDENTITY), stretch);
+        assert_eq!(Matrix2x3::IDENTITY.then(&stretch), stretch);
+    }
+
+    #[test]
+    fn around_keeps_the_pivot_in_place() {
+        let pivot = Point::new(1.0, 1.0);
+        // A quarter turn clockwise on screen, with y growing downwards.
+        let turn = Matrix2x3::rotation(FRAC_PI_2).around(pivot);
+        assert!(close(turn.apply(pivot), pivot));
+        assert!(close(turn.apply(Point::new(2.0, 1.0)), Point::new(1.0, 2.0)));
+        assert!(close(turn.apply(Point::new(1.0, 2.0)), Point::new(0.0, 1.0)));
+
+        let zoom = Matrix2x3::scaling(2.0, 3.0).around(pivot);
+        assert_eq!(zoom.apply(pivot), pivot);
+        assert_eq!(zoom.apply(Point::new(2.0, 2.0)), Point::new(3.0, 4.0));
+        assert_eq!(zoom.apply(Point::new(0.0, 0.0)), Point::new(-1.0, -2.0));
+    }
+
+    #[test]
+    fn keeps_axes() {
+        assert!(Matrix2x3::IDENTITY.keeps_axes());
+        assert!(Matrix2x3::translation(3.0, -4.0).keeps_axes());
+        assert!(Matrix2x3::scaling(-1.0, 2.0).keeps_axes());
+        assert!(Matrix2x3::rotation(FRAC_PI_2).keeps_axes());
+        assert!(Matrix2x3::rotation(-FRAC_PI_2).then(&Matrix2x3::scaling(1e-6, 1e6)).keeps_axes());
+        assert!(!Matrix2x3::rotation(FRAC_PI_4).keeps_axes());
+        assert!(!Matrix2x3::rotation(1e-6).keeps_axes());
+        // A shear moves one pair of sides but not the other.
+        assert!(!Matrix2x3 { c: 0.5, ..Matrix2x3::IDENTITY }.keeps_axes());
+    }
+
+    #[test]
+    fn a_rect_stays_a_rect_only_while_its_sides_stay_upright() {
+        let rect = Shape::Rect(Rect::new(Point::new(0.0, 0.0), Point::new(2.0, 1.0)));
+        match rect.rotated(FRAC_PI_2) {
+            Shape::Rect(turned) => {
+                assert!(close(turned.top_left, Point::new(-1.0, 0.0)));
+                assert!(close(turned.bottom_right, Point::new(0.0, 2.0)));
+            }
+            other => panic!("expected a Rect, got {:?}", other)
+        }
+        assert_eq!(rect.scaled(-2.0, 3.0),
+                   Shape::Rect(Rect::new(Point::new(-4.0, 0.0), Point::new(0.0, 3.0))));
+
+        match rect.rotated(FRAC_PI_4) {
+            Shape::Polygon(corners) => {
+                let expected = [Point::new(0.0, 0.0),
+                                Point::new(SQRT_2, SQRT_2),
+                                Point::new(SQRT_2 / 2.0, 3.0 * SQRT_2 / 2.0),
+                                Point::new(-SQRT_2 / 2.0, SQRT_2 / 2.0)];
+                assert_eq!(corners.len(), 4);
+                for (&corner, &expected) in corners.iter().zip(expected.iter()) {
+                    assert!(close(corner, expected), "{:?} != {:?}", corner, expected);
+                }
+            }
+            other => panic!("expected a Polygon, got {:?}", other)
+        }
+    }
+
+    // Checks the ellipse transform_ellipse() gives against points around the
+    // original ellipse mapped through `m`.
+    fn check_ellipse(m: &Matrix2x3, radius_x: f64, radius_y: f64, rotation: f64)
+        -> (f64, f64, f64)
+    {
+        let center = Point::new(1.0, -2.0);
+        let (rx, ry, angle) = transform_ellipse(m, radius_x, radius_y, rotation);
+        let (sin, cos) = rotation.sin_cos();
+        for step in 0..16 {
+            let (t_sin, t_cos) = (f64::from(step) * FRAC_PI_4 / 2.0).sin_cos();
+            let (x, y) = (radius_x * t_cos, radius_y * t_sin);
+            let on_original = center + Point::new(x * cos - y * sin, x * sin + y * cos);
+            let local = ellipse_normalize(m.apply(center), rx, ry, angle, m.apply(on_original));
+            assert!((local.dot(local) - 1.0).abs() < 1e-9, "{:?} is off the ellipse", local);
+        }
+        (rx, ry, angle)
+    }
+
+    #[test]
+    fn transform_ellipse_under_non_uniform_scaling() {
+        let (rx, ry, angle) = check_ellipse(&Matrix2x3::scaling(3.0, 2.0), 2.0, 1.0, 0.0);
+        assert!((rx - 6.0).abs() < 1e-9 && (ry - 2.0).abs() < 1e-9);
+        assert!(angle.sin().abs() < 1e-9);
+        // Stretching the short axis past the long one swaps them over.
+        let (rx, ry, angle) = check_ellipse(&Matrix2x3::scaling(1.0, 5.0), 2.0, 1.0, 0.0);
+        assert!((rx - 5.0).abs() < 1e-9 && (ry - 2.0).abs() < 1e-9);
+        assert!(angle.cos().abs() < 1e-9);
+        // A turned ellipse stretched along x leans further over.
+        let (rx, ry, angle) = check_ellipse(&Matrix2x3::scaling(4.0, 1.0), 3.0, 1.0, FRAC_PI_4);
+        assert!(rx > 3.0 && ry < 3.0);
+        assert!(angle.rem_euclid(PI) < FRAC_PI_4);
+        check_ellipse(&Matrix2x3::scaling(0.5, 2.0).then(&Matrix2x3::rotation(1.0)), 1.0, 1.0, 0.3);
+    }
+
+    #[test]
+    fn transform_ellipse_under_mirroring() {
+        let (rx, ry, angle) = check_ellipse(&Matrix2x3::scaling(-1.0, 1.0), 3.0, 1.0, FRAC_PI_6);
+        assert!((rx - 3.0).abs() < 1e-9 && (ry - 1.0).abs() < 1e-9);
+        assert!((angle + FRAC_PI_6).sin().abs() < 1e-9);
+        let (rx, ry, angle) = check_ellipse(&Matrix2x3::scaling(1.0, -1.0), 3.0, 1.0, FRAC_PI_6);
+        assert!((rx - 3.0).abs() < 1e-9 && (ry - 1.0).abs() < 1e-9);
+        assert!((angle + FRAC_PI_6).sin().abs() < 1e-9);
+        check_ellipse(&Matrix2x3::scaling(-2.0, 0.5), 3.0, 1.0, 1.2);
+    }
+}
@@ -0,0 +1,20 @@
+// main.rs follows the enums and patterns chapter as written in the book, with
+// snippets that lean on types the chapter never defines. The modules in this
+// library turn those sketches into real, compiling code that can be used from
+// other crates.
+//
+// Without the default `std` feature the library is `no_std` and needs only
+// `alloc`, so the tree can be used on targets with a heap but no operating
+// system. The pieces that need threads, locks, the clock or floating-point
+// maths are left out in that case.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod binary_tree;
+pub mod calendar;
+#[cfg(feature = "std")]
+pub mod geometry;
+pub mod rough_time;
+pub mod settings;
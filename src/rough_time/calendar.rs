@@ -0,0 +1,91 @@
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use crate::calendar::{Calendar, CalendarDate, DateError};
+
+use super::humanizer::local_day;
+use super::{RoughTime, Tense, TimeUnit};
+
+// The Julian Day Number of 1970-01-01.
+pub(super) const UNIX_EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+impl RoughTime {
+    // relative_to(), with months and years counted on `calendar` instead of
+    // taken as fixed lengths. "2 months ago" then means two of the
+    // calendar's own months back, however long they were, so a date near
+    // the start of a month reads the way someone using that calendar would
+    // count it. The 5 or 6 days of the Ethiopian Pagume are counted as the
+    // end of the month before rather than as a month of their own. Times
+    // under a month apart don't depend on the calendar and come out as
+    // relative_to() gives them.
+    //
+    // Months and years are counted between the UTC dates of `time` and
+    // `now`, rounding to the nearest whole one, with ties down, as
+    // relative_to() does. The calendar must be one whose dates can be worked
+    // out, which rules out the Chinese calendar.
+    pub fn relative_to_in(time: SystemTime,
+                          now: SystemTime,
+                          calendar: Calendar) -> Result<RoughTime, DateError> {
+        let (earlier, later, tense) = if time <= now {
+            (time, now, Tense::Past)
+        } else {
+            (now, time, Tense::Future)
+        };
+        let start = date_of(earlier, calendar)?;
+        let end = date_of(later, calendar)?;
+        let end_day = end.to_julian_day()?;
+
+        let years = start.whole_years_until(end)?;
+        if years > 0 {
+            let passed = start.add_years(years)?.to_julian_day()?;
+            let next = start.add_years(years + 1)?.to_julian_day()?;
+            return Ok(RoughTime::from_years(tense, years, end_day - passed, next - passed));
+        }
+
+        let months = start.whole_months_until(end)?;
+        let passed = start.add_months(months)?.to_julian_day()?;
+        let next = start.add_months(months + 1)?.to_julian_day()?;
+        let months = months + u32::from(2 * (end_day - passed) > next - passed);
+        if months == 0 {
+            return Ok(RoughTime::relative_to(time, now));
+        }
+        if start.add_months(months)? == start.add_years(1)? {
+            return Ok(RoughTime::from_parts(tense, TimeUnit::Years, 1));
+        }
+        Ok(RoughTime::from_parts(tense, TimeUnit::Months, months))
+    }
+
+    // `years` whole calendar years and `days` days into a year of
+    // `year_length`, in years, decades or centuries, whichever is the
+    // largest that fits. The count is rounded to nearest with ties down,
+    // and as with from_duration(), rounding up to a whole one of the next
+    // unit gives that unit instead.
+    fn from_years(tense: Tense, years: i64, days: i64, year_length: i64) -> RoughTime {
+        let (units, count) = if years >= 100 {
+            (TimeUnit::Centuries, round_years(years, days, 100))
+        } else if years >= 10 {
+            match round_years(years, days, 10) {
+                10 => (TimeUnit::Centuries, 1),
+                decades => (TimeUnit::Decades, decades)
+            }
+        } else {
+            match years + i64::from(2 * days > year_length) {
+                10 => (TimeUnit::Decades, 1),
+                years => (TimeUnit::Years, years)
+            }
+        };
+        RoughTime::from_parts(tense, units, u32::try_from(count).unwrap_or(u32::MAX))
+    }
+}
+
+// `years` whole years, plus `days` more, in units of `per_unit` years,
+// rounded to nearest with ties down. A fraction of a year only matters when
+// the whole years fall exactly halfway.
+fn round_years(years: i64, days: i64, per_unit: i64) -> i64 {
+    let (whole, rest) = (years / per_unit, years % per_unit);
+    whole + i64::from(2 * rest > per_unit || (2 * rest == per_unit && days > 0))
+}
+
+fn date_of(time: SystemTime, calendar: Calendar) -> Result<CalendarDate, DateError> {
+    CalendarDate::from_julian_day(calendar, UNIX_EPOCH_JULIAN_DAY + local_day(time, 0))
+}
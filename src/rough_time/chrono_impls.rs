@@ -0,0 +1,44 @@
+use chrono::{DateTime, TimeDelta, TimeZone};
+
+use super::{RoughTime, SystemClock, Tense};
+
+// A chrono::Duration is signed, so its sign gives the tense: a positive span
+// lies in the future and a negative one in the past. Like from_duration(),
+// this only rounds the span; it applies no JustNow window.
+impl From<TimeDelta> for RoughTime {
+    fn from(delta: TimeDelta) -> RoughTime {
+        let tense = if delta < TimeDelta::zero() { Tense::Past } else { Tense::Future };
+        // A TimeDelta's range is symmetric, so its absolute value always
+        // converts.
+        let span = delta.abs().to_std().expect("absolute TimeDelta is non-negative");
+        RoughTime::from_duration(span, tense)
+    }
+}
+
+// A point in time is described relative to the current time, in the same way
+// as RoughTime::relative_to(). RoughTime::relative_to_clock() does the same
+// against any clock.
+impl<Tz: TimeZone> From<DateTime<Tz>> for RoughTime {
+    fn from(time: DateTime<Tz>) -> RoughTime {
+        RoughTime::relative_to_clock(time, &SystemClock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use chrono::{DateTime, Utc};
+
+    use super::super::{FixedClock, TimeUnit};
+    use super::*;
+
+    #[test]
+    fn date_time_relative_to_a_fixed_clock() {
+        let clock = FixedClock::new(UNIX_EPOCH + Duration::from_secs(1_000_000));
+        let time = DateTime::<Utc>::from_timestamp(1_000_000 - 3 * 3_600, 0).unwrap();
+        assert_eq!(RoughTime::relative_to_clock(time, &clock), RoughTime::InThePast(TimeUnit::Hours, 3));
+        clock.advance(Duration::from_secs(2 * 86_400));
+        assert_eq!(RoughTime::relative_to_clock(time, &clock), RoughTime::InThePast(TimeUnit::Days, 2));
+    }
+}
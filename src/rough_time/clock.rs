@@ -0,0 +1,63 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+// Where the current time comes from, for everything that describes
+// timestamps relative to now. Code that reads the clock itself can't be
+// tested at the edges, since "3 days ago" can tip over into "4 days ago"
+// between reading the clock and checking the result; passing a FixedClock
+// instead makes now whatever the test says it is.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+// The real clock, SystemTime::now(). What the APIs that don't take a clock
+// use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+// A clock that only moves when it's told to. It can be moved through a
+// shared reference, so a test can hand an Rc<FixedClock> to a Humanizer or
+// Countdown and keep another to move time along underneath it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixedClock {
+    now: Cell<SystemTime>
+}
+
+impl FixedClock {
+    pub fn new(now: SystemTime) -> FixedClock {
+        FixedClock { now: Cell::new(now) }
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Rc<C> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
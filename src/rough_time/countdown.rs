@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime};
+
+use super::system_time::first_change;
+use super::{Clock, RoughTime, SystemClock};
+
+// Counts down to a moment in the future, for a UI that shows "in 5 minutes"
+// and needs to know when to redraw it. Each tick says what to show now and
+// how long it will be until that changes, so the UI can sleep for exactly
+// that long rather than polling every second.
+//
+// As an iterator, each call to next() reads the clock and returns a fresh
+// tick. Once the target has been reached it returns a final JustNow tick and
+// then stops. The clock is the system's unless another is given.
+pub struct Countdown<C = SystemClock> {
+    target: SystemTime,
+    clock: C,
+    finished: bool
+}
+
+// What a countdown shows at one moment: the time remaining, and how long
+// until that changes, or None once it has reached JustNow and never will.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tick {
+    pub time: RoughTime,
+    pub next_change: Option<Duration>
+}
+
+impl Countdown {
+    pub fn new(target: SystemTime) -> Countdown {
+        Countdown::with_clock(target, SystemClock)
+    }
+}
+
+impl<C: Clock> Countdown<C> {
+    pub fn with_clock(target: SystemTime, clock: C) -> Countdown<C> {
+        Countdown { target, clock, finished: false }
+    }
+
+    // The tick as of `now`, without touching the clock.
+    pub fn tick(&self, now: SystemTime) -> Tick {
+        let remaining = self.target.duration_since(now).unwrap_or_default();
+        Tick {
+            time: RoughTime::future_or_just_now(remaining),
+            next_change: time_until_change(remaining)
+        }
+    }
+}
+
+impl<C: Clock> Iterator for Countdown<C> {
+    type Item = Tick;
+
+    fn next(&mut self) -> Option<Tick> {
+        if self.finished {
+            return None;
+        }
+        let tick = self.tick(self.clock.now());
+        self.finished = tick.next_change.is_none();
+        Some(tick)
+    }
+}
+
+impl RoughTime {
+    pub fn countdown(target: SystemTime) -> Countdown {
+        Countdown::new(target)
+    }
+}
+
+// How long it takes, with `remaining` left to go, for the time shown to
+// change, or None if it already shows JustNow. Waiting out all of
+// `remaining` always reaches JustNow.
+fn time_until_change(remaining: Duration) -> Option<Duration> {
+    let showing = RoughTime::future_or_just_now(remaining);
+    if showing == RoughTime::JustNow {
+        return None;
+    }
+    Some(first_change(remaining, |wait| RoughTime::future_or_just_now(remaining - wait) == showing))
+}
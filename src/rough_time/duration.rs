@@ -0,0 +1,154 @@
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use super::{Approximation, QualifiedTime, Rounding, RoughTime, Tense, TimeUnit};
+
+impl RoughTime {
+    // Describes a span of time before or after now in whichever unit reads
+    // most naturally: the largest unit that fits into it at least once, with
+    // the count rounded to the nearest whole number. So 47 hours is "2 days",
+    // and 90 seconds, exactly halfway, rounds down to "a minute". When
+    // rounding brings the count up to a whole one of the next unit, that unit
+    // is used instead, giving "a week" rather than "7 days".
+    //
+    // Anything under a second is JustNow, and counts too large for a u32
+    // are capped at u32::MAX.
+    pub fn from_duration(duration: Duration, tense: Tense) -> RoughTime {
+        RoughTime::from_nanos(duration.as_nanos(), tense, Rounding::Nearest)
+    }
+
+    // from_duration(), rounding the count as `rounding` says.
+    pub fn from_duration_rounded(duration: Duration, tense: Tense, rounding: Rounding) -> RoughTime {
+        RoughTime::from_nanos(duration.as_nanos(), tense, rounding)
+    }
+
+    // from_duration_rounded(), also saying whether the result was exact and,
+    // if not, how it was rounded, so it can be written with a qualifier like
+    // "about 2 hours ago".
+    pub fn from_duration_qualified(duration: Duration,
+                                   tense: Tense,
+                                   rounding: Rounding) -> QualifiedTime {
+        let nanos = duration.as_nanos();
+        let time = RoughTime::from_nanos(nanos, tense, rounding);
+        let stated = time.offset_nanos().unsigned_abs();
+        let capped = time.count() == Some(u32::MAX);
+        QualifiedTime { time, approximation: approximation(stated, nanos, capped, rounding) }
+    }
+
+    pub fn in_past(duration: Duration) -> RoughTime {
+        RoughTime::from_duration(duration, Tense::Past)
+    }
+
+    pub fn in_future(duration: Duration) -> RoughTime {
+        RoughTime::from_duration(duration, Tense::Future)
+    }
+
+    // Re-expresses this time in the unit from_duration() would have chosen
+    // for it, so InTheFuture(Minutes, 90) becomes InTheFuture(Hours, 1) and
+    // InThePast(Days, 400) becomes InThePast(Years, 1). A count of zero
+    // becomes JustNow.
+    pub fn normalize(self) -> RoughTime {
+        self.normalize_with(Rounding::Nearest)
+    }
+
+    // normalize(), rounding the new count as `rounding` says.
+    pub fn normalize_with(self, rounding: Rounding) -> RoughTime {
+        let (units, count, tense) = match self {
+            RoughTime::InThePast(units, count) => (units, count, Tense::Past),
+            RoughTime::JustNow => return RoughTime::JustNow,
+            RoughTime::InTheFuture(units, count) => (units, count, Tense::Future)
+        };
+        RoughTime::from_nanos(u128::from(count) * units.nanos(), tense, rounding)
+    }
+
+    pub(super) fn from_nanos(nanos: u128, tense: Tense, rounding: Rounding) -> RoughTime {
+        match approximate(nanos, rounding) {
+            None => RoughTime::JustNow,
+            Some((units, count)) => RoughTime::from_parts(tense, units, count)
+        }
+    }
+
+    pub(super) fn from_parts(tense: Tense, units: TimeUnit, count: u32) -> RoughTime {
+        match tense {
+            Tense::Past => RoughTime::InThePast(units, count),
+            Tense::Future => RoughTime::InTheFuture(units, count)
+        }
+    }
+}
+
+// How a time stated as `stated` nanoseconds relates to the `actual` span it
+// was rounded from with `rounding`. A count `capped` at u32::MAX that falls
+// short is AtLeast, whatever the rounding.
+pub(super) fn approximation(stated: u128,
+                            actual: u128,
+                            capped: bool,
+                            rounding: Rounding) -> Approximation {
+    if stated == actual {
+        Approximation::Exact
+    } else if stated < actual && capped {
+        Approximation::AtLeast
+    } else {
+        match rounding {
+            Rounding::Nearest => Approximation::About,
+            Rounding::Down => Approximation::Over,
+            Rounding::Up => Approximation::Almost
+        }
+    }
+}
+
+// Picks the unit and count for a span of `nanos` nanoseconds, or None if the
+// span is shorter than the smallest unit. We work in nanoseconds so that the
+// fraction of a second in a Duration counts towards the rounding too.
+pub(super) fn approximate(nanos: u128, rounding: Rounding) -> Option<(TimeUnit, u32)> {
+    let index = TimeUnit::ALL.iter().rposition(|&units| nanos >= units.nanos())?;
+    let units = TimeUnit::ALL[index];
+
+    let size = units.nanos();
+    let count = match rounding {
+        // Ties go down.
+        Rounding::Nearest => (nanos + (size - 1) / 2) / size,
+        Rounding::Down => nanos / size,
+        Rounding::Up => nanos.div_ceil(size)
+    };
+
+    if let Some(&next) = TimeUnit::ALL.get(index + 1) {
+        if count * size >= next.nanos() {
+            return Some((next, 1));
+        }
+    }
+    Some((units, u32::try_from(count).unwrap_or(u32::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn duration_max_caps_the_count_at_least() {
+        let qualified =
+            RoughTime::from_duration_qualified(Duration::MAX, Tense::Past, Rounding::Nearest);
+        assert_eq!(qualified.time, RoughTime::InThePast(TimeUnit::Centuries, u32::MAX));
+        assert_eq!(qualified.approximation, Approximation::AtLeast);
+        assert_eq!(qualified.time.to_string(), "4294967295 centuries ago");
+        for rounding in [Rounding::Down, Rounding::Up] {
+            let time = RoughTime::from_duration_rounded(Duration::MAX, Tense::Future, rounding);
+            assert_eq!(time, RoughTime::InTheFuture(TimeUnit::Centuries, u32::MAX));
+        }
+    }
+
+    #[test]
+    fn u32_max_counts_convert_without_overflow() {
+        for units in TimeUnit::ALL {
+            let time = RoughTime::InThePast(units, u32::MAX);
+            let duration = units.as_duration(u32::MAX);
+            assert_eq!(time.offset_nanos(), -(duration.as_nanos() as i128));
+            // The same span, re-expressed in the largest unit that fits, and
+            // never in a smaller one.
+            let normalized = time.normalize();
+            assert!(normalized.units() >= Some(units));
+            assert_eq!(normalized, RoughTime::from_duration(duration, Tense::Past));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use core::fmt::{self, Write};
+
+use super::{English, Locale, Rounding, RoughTime, Tense, TimeUnit};
+
+// A way of writing out a RoughTime. Display always uses Long; to pick a style
+// for one particular call, pass a formatter to RoughTime::display_with():
+//
+//     println!("{}", rt.display_with(&Short));
+pub trait RoughTimeFormatter {
+    fn write_rough_time(&self, rt: RoughTime, out: &mut dyn Write) -> fmt::Result;
+
+    // Writes a time given in several units, largest first, as from
+    // precise_parts(). A formatter with no way of showing more than one unit
+    // can leave this out, and the parts are rounded to the nearest one unit
+    // and written with write_rough_time().
+    fn write_precise(&self,
+                     tense: Tense,
+                     parts: &[(TimeUnit, u32)],
+                     out: &mut dyn Write) -> fmt::Result {
+        let nanos = parts.iter().map(|&(units, count)| u128::from(count) * units.nanos()).sum();
+        self.write_rough_time(RoughTime::from_nanos(nanos, tense, Rounding::Nearest), out)
+    }
+}
+
+// Full English phrases: "3 days ago", "an hour from now", "just now".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Long;
+
+impl RoughTimeFormatter for Long {
+    fn write_rough_time(&self, rt: RoughTime, mut out: &mut dyn Write) -> fmt::Result {
+        rt.write_english(&mut out)
+    }
+
+    fn write_precise(&self,
+                     tense: Tense,
+                     parts: &[(TimeUnit, u32)],
+                     out: &mut dyn Write) -> fmt::Result {
+        English.write_precise(tense, parts, out)
+    }
+}
+
+// Compact output for tight spaces like chat timestamps: "3d" for three days
+// ago, "in 2h" for two hours from now, and "now".
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Short;
+
+impl RoughTimeFormatter for Short {
+    fn write_rough_time(&self, rt: RoughTime, out: &mut dyn Write) -> fmt::Result {
+        match rt {
+            RoughTime::InThePast(units, count) =>
+                write!(out, "{}{}", count, units.abbreviation()),
+            RoughTime::JustNow =>
+                out.write_str("now"),
+            RoughTime::InTheFuture(units, count) =>
+                write!(out, "in {}{}", count, units.abbreviation())
+        }
+    }
+
+    // "2h 15m", and "in 2h 15m" in the future.
+    fn write_precise(&self,
+                     tense: Tense,
+                     parts: &[(TimeUnit, u32)],
+                     out: &mut dyn Write) -> fmt::Result {
+        if parts.is_empty() {
+            return out.write_str("now");
+        }
+        if tense == Tense::Future {
+            out.write_str("in ")?;
+        }
+        for (i, &(units, count)) in parts.iter().enumerate() {
+            if i > 0 {
+                out.write_char(' ')?;
+            }
+            write!(out, "{}{}", count, units.abbreviation())?;
+        }
+        Ok(())
+    }
+}
+
+// Displays a RoughTime using a particular formatter. Returned by
+// RoughTime::display_with().
+pub struct DisplayWith<'a, F: ?Sized> {
+    rt: RoughTime,
+    formatter: &'a F
+}
+
+impl<'a, F: RoughTimeFormatter + ?Sized> fmt::Display for DisplayWith<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.formatter.write_rough_time(self.rt, f)
+    }
+}
+
+impl RoughTime {
+    pub fn display_with<F: RoughTimeFormatter + ?Sized>(self, formatter: &F) -> DisplayWith<'_, F> {
+        DisplayWith { rt: self, formatter }
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::calendar::{Calendar, CalendarDate};
+
+use super::calendar::UNIX_EPOCH_JULIAN_DAY;
+use super::RoughTime;
+
+impl RoughTime {
+    // Renders this time for a web page, the use the chapter opens with, as an
+    // HTML <time> element that carries the exact moment in machine-readable
+    // form alongside the rough text:
+    //
+    //     <time datetime="2024-05-01T12:00:00Z">3 days ago</time>
+    //
+    // `exact` is written in UTC to the second. A moment so far off that its
+    // year doesn't fit in an i32 is left out, and the element carries only
+    // the text.
+    pub fn to_html(&self, exact: SystemTime) -> String {
+        let mut html = String::from("<time");
+        if let Some(timestamp) = utc_timestamp(exact) {
+            html.push_str(" datetime=\"");
+            html.push_str(&timestamp);
+            html.push('"');
+        }
+        html.push('>');
+        escape_html(&mut html, &self.to_string());
+        html.push_str("</time>");
+        html
+    }
+}
+
+// `time` as an RFC 3339 timestamp in UTC, such as "2024-05-01T12:00:00Z",
+// the format the datetime attribute expects.
+fn utc_timestamp(time: SystemTime) -> Option<String> {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_secs() as i64,
+        // Round times before the epoch down, not towards it.
+        Err(err) => {
+            let before = err.duration();
+            -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+        }
+    };
+    let days = seconds.div_euclid(86_400);
+    let second_of_day = seconds.rem_euclid(86_400);
+    let date = CalendarDate::from_julian_day(Calendar::Gregorian, UNIX_EPOCH_JULIAN_DAY + days)
+        .ok()?;
+    // Years before 1 BC come out with a sign, as ISO 8601 writes them.
+    let sign = if date.year < 0 { "-" } else { "" };
+    let mut timestamp = String::new();
+    write!(timestamp, "{}{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+           sign, date.year.unsigned_abs(), date.month, date.day,
+           second_of_day / 3600, second_of_day / 60 % 60, second_of_day % 60)
+        .expect("writing to a String can't fail");
+    Some(timestamp)
+}
+
+// Appends `text` with the characters that mean something in HTML replaced by
+// character references, so it's safe in both element content and quoted
+// attribute values.
+fn escape_html(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use super::super::TimeUnit;
+
+    fn html_at(seconds: i64) -> String {
+        let time = if seconds < 0 {
+            UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+        } else {
+            UNIX_EPOCH + Duration::from_secs(seconds as u64)
+        };
+        RoughTime::JustNow.to_html(time)
+    }
+
+    #[test]
+    fn the_exact_time_is_written_in_utc() {
+        assert_eq!(html_at(0), r#"<time datetime="1970-01-01T00:00:00Z">just now</time>"#);
+        assert_eq!(html_at(1_714_564_800),
+                   r#"<time datetime="2024-05-01T12:00:00Z">just now</time>"#);
+        // 29 February in a leap year, a second before midnight.
+        assert_eq!(html_at(951_868_799),
+                   r#"<time datetime="2000-02-29T23:59:59Z">just now</time>"#);
+        assert_eq!(html_at(-1), r#"<time datetime="1969-12-31T23:59:59Z">just now</time>"#);
+        // 1 BC is year 0, and the year before it is -1.
+        assert_eq!(html_at(-62_198_755_200),
+                   r#"<time datetime="-0001-01-01T00:00:00Z">just now</time>"#);
+    }
+
+    #[test]
+    fn times_too_far_off_have_no_datetime() {
+        assert_eq!(html_at(i64::MAX / 2), "<time>just now</time>");
+    }
+
+    #[test]
+    fn the_text_is_escaped() {
+        let html = RoughTime::InThePast(TimeUnit::Days, 3).to_html(UNIX_EPOCH);
+        assert_eq!(html, r#"<time datetime="1970-01-01T00:00:00Z">3 days ago</time>"#);
+        let mut escaped = String::new();
+        escape_html(&mut escaped, r#"<a href="x">Tom & Jerry's</a>"#);
+        assert_eq!(escaped, "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;");
+    }
+}
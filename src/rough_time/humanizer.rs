@@ -0,0 +1,299 @@
+use std::fmt::Write;
+use std::mem;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::duration::approximation;
+use super::{precise_parts, Approximation, Clock, English, Locale, Rounding, RoughTime,
+            RoughTimeFormatter, Short, SystemClock, Tense, TimeUnit, UnitNames, Weekday};
+
+// All the choices involved in turning a timestamp into words, made once and
+// then applied to every timestamp:
+//
+//     let humanizer = Humanizer::builder()
+//         .rounding(Rounding::Down)
+//         .qualifiers(true)
+//         .just_now(Duration::from_secs(30))
+//         .build();
+//     let text = humanizer.humanize(post.created_at);   // "over 2 hours ago"
+//
+// The defaults match RoughTime::relative_to() and Display: English, full
+// phrases, rounding to nearest with no qualifiers, and a JustNow window of
+// RoughTime::JUST_NOW_WINDOW, against the system clock.
+pub struct Humanizer {
+    clock: Box<dyn Clock>,
+    locale: Box<dyn Locale>,
+    formatter: Option<Box<dyn RoughTimeFormatter>>,
+    just_now: Duration,
+    rounding: Rounding,
+    qualifiers: bool,
+    precision: usize,
+    calendar_days: Option<i32>
+}
+
+pub struct HumanizerBuilder {
+    humanizer: Humanizer,
+    unit_names: Vec<(TimeUnit, String, String)>
+}
+
+impl Humanizer {
+    pub fn builder() -> HumanizerBuilder {
+        HumanizerBuilder { humanizer: Humanizer::default(), unit_names: Vec::new() }
+    }
+
+    // Describes `time` relative to the time on the humanizer's clock.
+    pub fn humanize(&self, time: SystemTime) -> String {
+        self.humanize_at(time, self.clock.now())
+    }
+
+    // Describes `time` relative to `now`.
+    pub fn humanize_at(&self, time: SystemTime, now: SystemTime) -> String {
+        let (span, tense) = match now.duration_since(time) {
+            Ok(elapsed) => (elapsed, Tense::Past),
+            Err(err) => (err.duration(), Tense::Future)
+        };
+
+        let mut text = String::new();
+        if span < self.just_now {
+            self.write(RoughTime::JustNow, &mut text).expect("writing to a String can't fail");
+            return text;
+        }
+        // A moment ago is just now even if it was before midnight.
+        if let (Some(utc_offset), Tense::Past) = (self.calendar_days, tense) {
+            if let Some(text) = self.calendar_day(time, now, utc_offset) {
+                return text;
+            }
+        }
+
+        let written = if self.precision > 1 {
+            let parts = precise_parts(span, self.precision, self.rounding);
+            match self.formatter {
+                Some(ref formatter) =>
+                    formatter.write_precise(tense, &parts, &mut text),
+                None => {
+                    if self.qualifiers && !parts.is_empty() {
+                        let approximation = precise_approximation(&parts, span, self.rounding);
+                        let qualifier = self.locale.qualifier(approximation);
+                        if !qualifier.is_empty() {
+                            text.push_str(qualifier);
+                            text.push(' ');
+                        }
+                    }
+                    self.locale.write_precise(tense, &parts, &mut text)
+                }
+            }
+        } else {
+            let qualified = RoughTime::from_duration_qualified(span, tense, self.rounding);
+            match self.formatter {
+                Some(ref formatter) =>
+                    formatter.write_rough_time(qualified.time, &mut text),
+                None if self.qualifiers =>
+                    self.locale.write_qualified(qualified.time, qualified.approximation, &mut text),
+                None =>
+                    self.locale.write_rough_time(qualified.time, &mut text)
+            }
+        };
+        written.expect("writing to a String can't fail");
+        text
+    }
+
+    // Names the day `time` fell on, if it was on an earlier day than `now`
+    // but less than a week before, and the locale has the words for it.
+    fn calendar_day(&self, time: SystemTime, now: SystemTime, utc_offset: i32) -> Option<String> {
+        let day = local_day(time, utc_offset);
+        let days_ago = local_day(now, utc_offset) - day;
+        let name = match days_ago {
+            1 => self.locale.yesterday()?,
+            2..=6 => self.locale.weekday_name(Weekday::from_unix_day(day))?,
+            _ => return None
+        };
+        Some(name.to_string())
+    }
+
+    fn write(&self, rt: RoughTime, out: &mut dyn Write) -> std::fmt::Result {
+        match self.formatter {
+            Some(ref formatter) => formatter.write_rough_time(rt, out),
+            None => self.locale.write_rough_time(rt, out)
+        }
+    }
+}
+
+impl Default for Humanizer {
+    fn default() -> Humanizer {
+        Humanizer {
+            clock: Box::new(SystemClock),
+            locale: Box::new(English),
+            formatter: None,
+            just_now: RoughTime::JUST_NOW_WINDOW,
+            rounding: Rounding::Nearest,
+            qualifiers: false,
+            precision: 1,
+            calendar_days: None
+        }
+    }
+}
+
+impl HumanizerBuilder {
+    // Where humanize() gets the current time from.
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> HumanizerBuilder {
+        self.humanizer.clock = Box::new(clock);
+        self
+    }
+
+    // The language for full phrases.
+    pub fn locale<L: Locale + 'static>(mut self, locale: L) -> HumanizerBuilder {
+        self.humanizer.locale = Box::new(locale);
+        self
+    }
+
+    // Writes full phrases in the chosen locale: "3 days ago". The default.
+    pub fn long(mut self) -> HumanizerBuilder {
+        self.humanizer.formatter = None;
+        self
+    }
+
+    // Writes compact output with the Short formatter: "3d".
+    pub fn short(self) -> HumanizerBuilder {
+        self.formatter(Short)
+    }
+
+    // Writes output with any formatter, in place of the locale's phrases.
+    pub fn formatter<F: RoughTimeFormatter + 'static>(mut self, formatter: F) -> HumanizerBuilder {
+        self.humanizer.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    // How close to now a time must be to be described as JustNow.
+    pub fn just_now(mut self, window: Duration) -> HumanizerBuilder {
+        self.humanizer.just_now = window;
+        self
+    }
+
+    pub fn rounding(mut self, rounding: Rounding) -> HumanizerBuilder {
+        self.humanizer.rounding = rounding;
+        self
+    }
+
+    // Whether to say how the time was rounded: "about 3 days ago". Only full
+    // phrases are qualified.
+    pub fn qualifiers(mut self, qualifiers: bool) -> HumanizerBuilder {
+        self.humanizer.qualifiers = qualifiers;
+        self
+    }
+
+    // How many units to use, as for format_precise(). Precise output goes
+    // through the locale or formatter like any other, with the rounding
+    // applied to the smallest unit used and a qualifier, if asked for, saying
+    // how that came out. A formatter that can't show several units shows one.
+    pub fn precision(mut self, precision: usize) -> HumanizerBuilder {
+        self.humanizer.precision = precision;
+        self
+    }
+
+    // Describes past times from earlier days by the day instead of by the
+    // hours since: "yesterday" for the day before today, and the name of the
+    // day, "Tuesday", for the rest of the past week. Today's times are
+    // described as usual. Days run from midnight to midnight in the time zone
+    // `utc_offset` seconds ahead of UTC, which should be the reader's, since
+    // it's their idea of yesterday that matters. Only locales that provide
+    // the words for days use them.
+    pub fn calendar_days(mut self, utc_offset: i32) -> HumanizerBuilder {
+        self.humanizer.calendar_days = Some(utc_offset);
+        self
+    }
+
+    // Uses `one` and `other` as the names of `units` in full phrases, in
+    // whichever locale is chosen, as UnitNames does.
+    pub fn unit_name(mut self, units: TimeUnit, one: &str, other: &str) -> HumanizerBuilder {
+        self.unit_names.push((units, String::from(one), String::from(other)));
+        self
+    }
+
+    pub fn build(mut self) -> Humanizer {
+        if !self.unit_names.is_empty() {
+            let locale = mem::replace(&mut self.humanizer.locale, Box::new(English));
+            let mut names = UnitNames::wrap(locale);
+            for (units, one, other) in &self.unit_names {
+                names.set(*units, one, other);
+            }
+            self.humanizer.locale = Box::new(names);
+        }
+        self.humanizer
+    }
+}
+
+// The number of the day `time` falls on in the time zone `utc_offset`
+// seconds ahead of UTC, counting from 1970-01-01 in that zone.
+pub(super) fn local_day(time: SystemTime, utc_offset: i32) -> i64 {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => after.as_secs() as i64,
+        // A time before the epoch with a fraction of a second falls in the
+        // second before its whole seconds.
+        Err(err) => {
+            let before = err.duration();
+            -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+        }
+    };
+    (seconds + i64::from(utc_offset)).div_euclid(86_400)
+}
+
+// How `parts`, from precise_parts(), relate to the `duration` they were
+// worked out from, as from_duration_qualified() says for a single unit.
+fn precise_approximation(parts: &[(TimeUnit, u32)],
+                         duration: Duration,
+                         rounding: Rounding) -> Approximation {
+    let stated = parts.iter().map(|&(units, count)| u128::from(count) * units.nanos()).sum();
+    let capped = parts.first().is_some_and(|&(_, count)| count == u32::MAX);
+    approximation(stated, duration.as_nanos(), capped, rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_day_before_the_epoch() {
+        let before = |secs, nanos| UNIX_EPOCH - Duration::new(secs, nanos);
+        assert_eq!(local_day(before(0, 1), 0), -1);
+        assert_eq!(local_day(before(1, 0), 0), -1);
+        assert_eq!(local_day(before(86_400, 0), 0), -1);
+        assert_eq!(local_day(before(86_400, 1), 0), -2);
+        assert_eq!(local_day(before(1, 0), 3_600), 0);
+    }
+
+    #[test]
+    fn just_now_wins_over_yesterday() {
+        let midnight = UNIX_EPOCH + Duration::from_secs(20 * 86_400);
+        let humanizer = Humanizer::builder().calendar_days(0).build();
+        let now = midnight + Duration::from_secs(10);
+        assert_eq!(humanizer.humanize_at(midnight - Duration::from_secs(10), now), "just now");
+        assert_eq!(humanizer.humanize_at(midnight - Duration::from_secs(3_600), now), "yesterday");
+    }
+
+    #[test]
+    fn precise_output_goes_through_the_locale_and_formatter() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let earlier = now - Duration::from_secs(2 * 3_600 + 15 * 60 + 20);
+        let english = Humanizer::builder().precision(2).build();
+        assert_eq!(english.humanize_at(earlier, now), "2 hours 15 minutes ago");
+        let rounded_up = Humanizer::builder()
+            .precision(2)
+            .rounding(Rounding::Up)
+            .qualifiers(true)
+            .build();
+        assert_eq!(rounded_up.humanize_at(earlier, now), "almost 2 hours 16 minutes ago");
+        let short = Humanizer::builder().precision(2).short().build();
+        let later = now + Duration::from_secs(2 * 3_600 + 15 * 60);
+        assert_eq!(short.humanize_at(later, now), "in 2h 15m");
+    }
+
+    #[cfg(feature = "locales")]
+    #[test]
+    fn precise_output_in_another_language() {
+        use super::super::Spanish;
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let humanizer = Humanizer::builder().precision(2).locale(Spanish).build();
+        assert_eq!(humanizer.humanize_at(now - Duration::from_secs(3 * 86_400 + 3_600), now),
+                   "hace 3 días 1 hora");
+    }
+}
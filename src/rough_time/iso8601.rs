@@ -0,0 +1,179 @@
+use alloc::format;
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt;
+
+use super::{RoughTime, Tense, TimeUnit};
+
+impl RoughTime {
+    // Writes this time as an ISO 8601 duration, for APIs that speak them:
+    // "PT1H" for an hour from now, "-P3D" for 3 days ago. ISO 8601 itself
+    // has no negative durations, but the leading minus of ISO 8601-2 is
+    // what most libraries that accept signed durations read. JustNow is
+    // "PT0S". Decades and centuries have no designator of their own, so
+    // they're written in years.
+    pub fn to_iso8601(&self) -> String {
+        let (sign, units, count) = match *self {
+            RoughTime::InThePast(units, count) => ("-", units, count),
+            RoughTime::JustNow => return String::from("PT0S"),
+            RoughTime::InTheFuture(units, count) => ("", units, count)
+        };
+        let count = u64::from(count);
+        match units {
+            TimeUnit::Seconds => format!("{}PT{}S", sign, count),
+            TimeUnit::Minutes => format!("{}PT{}M", sign, count),
+            TimeUnit::Hours => format!("{}PT{}H", sign, count),
+            TimeUnit::Days => format!("{}P{}D", sign, count),
+            TimeUnit::Weeks => format!("{}P{}W", sign, count),
+            TimeUnit::Months => format!("{}P{}M", sign, count),
+            TimeUnit::Years => format!("{}P{}Y", sign, count),
+            TimeUnit::Decades => format!("{}P{}Y", sign, count * 10),
+            TimeUnit::Centuries => format!("{}P{}Y", sign, count * 100)
+        }
+    }
+
+    // Reads the ISO 8601 durations that to_iso8601() writes: an optional
+    // sign, then a single whole-number component, like "P3D", "-PT1H" or
+    // "+P2W". A duration of zero, in any unit, is JustNow. Durations with
+    // more than one component, like "P1DT12H", or with fractions can't be
+    // represented without rounding, so they're rejected rather than
+    // silently changed. Years come back as years, so a time in decades or
+    // centuries reads back as the same length of time in years, unless
+    // there are too many years for a u32 count: then they come back as
+    // decades or centuries, so everything to_iso8601() writes can be read.
+    pub fn from_iso8601(s: &str) -> Result<RoughTime, ParseIso8601Error> {
+        let err = ParseIso8601Error { _priv: () };
+        let (tense, rest) = match s.as_bytes().first() {
+            Some(b'-') => (Tense::Past, &s[1..]),
+            Some(b'+') => (Tense::Future, &s[1..]),
+            _ => (Tense::Future, s)
+        };
+        let rest = rest.strip_prefix('P').ok_or(err.clone())?;
+        let (time, rest) = match rest.strip_prefix('T') {
+            Some(rest) => (true, rest),
+            None => (false, rest)
+        };
+
+        let designator = rest.chars().last().ok_or(err.clone())?;
+        let digits = &rest[..rest.len() - designator.len_utf8()];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(err);
+        }
+        let count: u64 = digits.parse().map_err(|_| err.clone())?;
+        let units = match (time, designator) {
+            (true, 'S') => TimeUnit::Seconds,
+            (true, 'M') => TimeUnit::Minutes,
+            (true, 'H') => TimeUnit::Hours,
+            (false, 'D') => TimeUnit::Days,
+            (false, 'W') => TimeUnit::Weeks,
+            (false, 'M') => TimeUnit::Months,
+            (false, 'Y') => TimeUnit::Years,
+            _ => return Err(err)
+        };
+        if count == 0 {
+            return Ok(RoughTime::JustNow);
+        }
+        // More years than fit in a u32 can only have been written for
+        // decades or centuries, so they're read back as the smallest of
+        // those they come to a whole number of that fits.
+        let scales: &[(TimeUnit, u64)] = match units {
+            TimeUnit::Years =>
+                &[(TimeUnit::Years, 1), (TimeUnit::Decades, 10), (TimeUnit::Centuries, 100)],
+            _ =>
+                &[(units, 1)]
+        };
+        let (units, count) = scales.iter()
+            .filter(|&&(_, years)| count.is_multiple_of(years))
+            .find_map(|&(units, years)| Some((units, u32::try_from(count / years).ok()?)))
+            .ok_or(err)?;
+        Ok(RoughTime::from_parts(tense, units, count))
+    }
+}
+
+// The error returned when a string isn't an ISO 8601 duration that a
+// RoughTime can represent exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIso8601Error {
+    _priv: ()
+}
+
+impl fmt::Display for ParseIso8601Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("not a single-component ISO 8601 duration")
+    }
+}
+
+impl core::error::Error for ParseIso8601Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_unit_up_to_years_round_trips() {
+        let units = [(TimeUnit::Seconds, "PT7S"),
+                     (TimeUnit::Minutes, "PT7M"),
+                     (TimeUnit::Hours, "PT7H"),
+                     (TimeUnit::Days, "P7D"),
+                     (TimeUnit::Weeks, "P7W"),
+                     (TimeUnit::Months, "P7M"),
+                     (TimeUnit::Years, "P7Y")];
+        for &(units, iso) in &units {
+            for count in [1, 7, u32::MAX] {
+                let past = RoughTime::InThePast(units, count);
+                let future = RoughTime::InTheFuture(units, count);
+                for rt in [past, future] {
+                    assert_eq!(RoughTime::from_iso8601(&rt.to_iso8601()), Ok(rt), "{:?}", rt);
+                }
+            }
+            assert_eq!(RoughTime::InTheFuture(units, 7).to_iso8601(), iso);
+            assert_eq!(RoughTime::InThePast(units, 7).to_iso8601(), format!("-{}", iso));
+        }
+    }
+
+    #[test]
+    fn decades_and_centuries_are_written_in_years() {
+        assert_eq!(RoughTime::InThePast(TimeUnit::Decades, 3).to_iso8601(), "-P30Y");
+        assert_eq!(RoughTime::InTheFuture(TimeUnit::Centuries, 2).to_iso8601(), "P200Y");
+        assert_eq!(RoughTime::from_iso8601("-P30Y"),
+                   Ok(RoughTime::InThePast(TimeUnit::Years, 30)));
+        assert_eq!(RoughTime::from_iso8601("P200Y"),
+                   Ok(RoughTime::InTheFuture(TimeUnit::Years, 200)));
+    }
+
+    // Decades and centuries that are more years than a u32 holds read back
+    // as the same length of time, in the smallest unit that fits.
+    #[test]
+    fn more_years_than_a_u32_holds_read_back() {
+        let cases = [(RoughTime::InThePast(TimeUnit::Centuries, 50_000_000),
+                      RoughTime::InThePast(TimeUnit::Decades, 500_000_000)),
+                     (RoughTime::InTheFuture(TimeUnit::Centuries, u32::MAX),
+                      RoughTime::InTheFuture(TimeUnit::Centuries, u32::MAX)),
+                     (RoughTime::InThePast(TimeUnit::Decades, u32::MAX),
+                      RoughTime::InThePast(TimeUnit::Decades, u32::MAX))];
+        for (rt, read) in cases {
+            assert_eq!(RoughTime::from_iso8601(&rt.to_iso8601()), Ok(read), "{:?}", rt);
+        }
+        assert_eq!(RoughTime::InThePast(TimeUnit::Centuries, 50_000_000).to_iso8601(),
+                   "-P5000000000Y");
+        // 2^32 years are neither a u32 count of years nor a whole number of
+        // decades.
+        assert!(RoughTime::from_iso8601("P4294967296Y").is_err());
+        assert!(RoughTime::from_iso8601("P4294967296D").is_err());
+    }
+
+    #[test]
+    fn zero_is_just_now() {
+        assert_eq!(RoughTime::JustNow.to_iso8601(), "PT0S");
+        for iso in ["PT0S", "P0D", "-PT0M", "+P0Y"] {
+            assert_eq!(RoughTime::from_iso8601(iso), Ok(RoughTime::JustNow), "{}", iso);
+        }
+    }
+
+    #[test]
+    fn other_durations_are_errors() {
+        for iso in ["", "P", "3D", "P1DT12H", "P1.5D", "PT3D", "P3S", "P-3D", "P3X", "-"] {
+            assert!(RoughTime::from_iso8601(iso).is_err(), "{}", iso);
+        }
+    }
+}
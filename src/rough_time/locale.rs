@@ -0,0 +1,253 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use super::{Approximation, PluralCategory, PluralRule, RoughTime, Tense, TimeUnit, Weekday};
+
+// Everything needed to put a RoughTime into words in some language: the
+// plural rule for counts, the name of each unit in each plural form, and a
+// template for each kind of phrase.
+//
+// The past and future templates contain the placeholders "{count}" and
+// "{unit}", which are replaced by the count and the unit's name in the form
+// the count calls for. English uses "{count} {unit} ago"; a language that
+// puts its words in another order just writes its templates that way.
+pub trait Locale {
+    fn plural_category(&self, count: u32) -> PluralCategory;
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str;
+    fn just_now(&self) -> &str;
+    fn past_template(&self) -> &str;
+    fn future_template(&self) -> &str;
+
+    // A word to write in place of the count, if the language has one for it,
+    // given the unit and the name that will follow. English uses an article
+    // for a count of one, chosen by the sound of the name: "a day ago", "an
+    // hour ago". Languages with grammatical gender choose by the unit.
+    fn count_word(&self, _count: u32, _units: TimeUnit, _unit: &str) -> Option<&str> {
+        None
+    }
+
+    // Writes `rt` out in this locale. The default puts the pieces above
+    // together; a locale only needs to override it for grammar the templates
+    // can't express.
+    fn write_rough_time(&self, rt: RoughTime, out: &mut dyn Write) -> fmt::Result {
+        let (template, units, count) = match rt {
+            RoughTime::InThePast(units, count) => (self.past_template(), units, count),
+            RoughTime::JustNow => return out.write_str(self.just_now()),
+            RoughTime::InTheFuture(units, count) => (self.future_template(), units, count)
+        };
+        let unit = self.unit_name(units, self.plural_category(count));
+        write_template(out, template, self.count_word(count, units, unit), count, unit)
+    }
+
+    // Writes a time given in several units, largest first, as from
+    // precise_parts(): "2 hours 15 minutes ago". With no parts this is just
+    // now, and with one it's the same as write_rough_time(). Otherwise the
+    // stretch of the template from its first placeholder to the end of its
+    // last is written once for each part, so "hace {count} {unit}" gives
+    // "hace 2 horas 15 minutos". Counts are written as numbers, since "an
+    // hour 15 minutes ago" doesn't read well.
+    fn write_precise(&self,
+                     tense: Tense,
+                     parts: &[(TimeUnit, u32)],
+                     out: &mut dyn Write) -> fmt::Result {
+        match *parts {
+            [] =>
+                return out.write_str(self.just_now()),
+            [(units, count)] =>
+                return self.write_rough_time(RoughTime::from_parts(tense, units, count), out),
+            _ =>
+                ()
+        }
+        let template = match tense {
+            Tense::Past => self.past_template(),
+            Tense::Future => self.future_template()
+        };
+        let (start, end) = match (template.find("{count}"), template.find("{unit}")) {
+            (Some(count), Some(unit)) =>
+                (count.min(unit), (count + "{count}".len()).max(unit + "{unit}".len())),
+            _ =>
+                (0, template.len())
+        };
+        out.write_str(&template[..start])?;
+        for (i, &(units, count)) in parts.iter().enumerate() {
+            if i > 0 {
+                out.write_char(' ')?;
+            }
+            let unit = self.unit_name(units, self.plural_category(count));
+            write_template(out, &template[start..end], None, count, unit)?;
+        }
+        out.write_str(&template[end..])
+    }
+
+    // The word that says how a rounded time relates to the real one, like
+    // English "about" or "almost". The default has no words for any of
+    // them, so a locale that doesn't provide them leaves times unqualified.
+    fn qualifier(&self, _approximation: Approximation) -> &str {
+        ""
+    }
+
+    // The word for the day before today, for calendar-day phrasing. A locale
+    // that returns None, as the default does, is never asked to use it.
+    fn yesterday(&self) -> Option<&str> {
+        None
+    }
+
+    // The name of a day of the week, for calendar-day phrasing, as it would
+    // stand on its own for a time earlier this week.
+    fn weekday_name(&self, _day: Weekday) -> Option<&str> {
+        None
+    }
+
+    // Writes `rt` with its qualifier in front: "about 2 hours ago". JustNow
+    // is never qualified.
+    fn write_qualified(&self,
+                       rt: RoughTime,
+                       approximation: Approximation,
+                       out: &mut dyn Write) -> fmt::Result {
+        let qualifier = self.qualifier(approximation);
+        if !qualifier.is_empty() && rt != RoughTime::JustNow {
+            out.write_str(qualifier)?;
+            out.write_char(' ')?;
+        }
+        self.write_rough_time(rt, out)
+    }
+}
+
+// Copies `template` to `out`, filling in the placeholders, with `count_word`
+// standing in for the count if there is one. Braces that don't start a
+// placeholder are copied through as they are.
+fn write_template(out: &mut dyn Write,
+                  template: &str,
+                  count_word: Option<&str>,
+                  count: u32,
+                  unit: &str) -> fmt::Result {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.write_str(&rest[..start])?;
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{count}") {
+            match count_word {
+                Some(word) => out.write_str(word)?,
+                None => write!(out, "{}", count)?
+            }
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{unit}") {
+            out.write_str(unit)?;
+            rest = after;
+        } else {
+            out.write_char('{')?;
+            rest = &rest[1..];
+        }
+    }
+    out.write_str(rest)
+}
+
+// The default locale, and the one Display uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct English;
+
+impl Locale for English {
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        PluralRule::OneOther.category(count)
+    }
+
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str {
+        match category {
+            PluralCategory::One => units.singular(),
+            _ => units.plural()
+        }
+    }
+
+    fn just_now(&self) -> &str {
+        "just now"
+    }
+
+    fn past_template(&self) -> &str {
+        "{count} {unit} ago"
+    }
+
+    fn future_template(&self) -> &str {
+        "{count} {unit} from now"
+    }
+
+    // The chapter stops at "a hour from now", noting that it's not quite
+    // right. The article depends on the sound the unit starts with, which
+    // for our purposes means a vowel letter, or the silent h of "hour".
+    fn count_word(&self, count: u32, _units: TimeUnit, unit: &str) -> Option<&str> {
+        if count != 1 {
+            return None;
+        }
+        let starts_with_vowel_sound = unit.starts_with(|c: char| "aeiouAEIOU".contains(c))
+            || unit.get(..4).is_some_and(|start| start.eq_ignore_ascii_case("hour"));
+        Some(if starts_with_vowel_sound { "an" } else { "a" })
+    }
+
+    fn qualifier(&self, approximation: Approximation) -> &str {
+        match approximation {
+            Approximation::Exact => "",
+            Approximation::About => "about",
+            Approximation::Over => "over",
+            Approximation::Almost => "almost",
+            Approximation::AtLeast => "at least"
+        }
+    }
+
+    fn yesterday(&self) -> Option<&str> {
+        Some("yesterday")
+    }
+
+    fn weekday_name(&self, day: Weekday) -> Option<&str> {
+        Some(match day {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday"
+        })
+    }
+}
+
+// rough_time_to_english() for any language.
+pub fn rough_time_to_locale<L: Locale + ?Sized>(rt: RoughTime, locale: &L) -> String {
+    let mut phrase = String::new();
+    locale.write_rough_time(rt, &mut phrase)
+        .expect("writing to a String can't fail");
+    phrase
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_article_for_every_unit() {
+        let expected = [
+            (TimeUnit::Seconds, "a second ago", "a second from now"),
+            (TimeUnit::Minutes, "a minute ago", "a minute from now"),
+            (TimeUnit::Hours, "an hour ago", "an hour from now"),
+            (TimeUnit::Days, "a day ago", "a day from now"),
+            (TimeUnit::Weeks, "a week ago", "a week from now"),
+            (TimeUnit::Months, "a month ago", "a month from now"),
+            (TimeUnit::Years, "a year ago", "a year from now"),
+            (TimeUnit::Decades, "a decade ago", "a decade from now"),
+            (TimeUnit::Centuries, "a century ago", "a century from now")
+        ];
+        assert_eq!(expected.len(), TimeUnit::ALL.len());
+        for (units, past, future) in expected {
+            assert_eq!(rough_time_to_locale(RoughTime::InThePast(units, 1), &English), past);
+            assert_eq!(rough_time_to_locale(RoughTime::InTheFuture(units, 1), &English), future);
+        }
+    }
+
+    #[test]
+    fn english_article_follows_the_sound_of_the_name() {
+        assert_eq!(English.count_word(1, TimeUnit::Hours, "hour"), Some("an"));
+        assert_eq!(English.count_word(1, TimeUnit::Hours, "Hour"), Some("an"));
+        assert_eq!(English.count_word(1, TimeUnit::Years, "era"), Some("an"));
+        assert_eq!(English.count_word(1, TimeUnit::Years, "eon"), Some("an"));
+        assert_eq!(English.count_word(1, TimeUnit::Days, "house"), Some("a"));
+        assert_eq!(English.count_word(2, TimeUnit::Hours, "hours"), None);
+    }
+}
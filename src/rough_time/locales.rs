@@ -0,0 +1,204 @@
+// Locales for languages other than English, behind the `locales` feature.
+//
+// All three have grammatical gender, so the article that stands in for a
+// count of one depends on the unit: Spanish "hace un día" but "hace una
+// hora". None of them provide qualifiers yet, since in each the word goes
+// inside the phrase ("il y a environ 2 jours") rather than in front of it,
+// which write_qualified's default can't do.
+
+use super::{Locale, PluralCategory, PluralRule, TimeUnit, Weekday};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Spanish;
+
+impl Locale for Spanish {
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        PluralRule::OneOther.category(count)
+    }
+
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str {
+        let one = category == PluralCategory::One;
+        match units {
+            TimeUnit::Seconds => if one { "segundo" } else { "segundos" },
+            TimeUnit::Minutes => if one { "minuto" } else { "minutos" },
+            TimeUnit::Hours => if one { "hora" } else { "horas" },
+            TimeUnit::Days => if one { "día" } else { "días" },
+            TimeUnit::Weeks => if one { "semana" } else { "semanas" },
+            TimeUnit::Months => if one { "mes" } else { "meses" },
+            TimeUnit::Years => if one { "año" } else { "años" },
+            TimeUnit::Decades => if one { "década" } else { "décadas" },
+            TimeUnit::Centuries => if one { "siglo" } else { "siglos" }
+        }
+    }
+
+    fn just_now(&self) -> &str {
+        "justo ahora"
+    }
+
+    fn past_template(&self) -> &str {
+        "hace {count} {unit}"
+    }
+
+    fn future_template(&self) -> &str {
+        "dentro de {count} {unit}"
+    }
+
+    fn count_word(&self, count: u32, units: TimeUnit, _unit: &str) -> Option<&str> {
+        if count != 1 {
+            return None;
+        }
+        Some(match units {
+            TimeUnit::Hours | TimeUnit::Weeks | TimeUnit::Decades => "una",
+            _ => "un"
+        })
+    }
+
+    fn yesterday(&self) -> Option<&str> {
+        Some("ayer")
+    }
+
+    fn weekday_name(&self, day: Weekday) -> Option<&str> {
+        Some(match day {
+            Weekday::Monday => "el lunes",
+            Weekday::Tuesday => "el martes",
+            Weekday::Wednesday => "el miércoles",
+            Weekday::Thursday => "el jueves",
+            Weekday::Friday => "el viernes",
+            Weekday::Saturday => "el sábado",
+            Weekday::Sunday => "el domingo"
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct French;
+
+impl Locale for French {
+    // French treats zero as singular too: "0 jour".
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        PluralRule::ZeroAndOneOther.category(count)
+    }
+
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str {
+        let one = category == PluralCategory::One;
+        match units {
+            TimeUnit::Seconds => if one { "seconde" } else { "secondes" },
+            TimeUnit::Minutes => if one { "minute" } else { "minutes" },
+            TimeUnit::Hours => if one { "heure" } else { "heures" },
+            TimeUnit::Days => if one { "jour" } else { "jours" },
+            TimeUnit::Weeks => if one { "semaine" } else { "semaines" },
+            TimeUnit::Months => "mois",
+            TimeUnit::Years => if one { "an" } else { "ans" },
+            TimeUnit::Decades => if one { "décennie" } else { "décennies" },
+            TimeUnit::Centuries => if one { "siècle" } else { "siècles" }
+        }
+    }
+
+    fn just_now(&self) -> &str {
+        "à l'instant"
+    }
+
+    fn past_template(&self) -> &str {
+        "il y a {count} {unit}"
+    }
+
+    fn future_template(&self) -> &str {
+        "dans {count} {unit}"
+    }
+
+    fn count_word(&self, count: u32, units: TimeUnit, _unit: &str) -> Option<&str> {
+        if count != 1 {
+            return None;
+        }
+        Some(match units {
+            TimeUnit::Seconds
+            | TimeUnit::Minutes
+            | TimeUnit::Hours
+            | TimeUnit::Weeks
+            | TimeUnit::Decades => "une",
+            _ => "un"
+        })
+    }
+
+    fn yesterday(&self) -> Option<&str> {
+        Some("hier")
+    }
+
+    fn weekday_name(&self, day: Weekday) -> Option<&str> {
+        Some(match day {
+            Weekday::Monday => "lundi",
+            Weekday::Tuesday => "mardi",
+            Weekday::Wednesday => "mercredi",
+            Weekday::Thursday => "jeudi",
+            Weekday::Friday => "vendredi",
+            Weekday::Saturday => "samedi",
+            Weekday::Sunday => "dimanche"
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct German;
+
+impl Locale for German {
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        PluralRule::OneOther.category(count)
+    }
+
+    // Both "vor" and "in" take the dative, so the plurals are the dative
+    // ones: "vor 3 Tagen", not "vor 3 Tage".
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str {
+        let one = category == PluralCategory::One;
+        match units {
+            TimeUnit::Seconds => if one { "Sekunde" } else { "Sekunden" },
+            TimeUnit::Minutes => if one { "Minute" } else { "Minuten" },
+            TimeUnit::Hours => if one { "Stunde" } else { "Stunden" },
+            TimeUnit::Days => if one { "Tag" } else { "Tagen" },
+            TimeUnit::Weeks => if one { "Woche" } else { "Wochen" },
+            TimeUnit::Months => if one { "Monat" } else { "Monaten" },
+            TimeUnit::Years => if one { "Jahr" } else { "Jahren" },
+            TimeUnit::Decades => if one { "Jahrzehnt" } else { "Jahrzehnten" },
+            TimeUnit::Centuries => if one { "Jahrhundert" } else { "Jahrhunderten" }
+        }
+    }
+
+    fn just_now(&self) -> &str {
+        "gerade eben"
+    }
+
+    fn past_template(&self) -> &str {
+        "vor {count} {unit}"
+    }
+
+    fn future_template(&self) -> &str {
+        "in {count} {unit}"
+    }
+
+    // The dative article: "einer" for the feminine units, "einem" for the
+    // masculine and neuter ones.
+    fn count_word(&self, count: u32, units: TimeUnit, _unit: &str) -> Option<&str> {
+        if count != 1 {
+            return None;
+        }
+        Some(match units {
+            TimeUnit::Seconds | TimeUnit::Minutes | TimeUnit::Hours | TimeUnit::Weeks => "einer",
+            _ => "einem"
+        })
+    }
+
+    fn yesterday(&self) -> Option<&str> {
+        Some("gestern")
+    }
+
+    fn weekday_name(&self, day: Weekday) -> Option<&str> {
+        Some(match day {
+            Weekday::Monday => "am Montag",
+            Weekday::Tuesday => "am Dienstag",
+            Weekday::Wednesday => "am Mittwoch",
+            Weekday::Thursday => "am Donnerstag",
+            Weekday::Friday => "am Freitag",
+            Weekday::Saturday => "am Samstag",
+            Weekday::Sunday => "am Sonntag"
+        })
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+#[cfg(feature = "std")]
+mod calendar;
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
+mod countdown;
+mod duration;
+mod format;
+#[cfg(feature = "std")]
+mod html;
+#[cfg(feature = "std")]
+mod humanizer;
+mod iso8601;
+mod locale;
+#[cfg(feature = "locales")]
+mod locales;
+mod parse;
+mod plural;
+mod precise;
+mod qualified;
+mod range;
+#[cfg(feature = "std")]
+mod system_time;
+#[cfg(feature = "time")]
+mod time_impls;
+mod unit;
+mod unit_names;
+mod validate;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "std")]
+pub use self::clock::{Clock, FixedClock, SystemClock};
+#[cfg(feature = "std")]
+pub use self::countdown::{Countdown, Tick};
+pub use self::format::{DisplayWith, Long, RoughTimeFormatter, Short};
+#[cfg(feature = "std")]
+pub use self::humanizer::{Humanizer, HumanizerBuilder};
+pub use self::iso8601::ParseIso8601Error;
+pub use self::locale::{rough_time_to_locale, English, Locale};
+#[cfg(feature = "locales")]
+pub use self::locales::{French, German, Spanish};
+pub use self::parse::ParseRoughTimeError;
+pub use self::plural::{PluralCategory, PluralRule};
+pub use self::precise::{format_precise, precise_parts};
+pub use self::qualified::QualifiedTime;
+pub use self::range::RoughTimeRange;
+pub use self::unit::{ParseTimeUnitError, TimeUnit};
+pub use self::unit_names::UnitNames;
+pub use self::validate::{validate_birth_date, RoughTimeError, TimeError};
+#[cfg(feature = "wasm")]
+pub use self::wasm::{JsHumanizer, JsHumanizerBuilder, JsRoughTime};
+
+// The RoughTime example from the patterns chapter: a point in time described
+// the way a person would, as "3 days ago" or "just now", rather than as an
+// exact timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoughTime {
+    InThePast(TimeUnit, u32),
+    JustNow,
+    InTheFuture(TimeUnit, u32)
+}
+
+impl RoughTime {
+    pub fn is_past(&self) -> bool {
+        matches!(*self, RoughTime::InThePast(_, _))
+    }
+
+    pub fn is_future(&self) -> bool {
+        matches!(*self, RoughTime::InTheFuture(_, _))
+    }
+
+    pub fn units(&self) -> Option<TimeUnit> {
+        match *self {
+            RoughTime::InThePast(units, _) | RoughTime::InTheFuture(units, _) => Some(units),
+            RoughTime::JustNow => None
+        }
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        match *self {
+            RoughTime::InThePast(_, count) | RoughTime::InTheFuture(_, count) => Some(count),
+            RoughTime::JustNow => None
+        }
+    }
+}
+
+// Which side of now a span of time lies on, for the constructors that work
+// out a RoughTime from a span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tense {
+    Past,
+    Future
+}
+
+// How to round when a span of time doesn't come to a whole number of units.
+// Nearest rounds halves down, so that 90 minutes is an hour rather than two.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    Nearest,
+    Down,
+    Up
+}
+
+// How a RoughTime worked out from an exact span relates to that span: Exact
+// if the span was a whole number of units, and otherwise which way it was
+// rounded. Nearest rounding gives About, Down gives Over, since the real
+// span is longer than the count says, and Up gives Almost. AtLeast means the
+// count hit u32::MAX and the real span could be any amount longer.
+//
+// Carried alongside the RoughTime in a QualifiedTime, this lets code act on
+// the uncertainty, say by widening a search window, without having to parse
+// "about" back out of the rendered text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Approximation {
+    Exact,
+    About,
+    Over,
+    Almost,
+    AtLeast
+}
+
+impl Approximation {
+    pub fn is_exact(self) -> bool {
+        self == Approximation::Exact
+    }
+
+    // True if the real span may be longer than the RoughTime says.
+    pub fn may_be_longer(self) -> bool {
+        matches!(self, Approximation::About | Approximation::Over | Approximation::AtLeast)
+    }
+
+    // True if the real span may be shorter than the RoughTime says.
+    pub fn may_be_shorter(self) -> bool {
+        matches!(self, Approximation::About | Approximation::Almost)
+    }
+}
+
+// The days of the week, for calendar-day phrasing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday
+}
+
+impl Weekday {
+    // The day of the week of the day `day` days after 1970-01-01, which was
+    // a Thursday.
+    pub fn from_unix_day(day: i64) -> Weekday {
+        const DAYS: [Weekday; 7] = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday
+        ];
+        DAYS[(day + 3).rem_euclid(7) as usize]
+    }
+}
+
+impl RoughTime {
+    // Writes this time out in English to any fmt::Write, such as a String
+    // being reused across calls or a fixed-size buffer, without allocating
+    // anything along the way. The match from the chapter lives on in
+    // Locale::write_rough_time(), which this calls for the English locale.
+    pub fn write_english(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        English.write_rough_time(*self, w)
+    }
+}
+
+impl fmt::Display for RoughTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_english(f)
+    }
+}
+
+// The chapter's function, kept for code written against it. New code can use
+// the Display impl directly, in format!() or anywhere else.
+pub fn rough_time_to_english(rt: RoughTime) -> String {
+    rt.to_string()
+}
@@ -0,0 +1,144 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::{RoughTime, TimeUnit};
+
+// Reads back the phrases that Display writes, along with a few common
+// variations, so that values shown to users or written to logs can be parsed
+// again. Accepted forms, ignoring ASCII case and extra whitespace:
+//
+//     just now, now
+//     3 days ago, 1 day ago, a day ago, an hour ago
+//     3 days from now, a month from now
+//     in 3 days, in an hour
+//
+// Units can be singular or plural whatever the count, so "2 day ago" is
+// accepted too. A count of zero reads as just now.
+impl FromStr for RoughTime {
+    type Err = ParseRoughTimeError;
+
+    fn from_str(s: &str) -> Result<RoughTime, ParseRoughTimeError> {
+        // No accepted phrase is longer than four words, so there's no need
+        // to allocate to split them up.
+        let mut words = [""; 4];
+        let mut len = 0;
+        for word in s.split_whitespace() {
+            if len == words.len() {
+                return Err(ParseRoughTimeError { _priv: () });
+            }
+            words[len] = word;
+            len += 1;
+        }
+
+        let is = |word: &str, expected: &str| word.eq_ignore_ascii_case(expected);
+        match words[..len] {
+            [just, now] if is(just, "just") && is(now, "now") =>
+                Ok(RoughTime::JustNow),
+            [now] if is(now, "now") =>
+                Ok(RoughTime::JustNow),
+            [count, units, ago] if is(ago, "ago") =>
+                relative(RoughTime::InThePast, count, units),
+            [count, units, from, now] if is(from, "from") && is(now, "now") =>
+                relative(RoughTime::InTheFuture, count, units),
+            [in_, count, units] if is(in_, "in") =>
+                relative(RoughTime::InTheFuture, count, units),
+            _ =>
+                Err(ParseRoughTimeError { _priv: () })
+        }
+    }
+}
+
+// InThePast or InTheFuture, as `variant` says, except that a count of zero,
+// as in "0 days ago", is JustNow, as it is from RoughTime::saturating_new(),
+// rather than a value RoughTime::try_new() would refuse.
+fn relative(variant: fn(TimeUnit, u32) -> RoughTime,
+            count: &str,
+            units: &str) -> Result<RoughTime, ParseRoughTimeError> {
+    let (count, units) = (parse_count(count)?, parse_units(units)?);
+    Ok(if count == 0 { RoughTime::JustNow } else { variant(units, count) })
+}
+
+// A count is either a number or an article standing in for one.
+fn parse_count(word: &str) -> Result<u32, ParseRoughTimeError> {
+    if word.eq_ignore_ascii_case("a") || word.eq_ignore_ascii_case("an") {
+        return Ok(1);
+    }
+    word.parse().map_err(|_| ParseRoughTimeError { _priv: () })
+}
+
+fn parse_units(word: &str) -> Result<TimeUnit, ParseRoughTimeError> {
+    word.parse().map_err(|_| ParseRoughTimeError { _priv: () })
+}
+
+// The error returned when a string isn't a RoughTime phrase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRoughTimeError {
+    _priv: ()
+}
+
+impl fmt::Display for ParseRoughTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("not a recognized relative time")
+    }
+}
+
+impl core::error::Error for ParseRoughTimeError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn display_round_trips_for_every_unit_and_tense() {
+        for &units in TimeUnit::ALL.iter() {
+            for count in [1, 2, 7, 59, 1_000, u32::MAX] {
+                let past = RoughTime::InThePast(units, count);
+                let future = RoughTime::InTheFuture(units, count);
+                for rt in [past, future] {
+                    assert_eq!(rt.to_string().parse(), Ok(rt), "{}", rt);
+                }
+            }
+        }
+        assert_eq!(RoughTime::JustNow.to_string().parse(), Ok(RoughTime::JustNow));
+    }
+
+    #[test]
+    fn articles_stand_for_one() {
+        assert_eq!(RoughTime::InThePast(TimeUnit::Days, 1).to_string(), "a day ago");
+        assert_eq!(RoughTime::InTheFuture(TimeUnit::Hours, 1).to_string(), "an hour from now");
+        for (s, expected) in [("a day ago", RoughTime::InThePast(TimeUnit::Days, 1)),
+                              ("An hour ago", RoughTime::InThePast(TimeUnit::Hours, 1)),
+                              ("a month from now", RoughTime::InTheFuture(TimeUnit::Months, 1)),
+                              ("in an hour", RoughTime::InTheFuture(TimeUnit::Hours, 1)),
+                              ("in a  year", RoughTime::InTheFuture(TimeUnit::Years, 1))] {
+            assert_eq!(s.parse(), Ok(expected), "{}", s);
+        }
+    }
+
+    #[test]
+    fn other_accepted_forms() {
+        for s in ["just now", "  Just   NOW ", "now"] {
+            assert_eq!(s.parse(), Ok(RoughTime::JustNow), "{}", s);
+        }
+        assert_eq!("in 3 days".parse(), Ok(RoughTime::InTheFuture(TimeUnit::Days, 3)));
+        assert_eq!("2 day ago".parse(), Ok(RoughTime::InThePast(TimeUnit::Days, 2)));
+    }
+
+    // A zero count would make a value try_new() refuses, so it's just now.
+    #[test]
+    fn a_zero_count_is_just_now() {
+        for s in ["0 days ago", "0 hours from now", "in 0 minutes"] {
+            assert_eq!(s.parse(), Ok(RoughTime::JustNow), "{}", s);
+        }
+    }
+
+    #[test]
+    fn other_phrases_are_errors() {
+        for s in ["", "ago", "3 days", "a few days ago", "-1 days ago", "3 fortnights ago",
+                  "in 3 days from now", "4294967296 seconds ago"] {
+            assert!(s.parse::<RoughTime>().is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// The plural categories defined by the Unicode CLDR. A language's plural rule
+// sorts every count into one of these, and the language has a different form
+// of each noun for each category it uses. English only uses One and Other,
+// but Russian needs One, Few and Many to get "1 день", "2 дня", "5 дней", and
+// Arabic uses all six.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other
+}
+
+// The CLDR plural rules for whole numbers shared by the most widely used
+// languages, so that a Locale can implement plural_category() by picking
+// one. Each is named after the categories it uses, and lists some of the
+// languages that follow it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralRule {
+    // Every count is Other: Chinese, Japanese, Korean, Thai, Vietnamese.
+    Other,
+    // One for 1: English, German, Dutch, Swedish, Spanish, Italian, Greek.
+    OneOther,
+    // One for 0 and 1: French, Brazilian Portuguese, Hindi.
+    ZeroAndOneOther,
+    // One for 21, 31..., Few for 2-4, 22-24..., Many for the rest: Russian,
+    // Ukrainian, Belarusian.
+    EastSlavic,
+    // One for 1 only, then Few and Many as for EastSlavic: Polish.
+    Polish,
+    // One for 1, Few for 2-4, Other for the rest: Czech, Slovak.
+    CzechSlovak,
+    // Zero, One and Two for 0, 1 and 2, Few when the last two digits are
+    // 3-10, Many when they're 11-99: Arabic.
+    Arabic
+}
+
+impl PluralRule {
+    pub fn category(self, count: u32) -> PluralCategory {
+        let last_digit = count % 10;
+        let last_two_digits = count % 100;
+        let teens = (12..=14).contains(&last_two_digits);
+        match self {
+            PluralRule::Other =>
+                PluralCategory::Other,
+            PluralRule::OneOther =>
+                if count == 1 { PluralCategory::One } else { PluralCategory::Other },
+            PluralRule::ZeroAndOneOther =>
+                if count <= 1 { PluralCategory::One } else { PluralCategory::Other },
+            PluralRule::EastSlavic =>
+                if last_digit == 1 && last_two_digits != 11 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&last_digit) && !teens {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                },
+            PluralRule::Polish =>
+                if count == 1 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&last_digit) && !teens {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                },
+            PluralRule::CzechSlovak =>
+                match count {
+                    1 => PluralCategory::One,
+                    2..=4 => PluralCategory::Few,
+                    _ => PluralCategory::Other
+                },
+            PluralRule::Arabic =>
+                match (count, last_two_digits) {
+                    (0, _) => PluralCategory::Zero,
+                    (1, _) => PluralCategory::One,
+                    (2, _) => PluralCategory::Two,
+                    (_, 3..=10) => PluralCategory::Few,
+                    (_, 11..=99) => PluralCategory::Many,
+                    _ => PluralCategory::Other
+                }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::PluralCategory::{Few, Many, One, Other, Two, Zero};
+
+    const COUNTS: [u32; 10] = [0, 1, 2, 5, 11, 21, 22, 25, 101, 111];
+
+    fn categories(rule: PluralRule) -> [PluralCategory; 10] {
+        let mut categories = [Other; 10];
+        for (category, &count) in categories.iter_mut().zip(COUNTS.iter()) {
+            *category = rule.category(count);
+        }
+        categories
+    }
+
+    // Checked against the CLDR plural rules for a language that follows
+    // each rule.
+    #[test]
+    fn each_rule_matches_cldr() {
+        assert_eq!(categories(PluralRule::Other), [Other; 10]);
+        assert_eq!(categories(PluralRule::OneOther),
+                   [Other, One, Other, Other, Other, Other, Other, Other, Other, Other]);
+        assert_eq!(categories(PluralRule::ZeroAndOneOther),
+                   [One, One, Other, Other, Other, Other, Other, Other, Other, Other]);
+        assert_eq!(categories(PluralRule::EastSlavic),
+                   [Many, One, Few, Many, Many, One, Few, Many, One, Many]);
+        assert_eq!(categories(PluralRule::Polish),
+                   [Many, One, Few, Many, Many, Many, Few, Many, Many, Many]);
+        assert_eq!(categories(PluralRule::CzechSlovak),
+                   [Other, One, Few, Other, Other, Other, Other, Other, Other, Other]);
+        assert_eq!(categories(PluralRule::Arabic),
+                   [Zero, One, Two, Few, Many, Many, Many, Many, Other, Many]);
+    }
+
+    // The teens take Many in the Slavic rules even where their last digit
+    // alone would make them One or Few.
+    #[test]
+    fn teens_are_many() {
+        for count in [11, 12, 13, 14, 112, 1013] {
+            assert_eq!(PluralRule::EastSlavic.category(count), Many, "{}", count);
+            assert_eq!(PluralRule::Polish.category(count), Many, "{}", count);
+        }
+        assert_eq!(PluralRule::EastSlavic.category(1001), One);
+        assert_eq!(PluralRule::Polish.category(1001), Many);
+        assert_eq!(PluralRule::Polish.category(1002), Few);
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use super::{English, Locale, Rounding, Tense, TimeUnit};
+
+// Like RoughTime::from_duration() followed by to_string(), but using up to
+// `precision` units instead of one, so that two hours and a quarter reads
+// "2 hours 15 minutes ago" with a precision of 2 rather than "2 hours ago".
+// The future reads the same way as a RoughTime's: "2 hours 15 minutes from
+// now".
+//
+// The units used are the largest one that fits, as for from_duration(), and
+// the `precision - 1` units below it. The span is rounded to the nearest
+// whole number of the smallest of them, ties going down, and units that come
+// to zero are left out, so 2 hours and 10 seconds is just "2 hours ago". With
+// a precision of 0 or 1, or when only one unit is left, the output is the
+// same as the RoughTime's. For other languages and roundings, use a
+// Humanizer, or Locale::write_precise() with precise_parts().
+pub fn format_precise(duration: Duration, tense: Tense, precision: usize) -> String {
+    let parts = precise_parts(duration, precision, Rounding::Nearest);
+    let mut phrase = String::new();
+    English.write_precise(tense, &parts, &mut phrase)
+        .expect("writing to a String can't fail");
+    phrase
+}
+
+// The units and counts format_precise() writes, largest first and without
+// those that come to zero, with the smallest unit's count rounded as
+// `rounding` says. A span under a second has none. Counts too large for a
+// u32 are capped at u32::MAX.
+pub fn precise_parts(duration: Duration,
+                     precision: usize,
+                     rounding: Rounding) -> Vec<(TimeUnit, u32)> {
+    split(duration.as_nanos(), precision.max(1), rounding)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .map(|(units, count)| (units, u32::try_from(count).unwrap_or(u32::MAX)))
+        .collect()
+}
+
+// Breaks `nanos` down into counts of up to `precision` consecutive units,
+// largest first, or returns None if it's shorter than a second.
+fn split(nanos: u128, precision: usize, rounding: Rounding) -> Option<Vec<(TimeUnit, u128)>> {
+    let top = top_unit(nanos)?;
+    let bottom = (top + 1).saturating_sub(precision);
+
+    // Take whole units off the top, rounding only the last count.
+    let mut rest = nanos;
+    let mut parts = Vec::new();
+    for &units in TimeUnit::ALL[bottom..=top].iter().rev() {
+        let size = units.nanos();
+        if units == TimeUnit::ALL[bottom] {
+            let count = match rounding {
+                // Ties go down.
+                Rounding::Nearest => (rest + (size - 1) / 2) / size,
+                Rounding::Down => rest / size,
+                Rounding::Up => rest.div_ceil(size)
+            };
+            parts.push((units, count));
+        } else {
+            parts.push((units, rest / size));
+            rest %= size;
+        }
+    }
+
+    // Rounding up can leave a count worth a whole one of the unit above it,
+    // as with 59.6 seconds rounding to 60, and that can ripple all the way up.
+    // Carry it, dropping whatever is left over: the units don't all divide
+    // evenly, but the output is only meant to be rough.
+    for i in (0..parts.len()).rev() {
+        let (units, count) = parts[i];
+        let next = match TimeUnit::ALL.get(units as usize + 1) {
+            Some(&next) => next,
+            None => break
+        };
+        if count * units.nanos() < next.nanos() {
+            break;
+        }
+        parts[i].1 = 0;
+        if i == 0 {
+            parts.insert(0, (next, 1));
+            parts.truncate(precision);
+        } else {
+            parts[i - 1].1 += 1;
+        }
+    }
+    Some(parts)
+}
+
+fn top_unit(nanos: u128) -> Option<usize> {
+    TimeUnit::ALL.iter().rposition(|&units| nanos >= units.nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn past_and_future_read_like_a_rough_time() {
+        let span = Duration::from_secs(2 * 3_600 + 15 * 60);
+        assert_eq!(format_precise(span, Tense::Past, 2), "2 hours 15 minutes ago");
+        assert_eq!(format_precise(span, Tense::Future, 2), "2 hours 15 minutes from now");
+        assert_eq!(format_precise(Duration::from_secs(2 * 3_600), Tense::Future, 2), "2 hours from now");
+    }
+}
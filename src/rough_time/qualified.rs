@@ -0,0 +1,26 @@
+use core::fmt;
+
+use super::{Approximation, English, Locale, RoughTime};
+
+// A RoughTime together with how it relates to the exact time it was worked
+// out from. Returned by RoughTime::from_duration_qualified().
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QualifiedTime {
+    pub time: RoughTime,
+    pub approximation: Approximation
+}
+
+// A RoughTime given directly is taken at its word.
+impl From<RoughTime> for QualifiedTime {
+    fn from(time: RoughTime) -> QualifiedTime {
+        QualifiedTime { time, approximation: Approximation::Exact }
+    }
+}
+
+// Writes the time in English with its qualifier: "about 2 hours ago", "over
+// a year ago", "almost 3 days from now".
+impl fmt::Display for QualifiedTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        English.write_qualified(self.time, self.approximation, f)
+    }
+}
@@ -0,0 +1,110 @@
+use core::fmt;
+use core::time::Duration;
+
+use super::{Rounding, RoughTime, Tense, TimeUnit};
+
+// A span of rough times, for when all we know is that something happened
+// somewhere between two points: "between 2 and 3 days ago". The two ends are
+// kept in time order, earliest first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RoughTimeRange {
+    earliest: RoughTime,
+    latest: RoughTime
+}
+
+impl RoughTimeRange {
+    // The range between two rough times, given in either order.
+    pub fn new(a: RoughTime, b: RoughTime) -> RoughTimeRange {
+        if a.offset_nanos() <= b.offset_nanos() {
+            RoughTimeRange { earliest: a, latest: b }
+        } else {
+            RoughTimeRange { earliest: b, latest: a }
+        }
+    }
+
+    // The range reaching `tolerance` either side of `rt`.
+    pub fn around(rt: RoughTime, tolerance: Duration) -> RoughTimeRange {
+        let center = rt.offset_nanos();
+        let tolerance = tolerance.as_nanos() as i128;
+        RoughTimeRange {
+            earliest: RoughTime::from_offset_nanos(center.saturating_sub(tolerance)),
+            latest: RoughTime::from_offset_nanos(center.saturating_add(tolerance))
+        }
+    }
+
+    pub fn earliest(&self) -> RoughTime {
+        self.earliest
+    }
+
+    pub fn latest(&self) -> RoughTime {
+        self.latest
+    }
+}
+
+#[cfg(feature = "std")]
+impl RoughTimeRange {
+    // The range between two timestamps, as seen from `now`.
+    pub fn between(a: std::time::SystemTime,
+                   b: std::time::SystemTime,
+                   now: std::time::SystemTime) -> RoughTimeRange {
+        RoughTimeRange::new(RoughTime::relative_to(a, now), RoughTime::relative_to(b, now))
+    }
+}
+
+impl RoughTime {
+    // How far this time lies from now, in nanoseconds: negative in the past
+    // and positive in the future.
+    pub(super) fn offset_nanos(self) -> i128 {
+        match self {
+            RoughTime::InThePast(units, count) => -((u128::from(count) * units.nanos()) as i128),
+            RoughTime::JustNow => 0,
+            RoughTime::InTheFuture(units, count) => (u128::from(count) * units.nanos()) as i128
+        }
+    }
+
+    fn from_offset_nanos(offset: i128) -> RoughTime {
+        let tense = if offset < 0 { Tense::Past } else { Tense::Future };
+        RoughTime::from_nanos(offset.unsigned_abs(), tense, Rounding::Nearest)
+    }
+}
+
+// Where both ends lie on the same side of now, the phrase is shared, with the
+// end nearer to now coming first: "between 2 and 3 days ago", "between 20
+// hours and 2 days from now". Otherwise each end is written out in full:
+// "between 2 hours ago and 3 hours from now".
+impl fmt::Display for RoughTimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::RoughTime::*;
+
+        match (self.earliest, self.latest) {
+            (earliest, latest) if earliest == latest =>
+                write!(f, "{}", earliest),
+            (InThePast(far_units, far), InThePast(near_units, near)) => {
+                f.write_str("between ")?;
+                write_pair(f, (near_units, near), (far_units, far))?;
+                f.write_str(" ago")
+            }
+            (InTheFuture(near_units, near), InTheFuture(far_units, far)) => {
+                f.write_str("between ")?;
+                write_pair(f, (near_units, near), (far_units, far))?;
+                f.write_str(" from now")
+            }
+            (earliest, latest) =>
+                write!(f, "between {} and {}", earliest, latest)
+        }
+    }
+}
+
+// Writes "2 and 3 days", or "20 hours and 2 days" when the units differ.
+fn write_pair(f: &mut fmt::Formatter,
+              (near_units, near): (TimeUnit, u32),
+              (far_units, far): (TimeUnit, u32)) -> fmt::Result {
+    let name = |units: TimeUnit, count: u32| {
+        if count == 1 { units.singular() } else { units.plural() }
+    };
+    if near_units == far_units {
+        write!(f, "{} and {} {}", near, far, name(far_units, far))
+    } else {
+        write!(f, "{} {} and {} {}", near, name(near_units, near), far, name(far_units, far))
+    }
+}
@@ -0,0 +1,217 @@
+use std::time::{Duration, SystemTime};
+
+use super::{Clock, RoughTime, SystemClock};
+
+impl RoughTime {
+    // How close to now a time has to be, on either side, to count as
+    // JustNow when working from timestamps. Nobody wants to be told something
+    // happened "3 seconds ago".
+    pub const JUST_NOW_WINDOW: Duration = Duration::from_secs(45);
+
+    // Describes `time` relative to `now`, in the past or the future depending
+    // on which side of `now` it falls, and as JustNow if it's within
+    // JUST_NOW_WINDOW of it. Taking `now` as an argument, rather than reading
+    // the clock, lets a whole page of timestamps be described against the
+    // same moment.
+    pub fn relative_to(time: SystemTime, now: SystemTime) -> RoughTime {
+        match now.duration_since(time) {
+            Ok(elapsed) => RoughTime::past_or_just_now(elapsed),
+            Err(err) => RoughTime::future_or_just_now(err.duration())
+        }
+    }
+
+    // relative_to(), as of the time on `clock`. This takes anything that
+    // converts to a SystemTime, so a chrono DateTime or a time
+    // OffsetDateTime can be described against a clock that tests control,
+    // which their From conversions, reading the system clock, can't be.
+    pub fn relative_to_clock(time: impl Into<SystemTime>, clock: &impl Clock) -> RoughTime {
+        RoughTime::relative_to(time.into(), clock.now())
+    }
+
+    // Describes event `a` relative to event `b`: InThePast if `a` happened
+    // first, InTheFuture if it happened after. Unlike relative_to(), there's
+    // no JustNow window, since two events 10 seconds apart are 10 seconds
+    // apart however it would read on a page; only a gap under a second is
+    // JustNow, as with from_duration().
+    pub fn between(a: SystemTime, b: SystemTime) -> RoughTime {
+        match b.duration_since(a) {
+            Ok(before) => RoughTime::in_past(before),
+            Err(err) => RoughTime::in_future(err.duration())
+        }
+    }
+
+    // Describes how long ago `earlier` was, as of now. A timestamp slightly
+    // in the future, within JUST_NOW_WINDOW, which is usually just clock skew
+    // between machines, is treated as JustNow rather than turning into "a
+    // few seconds from now". One further ahead than that isn't skew, and is
+    // described as InTheFuture, as relative_to() would.
+    pub fn since(earlier: SystemTime) -> RoughTime {
+        RoughTime::since_with(earlier, &SystemClock)
+    }
+
+    // since(), as of the time on `clock`.
+    pub fn since_with(earlier: SystemTime, clock: &impl Clock) -> RoughTime {
+        RoughTime::relative_to(earlier, clock.now())
+    }
+
+    // Describes how long it is until `later`, as of now. Once `later` has
+    // passed, this is JustNow, the way a countdown stops at zero.
+    pub fn until(later: SystemTime) -> RoughTime {
+        RoughTime::until_with(later, &SystemClock)
+    }
+
+    // until(), as of the time on `clock`.
+    pub fn until_with(later: SystemTime, clock: &impl Clock) -> RoughTime {
+        match later.duration_since(clock.now()) {
+            Ok(remaining) => RoughTime::future_or_just_now(remaining),
+            Err(_) => RoughTime::JustNow
+        }
+    }
+
+    // The earliest and latest moments, as seen from `now`, that this rough
+    // time could stand for: half a unit either side of the stated count, so
+    // "3 days ago" covers everything from 3.5 days ago to 2.5 days ago, and
+    // JustNow covers JUST_NOW_WINDOW either side of `now`. That's useful for
+    // turning fuzzy input into a query range.
+    //
+    // Panics if the interval reaches past what SystemTime can represent, as
+    // adding to a SystemTime does.
+    pub fn to_interval(&self, now: SystemTime) -> (SystemTime, SystemTime) {
+        let offset = self.offset_nanos();
+        let half_width = match self.units() {
+            Some(units) => units.nanos() / 2,
+            None => RoughTime::JUST_NOW_WINDOW.as_nanos()
+        } as i128;
+        (shift(now, offset - half_width), shift(now, offset + half_width))
+    }
+
+    // How long after `now` it will be until relative_to() describes this
+    // time differently, taking it to be exact as of `now`: "2 minutes from
+    // now" stays that way for half a minute before it becomes "a minute from
+    // now", and "3 days ago" changes to "4 days ago" half a day later. A
+    // front end can sleep for this long before redrawing rather than
+    // polling. Every time changes eventually, since even a future one
+    // passes into the past, except one so far off that the change lies
+    // beyond what SystemTime can represent, for which this is Duration::MAX.
+    pub fn next_change(&self, now: SystemTime) -> Duration {
+        let target = match checked_shift(now, self.offset_nanos()) {
+            Some(target) => target,
+            None => return Duration::MAX
+        };
+        let showing = RoughTime::relative_to(target, now);
+        let shows_same = |wait: Duration| {
+            now.checked_add(wait).map(|later| RoughTime::relative_to(target, later) == showing)
+        };
+
+        // Waiting until the time is a whole unit past where it stands, plus
+        // the JustNow window on either side, is always enough for it to
+        // change.
+        let step = self.units().map_or(Duration::ZERO, |units| units.as_duration(1));
+        let changed = duration_from_nanos(self.offset_nanos().unsigned_abs())
+            .saturating_add(step)
+            .saturating_add(RoughTime::JUST_NOW_WINDOW * 2);
+        if shows_same(changed) != Some(false) {
+            return Duration::MAX;
+        }
+        first_change(changed, |wait| shows_same(wait) == Some(true))
+    }
+
+    pub(super) fn past_or_just_now(elapsed: Duration) -> RoughTime {
+        if elapsed < RoughTime::JUST_NOW_WINDOW {
+            RoughTime::JustNow
+        } else {
+            RoughTime::in_past(elapsed)
+        }
+    }
+
+    pub(super) fn future_or_just_now(remaining: Duration) -> RoughTime {
+        if remaining < RoughTime::JUST_NOW_WINDOW {
+            RoughTime::JustNow
+        } else {
+            RoughTime::in_future(remaining)
+        }
+    }
+}
+
+// The shortest wait after which `shows_same` no longer holds, given that it
+// holds for no wait at all and doesn't after `changed`. What's shown only
+// ever changes one way as time passes, so the waits showing the same thing
+// form one unbroken stretch, and we can binary search for its end.
+pub(super) fn first_change(mut changed: Duration,
+                           shows_same: impl Fn(Duration) -> bool) -> Duration {
+    // `same` is a wait after which the same thing is still shown, and
+    // `changed` one after which it isn't.
+    let mut same = Duration::ZERO;
+    while changed - same > Duration::from_nanos(1) {
+        let middle = same + (changed - same) / 2;
+        if shows_same(middle) {
+            same = middle;
+        } else {
+            changed = middle;
+        }
+    }
+    changed
+}
+
+// Moves `time` by `nanos` nanoseconds, forwards if positive and backwards if
+// negative.
+fn shift(time: SystemTime, nanos: i128) -> SystemTime {
+    checked_shift(time, nanos).expect("interval is outside the range of SystemTime")
+}
+
+// shift(), or None if the result is outside the range of SystemTime.
+fn checked_shift(time: SystemTime, nanos: i128) -> Option<SystemTime> {
+    let span = duration_from_nanos(nanos.unsigned_abs());
+    if nanos < 0 { time.checked_sub(span) } else { time.checked_add(span) }
+}
+
+fn duration_from_nanos(nanos: u128) -> Duration {
+    Duration::new((nanos / 1_000_000_000) as u64, (nanos % 1_000_000_000) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::super::{FixedClock, TimeUnit};
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(86_400);
+
+    #[test]
+    fn times_before_the_epoch() {
+        let now = UNIX_EPOCH - DAY * 365 * 10;
+        assert_eq!(RoughTime::relative_to(now - DAY * 3, now),
+                   RoughTime::InThePast(TimeUnit::Days, 3));
+        assert_eq!(RoughTime::relative_to(UNIX_EPOCH, now),
+                   RoughTime::InTheFuture(TimeUnit::Decades, 1));
+        assert_eq!(RoughTime::between(now, UNIX_EPOCH), RoughTime::InThePast(TimeUnit::Decades, 1));
+
+        let (earliest, latest) = RoughTime::InThePast(TimeUnit::Days, 3).to_interval(now);
+        assert_eq!(earliest, now - DAY * 7 / 2);
+        assert_eq!(latest, now - DAY * 5 / 2);
+    }
+
+    #[test]
+    fn since_forgives_only_a_little_skew() {
+        let clock = FixedClock::new(UNIX_EPOCH + DAY);
+        let now = clock.now();
+        assert_eq!(RoughTime::since_with(now - DAY * 2, &clock),
+                   RoughTime::InThePast(TimeUnit::Days, 2));
+        assert_eq!(RoughTime::since_with(now, &clock), RoughTime::JustNow);
+        let skew = RoughTime::JUST_NOW_WINDOW - Duration::from_secs(1);
+        assert_eq!(RoughTime::since_with(now + skew, &clock), RoughTime::JustNow);
+        assert_eq!(RoughTime::since_with(now + Duration::from_secs(120), &clock),
+                   RoughTime::InTheFuture(TimeUnit::Minutes, 2));
+        assert_eq!(RoughTime::since_with(now + DAY * 3, &clock),
+                   RoughTime::InTheFuture(TimeUnit::Days, 3));
+    }
+
+    #[test]
+    fn next_change_of_a_time_beyond_system_time() {
+        for rt in [RoughTime::InTheFuture(TimeUnit::Centuries, u32::MAX),
+                   RoughTime::InThePast(TimeUnit::Centuries, u32::MAX)] {
+            assert_eq!(rt.next_change(UNIX_EPOCH), Duration::MAX);
+        }
+    }
+}
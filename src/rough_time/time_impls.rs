@@ -0,0 +1,36 @@
+use time::{Duration, OffsetDateTime};
+
+use super::{RoughTime, SystemClock, Tense};
+
+// The same conversions as for chrono, for users of the time crate. A
+// time::Duration is signed: positive spans lie in the future and negative
+// ones in the past.
+impl From<Duration> for RoughTime {
+    fn from(delta: Duration) -> RoughTime {
+        let tense = if delta.is_negative() { Tense::Past } else { Tense::Future };
+        RoughTime::from_duration(delta.unsigned_abs(), tense)
+    }
+}
+
+// Described relative to the current time, like RoughTime::relative_to(), or
+// against any clock with RoughTime::relative_to_clock().
+impl From<OffsetDateTime> for RoughTime {
+    fn from(time: OffsetDateTime) -> RoughTime {
+        RoughTime::relative_to_clock(time, &SystemClock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::super::{FixedClock, TimeUnit};
+    use super::*;
+
+    #[test]
+    fn offset_date_time_relative_to_a_fixed_clock() {
+        let clock = FixedClock::new(UNIX_EPOCH + std::time::Duration::from_secs(1_000_000));
+        let time = OffsetDateTime::from_unix_timestamp(1_000_000 + 5 * 60).unwrap();
+        assert_eq!(RoughTime::relative_to_clock(time, &clock), RoughTime::InTheFuture(TimeUnit::Minutes, 5));
+    }
+}
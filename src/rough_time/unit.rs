@@ -0,0 +1,187 @@
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+
+use super::duration::approximate;
+use super::Rounding;
+
+// The units a RoughTime can be measured in, from smallest to largest. The
+// ordering follows their length, so TimeUnit::Hours < TimeUnit::Days.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+    Decades,
+    Centuries
+}
+
+impl TimeUnit {
+    // Every unit, smallest first.
+    pub const ALL: [TimeUnit; 9] = [
+        TimeUnit::Seconds,
+        TimeUnit::Minutes,
+        TimeUnit::Hours,
+        TimeUnit::Days,
+        TimeUnit::Weeks,
+        TimeUnit::Months,
+        TimeUnit::Years,
+        TimeUnit::Decades,
+        TimeUnit::Centuries
+    ];
+
+    // How long `count` of this unit last. A day is taken to be exactly
+    // 86,400 seconds, ignoring leap seconds and daylight saving, and a week
+    // is seven of them. Months and years vary in length, so they're taken as
+    // round figures: a month is 30 days and a year 365, with decades and
+    // centuries made of those years. That makes "12 months" five days short
+    // of a year, and a count of years drift a day from the calendar every
+    // four years, which is fine for rough times but not for date arithmetic;
+    // use the calendar module for that.
+    pub fn as_duration(self, count: u32) -> Duration {
+        // The longest span this can produce, u32::MAX centuries, still fits
+        // in a u64 of seconds.
+        Duration::from_secs(self.seconds() * u64::from(count))
+    }
+
+    // The unit and count that describe `duration` best, as chosen by
+    // RoughTime::from_duration(): the largest unit that fits at least once,
+    // with the count rounded to nearest. Anything under a second has no
+    // count of any unit to give, so it comes out as None.
+    pub fn best_fit(duration: Duration) -> Option<(TimeUnit, u32)> {
+        approximate(duration.as_nanos(), Rounding::Nearest)
+    }
+
+    // The length of one of this unit in seconds, with the lengths described
+    // at as_duration().
+    pub(crate) fn seconds(self) -> u64 {
+        const DAY: u64 = 24 * 60 * 60;
+        const YEAR: u64 = 365 * DAY;
+        match self {
+            TimeUnit::Seconds => 1,
+            TimeUnit::Minutes => 60,
+            TimeUnit::Hours => 60 * 60,
+            TimeUnit::Days => DAY,
+            TimeUnit::Weeks => 7 * DAY,
+            TimeUnit::Months => 30 * DAY,
+            TimeUnit::Years => YEAR,
+            TimeUnit::Decades => 10 * YEAR,
+            TimeUnit::Centuries => 100 * YEAR
+        }
+    }
+
+    pub(crate) fn nanos(self) -> u128 {
+        u128::from(self.seconds()) * 1_000_000_000
+    }
+
+    // The name of the unit for a count of one: "hour" in "1 hour ago".
+    pub fn singular(self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "second",
+            TimeUnit::Minutes => "minute",
+            TimeUnit::Hours => "hour",
+            TimeUnit::Days => "day",
+            TimeUnit::Weeks => "week",
+            TimeUnit::Months => "month",
+            TimeUnit::Years => "year",
+            TimeUnit::Decades => "decade",
+            TimeUnit::Centuries => "century"
+        }
+    }
+
+    // The name of the unit for any other count: "hours" in "3 hours ago".
+    pub fn plural(self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "seconds",
+            TimeUnit::Minutes => "minutes",
+            TimeUnit::Hours => "hours",
+            TimeUnit::Days => "days",
+            TimeUnit::Weeks => "weeks",
+            TimeUnit::Months => "months",
+            TimeUnit::Years => "years",
+            TimeUnit::Decades => "decades",
+            TimeUnit::Centuries => "centuries"
+        }
+    }
+
+    // A short form of the unit for compact output, as in "3h" or "2mo".
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "s",
+            TimeUnit::Minutes => "m",
+            TimeUnit::Hours => "h",
+            TimeUnit::Days => "d",
+            TimeUnit::Weeks => "w",
+            TimeUnit::Months => "mo",
+            TimeUnit::Years => "y",
+            TimeUnit::Decades => "dec",
+            TimeUnit::Centuries => "c"
+        }
+    }
+}
+
+// A unit displays as its plural name, matching the variant names.
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.plural())
+    }
+}
+
+// Parses either name of a unit, ignoring ASCII case and surrounding
+// whitespace, so "Hour", "hours" and " HOURS " all give TimeUnit::Hours.
+impl FromStr for TimeUnit {
+    type Err = ParseTimeUnitError;
+
+    fn from_str(s: &str) -> Result<TimeUnit, ParseTimeUnitError> {
+        let s = s.trim();
+        TimeUnit::ALL.iter()
+            .cloned()
+            .find(|unit| {
+                s.eq_ignore_ascii_case(unit.singular()) || s.eq_ignore_ascii_case(unit.plural())
+            })
+            .ok_or(ParseTimeUnitError { _priv: () })
+    }
+}
+
+// The error returned when a string doesn't name a TimeUnit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTimeUnitError {
+    _priv: ()
+}
+
+impl fmt::Display for ParseTimeUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("not a recognized time unit")
+    }
+}
+
+impl core::error::Error for ParseTimeUnitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn months_and_years_are_30_and_365_days() {
+        assert_eq!(TimeUnit::Months.as_duration(1), Duration::from_secs(30 * DAY));
+        assert_eq!(TimeUnit::Years.as_duration(2), Duration::from_secs(730 * DAY));
+        assert_eq!(TimeUnit::Centuries.as_duration(1), Duration::from_secs(36_500 * DAY));
+        assert_eq!(TimeUnit::Centuries.as_duration(u32::MAX),
+                   Duration::from_secs(36_500 * DAY * u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn best_fit_has_nothing_under_a_second() {
+        assert_eq!(TimeUnit::best_fit(Duration::ZERO), None);
+        assert_eq!(TimeUnit::best_fit(Duration::from_millis(999)), None);
+        assert_eq!(TimeUnit::best_fit(Duration::from_secs(1)), Some((TimeUnit::Seconds, 1)));
+        assert_eq!(TimeUnit::best_fit(Duration::from_secs(50 * DAY)), Some((TimeUnit::Months, 2)));
+        assert_eq!(TimeUnit::best_fit(Duration::from_secs(365 * DAY)), Some((TimeUnit::Years, 1)));
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use super::{Approximation, Locale, PluralCategory, TimeUnit, Weekday};
+
+// A locale with some of its unit names replaced, for house styles that want
+// "3 hrs ago" or "5 min ago" without writing a whole locale:
+//
+//     let names = UnitNames::new(English)
+//         .unit(TimeUnit::Hours, "hr", "hrs")
+//         .unit(TimeUnit::Minutes, "min", "min");
+//     rough_time_to_locale(rt, &names)   // "3 hrs ago"
+//
+// Each override gives the name for the locale's One category and the name
+// for every other category. An overridden unit is always written with its
+// count as digits, since the locale's words for one ("an hour") were chosen
+// to suit its own names. Everything else comes from the wrapped locale,
+// except that a locale overriding write_rough_time() for its grammar is
+// written with its templates instead.
+pub struct UnitNames {
+    locale: Box<dyn Locale>,
+    names: BTreeMap<TimeUnit, (String, String)>
+}
+
+impl UnitNames {
+    pub fn new<L: Locale + 'static>(locale: L) -> UnitNames {
+        UnitNames::wrap(Box::new(locale))
+    }
+
+    pub(super) fn wrap(locale: Box<dyn Locale>) -> UnitNames {
+        UnitNames { locale, names: BTreeMap::new() }
+    }
+
+    // Uses `one` and `other` as the names of `units`, replacing any names
+    // given for it before.
+    pub fn unit(mut self, units: TimeUnit, one: &str, other: &str) -> UnitNames {
+        self.set(units, one, other);
+        self
+    }
+
+    pub fn set(&mut self, units: TimeUnit, one: &str, other: &str) {
+        self.names.insert(units, (String::from(one), String::from(other)));
+    }
+}
+
+impl Locale for UnitNames {
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        self.locale.plural_category(count)
+    }
+
+    fn unit_name(&self, units: TimeUnit, category: PluralCategory) -> &str {
+        match self.names.get(&units) {
+            Some((one, _)) if category == PluralCategory::One => one,
+            Some((_, other)) => other,
+            None => self.locale.unit_name(units, category)
+        }
+    }
+
+    fn just_now(&self) -> &str {
+        self.locale.just_now()
+    }
+
+    fn past_template(&self) -> &str {
+        self.locale.past_template()
+    }
+
+    fn future_template(&self) -> &str {
+        self.locale.future_template()
+    }
+
+    fn count_word(&self, count: u32, units: TimeUnit, unit: &str) -> Option<&str> {
+        if self.names.contains_key(&units) {
+            return None;
+        }
+        self.locale.count_word(count, units, unit)
+    }
+
+    fn qualifier(&self, approximation: Approximation) -> &str {
+        self.locale.qualifier(approximation)
+    }
+
+    fn yesterday(&self) -> Option<&str> {
+        self.locale.yesterday()
+    }
+
+    fn weekday_name(&self, day: Weekday) -> Option<&str> {
+        self.locale.weekday_name(day)
+    }
+}
@@ -0,0 +1,118 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use super::{Approximation, QualifiedTime, RoughTime, Tense, TimeUnit};
+
+// The error returned when a RoughTime is impossible for what it describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeError {
+    // A birth date that hasn't happened yet.
+    BornInTheFuture(RoughTime)
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TimeError::BornInTheFuture(rt) =>
+                write!(f, "birth date is in the future ({})", rt)
+        }
+    }
+}
+
+impl core::error::Error for TimeError {}
+
+// The error returned by RoughTime::try_new() for a value that shouldn't be
+// built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoughTimeError {
+    // A count of zero, which would read "0 months ago". That's JustNow.
+    ZeroCount
+}
+
+impl fmt::Display for RoughTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RoughTimeError::ZeroCount =>
+                f.write_str("count is zero; use RoughTime::JustNow")
+        }
+    }
+}
+
+impl core::error::Error for RoughTimeError {}
+
+impl RoughTime {
+    // Builds InThePast or InTheFuture, checking that the result makes sense.
+    // The variants can still be built directly; this is for values that come
+    // from outside, such as user input.
+    //
+    // Every nonzero count is accepted: even u32::MAX centuries fits in a
+    // Duration. Whether the time can be placed on the calendar depends on
+    // when it's measured from, so that's left to the conversions to
+    // SystemTime.
+    pub fn try_new(tense: Tense, units: TimeUnit, count: u32) -> Result<RoughTime, RoughTimeError> {
+        if count == 0 {
+            return Err(RoughTimeError::ZeroCount);
+        }
+        Ok(RoughTime::from_parts(tense, units, count))
+    }
+
+    // Builds InThePast or InTheFuture from a count that may not fit in a
+    // u32, such as one worked out from a 64-bit timestamp. A count too large
+    // is capped at u32::MAX and qualified as AtLeast, so it reads "at least
+    // 4294967295 seconds ago" rather than wrapping around to something
+    // recent. A count of zero is JustNow.
+    pub fn saturating_new(tense: Tense, units: TimeUnit, count: u64) -> QualifiedTime {
+        match u32::try_from(count) {
+            Ok(0) => QualifiedTime::from(RoughTime::JustNow),
+            Ok(count) => QualifiedTime::from(RoughTime::from_parts(tense, units, count)),
+            Err(_) => QualifiedTime {
+                time: RoughTime::from_parts(tense, units, u32::MAX),
+                approximation: Approximation::AtLeast
+            }
+        }
+    }
+}
+
+// The chapter's
+//
+//     if let RoughTime::InTheFuture(_, _) = user.date_of_birth() {
+//         user.set_time_traveler(true);
+//     }
+//
+// as a check for input validation. Someone born just now is fine; someone
+// born in the future is not.
+pub fn validate_birth_date(date_of_birth: RoughTime) -> Result<(), TimeError> {
+    if date_of_birth.is_future() {
+        return Err(TimeError::BornInTheFuture(date_of_birth));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_new_rejects_only_a_zero_count() {
+        assert_eq!(RoughTime::try_new(Tense::Past, TimeUnit::Days, 0), Err(RoughTimeError::ZeroCount));
+        for units in TimeUnit::ALL {
+            assert_eq!(RoughTime::try_new(Tense::Future, units, u32::MAX),
+                       Ok(RoughTime::InTheFuture(units, u32::MAX)));
+        }
+    }
+
+    #[test]
+    fn saturating_new_caps_counts_beyond_u32() {
+        let exact = RoughTime::saturating_new(Tense::Past, TimeUnit::Seconds, u64::from(u32::MAX));
+        assert_eq!(exact.time, RoughTime::InThePast(TimeUnit::Seconds, u32::MAX));
+        assert_eq!(exact.approximation, Approximation::Exact);
+
+        for count in [u64::from(u32::MAX) + 1, u64::MAX] {
+            let capped = RoughTime::saturating_new(Tense::Future, TimeUnit::Centuries, count);
+            assert_eq!(capped.time, RoughTime::InTheFuture(TimeUnit::Centuries, u32::MAX));
+            assert_eq!(capped.approximation, Approximation::AtLeast);
+        }
+        let zero = RoughTime::saturating_new(Tense::Past, TimeUnit::Days, 0);
+        assert_eq!(zero.time, RoughTime::JustNow);
+    }
+}
@@ -0,0 +1,180 @@
+// JavaScript bindings, behind the `wasm` feature, so a browser front end can
+// describe times with the same code as the server and the two never
+// disagree about when "an hour ago" becomes "2 hours ago".
+//
+// Timestamps cross the boundary the way JavaScript keeps them, as
+// milliseconds since the Unix epoch (what Date.now() returns), and every
+// function that needs the current time takes it that way too. The clock
+// can't be read from here: SystemTime::now() panics on wasm32-unknown-unknown.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use wasm_bindgen::prelude::*;
+
+use super::{English, Humanizer, HumanizerBuilder, Rounding, RoughTime};
+
+// A RoughTime, seen from JavaScript as a class with the same name.
+#[wasm_bindgen(js_name = RoughTime)]
+pub struct JsRoughTime(RoughTime);
+
+#[wasm_bindgen(js_class = RoughTime)]
+impl JsRoughTime {
+    // RoughTime::relative_to().
+    #[wasm_bindgen(js_name = relativeTo)]
+    pub fn relative_to(time_ms: f64, now_ms: f64) -> JsRoughTime {
+        JsRoughTime(RoughTime::relative_to(system_time(time_ms), system_time(now_ms)))
+    }
+
+    // RoughTime::between().
+    pub fn between(a_ms: f64, b_ms: f64) -> JsRoughTime {
+        JsRoughTime(RoughTime::between(system_time(a_ms), system_time(b_ms)))
+    }
+
+    // RoughTime::from_iso8601(), throwing on a duration it can't represent.
+    #[wasm_bindgen(js_name = fromIso8601)]
+    pub fn from_iso8601(s: &str) -> Result<JsRoughTime, JsError> {
+        RoughTime::from_iso8601(s).map(JsRoughTime).map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = isPast)]
+    pub fn is_past(&self) -> bool {
+        self.0.is_past()
+    }
+
+    #[wasm_bindgen(js_name = isFuture)]
+    pub fn is_future(&self) -> bool {
+        self.0.is_future()
+    }
+
+    #[wasm_bindgen(js_name = toIso8601)]
+    pub fn to_iso8601(&self) -> String {
+        self.0.to_iso8601()
+    }
+
+    // RoughTime::next_change(), in milliseconds, ready for setTimeout().
+    #[wasm_bindgen(js_name = nextChange)]
+    pub fn next_change(&self, now_ms: f64) -> f64 {
+        self.0.next_change(system_time(now_ms)).as_secs_f64() * 1000.0
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[wasm_bindgen(js_name = roughTimeToEnglish)]
+pub fn rough_time_to_english(rt: &JsRoughTime) -> String {
+    super::rough_time_to_english(rt.0)
+}
+
+#[wasm_bindgen(js_name = Humanizer)]
+pub struct JsHumanizer(Humanizer);
+
+#[wasm_bindgen(js_class = Humanizer)]
+impl JsHumanizer {
+    // A humanizer with the defaults, as Humanizer::default().
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsHumanizer {
+        JsHumanizer(Humanizer::default())
+    }
+
+    pub fn builder() -> JsHumanizerBuilder {
+        JsHumanizerBuilder(Humanizer::builder())
+    }
+
+    // Humanizer::humanize_at().
+    pub fn humanize(&self, time_ms: f64, now_ms: f64) -> String {
+        self.0.humanize_at(system_time(time_ms), system_time(now_ms))
+    }
+}
+
+impl Default for JsHumanizer {
+    fn default() -> JsHumanizer {
+        JsHumanizer::new()
+    }
+}
+
+// HumanizerBuilder, chained from JavaScript the same way:
+//
+//     const humanizer = Humanizer.builder().rounding("down").qualifiers(true).build();
+#[wasm_bindgen(js_name = HumanizerBuilder)]
+pub struct JsHumanizerBuilder(HumanizerBuilder);
+
+#[wasm_bindgen(js_class = HumanizerBuilder)]
+impl JsHumanizerBuilder {
+    // The language for full phrases, by its tag: "en", and with the
+    // `locales` feature, "es", "fr" and "de".
+    pub fn locale(self, tag: &str) -> Result<JsHumanizerBuilder, JsError> {
+        let builder = match tag {
+            "en" => self.0.locale(English),
+            #[cfg(feature = "locales")]
+            "es" => self.0.locale(super::Spanish),
+            #[cfg(feature = "locales")]
+            "fr" => self.0.locale(super::French),
+            #[cfg(feature = "locales")]
+            "de" => self.0.locale(super::German),
+            _ => return Err(JsError::new(&format!("unsupported locale {:?}", tag)))
+        };
+        Ok(JsHumanizerBuilder(builder))
+    }
+
+    pub fn long(self) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.long())
+    }
+
+    pub fn short(self) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.short())
+    }
+
+    #[wasm_bindgen(js_name = justNow)]
+    pub fn just_now(self, window_ms: f64) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.just_now(duration(window_ms)))
+    }
+
+    // The rounding mode by name: "nearest", "down" or "up".
+    pub fn rounding(self, rounding: &str) -> Result<JsHumanizerBuilder, JsError> {
+        let rounding = match rounding {
+            "nearest" => Rounding::Nearest,
+            "down" => Rounding::Down,
+            "up" => Rounding::Up,
+            _ => return Err(JsError::new(&format!("unknown rounding {:?}", rounding)))
+        };
+        Ok(JsHumanizerBuilder(self.0.rounding(rounding)))
+    }
+
+    pub fn qualifiers(self, qualifiers: bool) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.qualifiers(qualifiers))
+    }
+
+    pub fn precision(self, precision: usize) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.precision(precision))
+    }
+
+    // The UTC offset here is in minutes, the unit of JavaScript's
+    // getTimezoneOffset(), though with the opposite sign: minutes ahead of
+    // UTC, so -new Date().getTimezoneOffset().
+    #[wasm_bindgen(js_name = calendarDays)]
+    pub fn calendar_days(self, utc_offset_minutes: i32) -> JsHumanizerBuilder {
+        JsHumanizerBuilder(self.0.calendar_days(utc_offset_minutes.saturating_mul(60)))
+    }
+
+    pub fn build(self) -> JsHumanizer {
+        JsHumanizer(self.0.build())
+    }
+}
+
+// The moment `ms` milliseconds after the Unix epoch, or before it if
+// negative. Values outside SystemTime's range are clamped to the epoch
+// rather than panicking across the boundary.
+fn system_time(ms: f64) -> SystemTime {
+    let offset = duration(ms.abs());
+    let time = if ms < 0.0 { UNIX_EPOCH.checked_sub(offset) } else { UNIX_EPOCH.checked_add(offset) };
+    time.unwrap_or(UNIX_EPOCH)
+}
+
+// A span of `ms` milliseconds, with NaN and negative spans as zero and ones
+// too long for a Duration as Duration::MAX.
+fn duration(ms: f64) -> Duration {
+    Duration::try_from_secs_f64(ms.max(0.0) / 1000.0).unwrap_or(Duration::MAX)
+}
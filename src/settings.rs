@@ -0,0 +1,176 @@
+// The `settings` object from the chapter's calendar example:
+//
+//     let calendar =
+//         match settings.get_string("calendar") {
+//             "gregorian" => Calendar::Gregorian,
+//             ...
+//         };
+//
+// Settings come in layers, each overriding the ones below it: the defaults
+// at the bottom, then whatever a program adds on top, say a system-wide
+// file, then the user's own file, then the command line. The defaults also
+// say which settings exist, so a misspelled key in a file or in the code is
+// reported rather than silently ignored.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Settings {
+    // layers[0] is the defaults; later layers override earlier ones.
+    layers: Vec<BTreeMap<String, String>>
+}
+
+impl Settings {
+    // Settings with the given defaults, which are also the complete list of
+    // keys that can be set or read.
+    pub fn new<I, K, V>(defaults: I) -> Settings
+        where I: IntoIterator<Item = (K, V)>, K: Into<String>, V: Into<String>
+    {
+        let defaults = defaults.into_iter().map(|(key, value)| (key.into(), value.into())).collect();
+        Settings { layers: vec![defaults] }
+    }
+
+    // Adds a layer of values on top of the existing ones. If any key isn't
+    // one of the defaults, nothing is added and the first such key is
+    // reported.
+    pub fn add_layer<I, K, V>(&mut self, values: I) -> Result<(), SettingsError>
+        where I: IntoIterator<Item = (K, V)>, K: Into<String>, V: Into<String>
+    {
+        let mut layer = BTreeMap::new();
+        for (key, value) in values {
+            let key = key.into();
+            if !self.layers[0].contains_key(&key) {
+                return Err(SettingsError::UnknownKey(key));
+            }
+            layer.insert(key, value.into());
+        }
+        self.layers.push(layer);
+        Ok(())
+    }
+
+    // The value of `key` from the topmost layer that sets it.
+    pub fn get_string(&self, key: &str) -> Result<&str, SettingsError> {
+        self.layers.iter()
+            .rev()
+            .find_map(|layer| layer.get(key))
+            .map(String::as_str)
+            .ok_or_else(|| SettingsError::UnknownKey(String::from(key)))
+    }
+
+    // The value of `key`, parsed:
+    //
+    //     let calendar: Calendar = settings.get_parsed("calendar")?;
+    pub fn get_parsed<T>(&self, key: &str) -> Result<T, SettingsError>
+        where T: FromStr, T::Err: core::error::Error + Send + Sync + 'static
+    {
+        let value = self.get_string(key)?;
+        value.parse().map_err(|err| SettingsError::InvalidValue {
+            key: String::from(key),
+            value: String::from(value),
+            source: Box::new(err)
+        })
+    }
+
+    // Every key there is, in sorted order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.layers[0].keys().map(String::as_str)
+    }
+}
+
+// The error returned when a setting can't be read or set.
+#[derive(Debug)]
+pub enum SettingsError {
+    // A key that isn't one of the defaults.
+    UnknownKey(String),
+    // A value that doesn't parse as the type asked for.
+    InvalidValue {
+        key: String,
+        value: String,
+        source: Box<dyn core::error::Error + Send + Sync>
+    }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SettingsError::UnknownKey(ref key) =>
+                write!(f, "unknown setting {:?}", key),
+            SettingsError::InvalidValue { ref key, ref value, ref source } =>
+                write!(f, "invalid value {:?} for setting {:?}: {}", value, key, source)
+        }
+    }
+}
+
+impl core::error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match *self {
+            SettingsError::UnknownKey(_) => None,
+            SettingsError::InvalidValue { ref source, .. } => Some(&**source)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::calendar::Calendar;
+
+    // The chapter's example as written, with its parse_error() standing in as
+    // an error of our own.
+    fn calendar(settings: &Settings) -> Result<Calendar, String> {
+        let calendar =
+            match settings.get_string("calendar").map_err(|err| err.to_string())? {
+                "gregorian" => Calendar::Gregorian,
+                "chinese" => Calendar::Chinese,
+                "ethiopian" => Calendar::Ethiopian,
+                other => return Err(format!("bad calendar setting {:?}", other))
+            };
+        Ok(calendar)
+    }
+
+    fn layered() -> Settings {
+        let mut settings = Settings::new([("calendar", "gregorian"), ("week_start", "monday")]);
+        settings.add_layer([("calendar", "chinese")]).unwrap();
+        settings
+    }
+
+    #[test]
+    fn chapter_example_reads_the_top_layer() {
+        let mut settings = Settings::new([("calendar", "gregorian")]);
+        assert_eq!(calendar(&settings), Ok(Calendar::Gregorian));
+        settings.add_layer([("calendar", "ethiopian")]).unwrap();
+        assert_eq!(calendar(&settings), Ok(Calendar::Ethiopian));
+
+        let settings = layered();
+        assert_eq!(calendar(&settings), Ok(Calendar::Chinese));
+        assert_eq!(settings.get_string("week_start").unwrap(), "monday");
+        assert_eq!(settings.get_parsed::<Calendar>("calendar").unwrap(), Calendar::Chinese);
+    }
+
+    #[test]
+    fn chapter_example_reports_a_bad_value() {
+        let mut settings = layered();
+        settings.add_layer([("calendar", "mayan")]).unwrap();
+        assert_eq!(calendar(&settings), Err(String::from("bad calendar setting \"mayan\"")));
+        assert!(matches!(settings.get_parsed::<Calendar>("calendar"),
+                         Err(SettingsError::InvalidValue { .. })));
+    }
+
+    #[test]
+    fn unknown_keys_are_errors() {
+        let mut settings = layered();
+        assert!(matches!(settings.add_layer([("calender", "julian")]),
+                         Err(SettingsError::UnknownKey(ref key)) if key == "calender"));
+        assert_eq!(calendar(&settings), Ok(Calendar::Chinese));
+        assert!(matches!(settings.get_string("calender"), Err(SettingsError::UnknownKey(_))));
+    }
+}